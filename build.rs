@@ -0,0 +1,93 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Embed the pristine exercises, so that `rustlings reset` can restore them without git.
+// info.toml is embedded as well to restore it if it was deleted.
+fn collect(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+}
+
+// `None` if git isn't installed
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    assert!(
+        output.status.success(),
+        "git {} failed: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Some(String::from_utf8(output.stdout).expect("The exercises are UTF-8"))
+}
+
+// In a git checkout, the exercises are embedded from the last commit, so that solving them
+// in the repository before building doesn't turn the solutions into the pristine copies.
+fn committed() -> Option<(Vec<(String, String)>, String)> {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/index");
+    if !git(&["status", "--porcelain", "--", "exercises", "info.toml"])?.is_empty() {
+        println!(
+            "cargo:warning=The exercises have uncommitted changes, which aren't embedded as their pristine copies"
+        );
+    }
+
+    let exercises = git(&["ls-tree", "-r", "--name-only", "HEAD", "exercises"])?
+        .lines()
+        .filter(|path| path.ends_with(".rs"))
+        .map(|path| Some((path.to_string(), git(&["show", &format!("HEAD:./{path}")])?)))
+        .collect::<Option<_>>()?;
+    Some((exercises, git(&["show", "HEAD:./info.toml"])?))
+}
+
+// Outside of a git checkout, e.g. in the published crate, or without git,
+// the files are embedded as they are.
+fn working_tree() -> (Vec<(String, String)>, String) {
+    let mut files = Vec::new();
+    collect(Path::new("exercises"), &mut files);
+    files.sort();
+
+    let exercises = files
+        .into_iter()
+        .map(|file| {
+            let path = file
+                .to_str()
+                .expect("exercise paths are UTF-8")
+                .replace('\\', "/");
+            (path, fs::read_to_string(&file).unwrap())
+        })
+        .collect();
+    (exercises, fs::read_to_string("info.toml").unwrap())
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=exercises");
+    println!("cargo:rerun-if-changed=info.toml");
+
+    let committed = if Path::new(".git").exists() {
+        committed()
+    } else {
+        None
+    };
+    let (exercises, info_toml) = committed.unwrap_or_else(working_tree);
+
+    let mut out = String::from("pub const EXERCISES: &[(&str, &str)] = &[\n");
+    for (path, content) in exercises {
+        out.push_str(&format!("    ({path:?}, {content:?}),\n"));
+    }
+    out.push_str("];\n");
+    out.push_str(&format!("pub const INFO_TOML: &str = {info_toml:?};\n"));
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("pristine.rs"), out).unwrap();
+}
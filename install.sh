@@ -3,6 +3,22 @@ set -euo pipefail
 
 echo -e "\nLet's get you set up with Rustlings!"
 
+# `install.sh --offline <archive> [path]` installs from a local archive
+# containing vendored dependencies instead of downloading anything.
+Offline=""
+if [[ ${1:-} == "--offline" ]]
+then
+    Offline=${2:-}
+    if [[ -z ${Offline} || ! -f ${Offline} ]]
+    then
+        echo "ERROR: --offline needs the path to a Rustlings archive"
+        echo "Usage: install.sh --offline rustlings-offline.tar.gz [path]"
+        exit 1
+    fi
+    Offline="$(cd "$(dirname "$Offline")" && pwd)/$(basename "$Offline")"
+    shift 2
+fi
+
 echo "Checking requirements..."
 if [ -x "$(command -v git)" ]
 then
@@ -124,7 +140,7 @@ function vercomp() {
 }
 
 RustVersion=$(rustc --version | cut -d " " -f 2)
-MinRustVersion=1.70
+MinRustVersion=1.74
 vercomp "$RustVersion" $MinRustVersion || ec=$?
 if [ ${ec:-0} -eq 2 ]
 then
@@ -135,15 +151,117 @@ else
     echo "SUCCESS: Rust is up to date"
 fi
 
+# Copies the files of the Rustlings checkout in $1 into the non-empty directory $2.
+# Asks whether to skip, overwrite or back up each file that already exists.
+# The .git directory of the checkout is moved as a whole, unless $2 is a git repository already:
+# Merging the objects and refs of two repositories file by file would corrupt both.
+function merge_into() {
+    local Choice="" File
+    (cd "$1" && find . -path ./.git -prune -o -path ./target -prune -o -type f -print) |
+    while IFS= read -r File
+    do
+        File=${File#./}
+        if [ -e "$2/$File" ]
+        then
+            if cmp -s "$1/$File" "$2/$File"
+            then
+                continue
+            fi
+            local Action=$Choice
+            if [ -z "$Action" ]
+            then
+                echo "$File already exists."
+                read -r -p "[s]kip, [o]verwrite, [b]ackup (uppercase to apply to all remaining files)? " Action < /dev/tty || Action=""
+                case $Action in
+                    S|O|B) Choice=$Action ;;
+                esac
+            fi
+            case $Action in
+                o|O) ;;
+                b|B) mv "$2/$File" "$2/$File.bak"
+                     echo "Moved the existing $File to $File.bak" ;;
+                *) continue ;;
+            esac
+        fi
+        mkdir -p "$(dirname "$2/$File")"
+        cp "$1/$File" "$2/$File"
+    done
+
+    if [ -d "$1/.git" ] && ! [ -e "$2/.git" ]
+    then
+        mv "$1/.git" "$2/.git"
+    elif [ -d "$1/.git" ]
+    then
+        echo "$2 is a git repository already, so the history of Rustlings wasn't added to it."
+    fi
+}
+
 Path=${1:-rustlings/}
+CargoBin="${CARGO_HOME:-$HOME/.cargo}/bin"
+
+# Rustlings is set up in a temporary directory first if the target already contains files.
+Checkout=$Path
+if [ -d "$Path" ] && [ -n "$(ls -A "$Path")" ]
+then
+    echo "$Path is not empty. Rustlings will be added next to the existing files."
+    Checkout=$(mktemp -d)
+    Path=$(cd "$Path" && pwd)
+fi
+
+if [[ -n ${Offline} ]]
+then
+    echo "Unpacking Rustlings from $Offline at $Path..."
+    mkdir -p "$Checkout"
+    tar -xzf "$Offline" -C "$Checkout" --strip-components=1
+    cd "$Checkout"
+
+    if ! [ -d vendor ]
+    then
+        echo "ERROR: The archive doesn't contain vendored dependencies."
+        echo "Please create it with 'cargo vendor' as described in the README."
+        exit 1
+    fi
+
+    mkdir -p .cargo
+    printf '%s\n' \
+        '[source.crates-io]' \
+        'replace-with = "vendored-sources"' \
+        '' \
+        '[source.vendored-sources]' \
+        'directory = "vendor"' > .cargo/config.toml
+
+    echo "Installing the 'rustlings' executable..."
+    cargo install --locked --offline --force --path .
+
+    if ! [ -x "$(command -v rustlings)" ]
+    then
+        echo "WARNING: Please check that you have '$CargoBin' in your PATH environment variable!"
+    fi
+
+    # Clippy can't be downloaded without network access.
+    Clippy=$(rustup component list | grep "clippy" | grep "installed" || true)
+    if [ -z "$Clippy" ]
+    then
+        echo "WARNING: Clippy is not installed and cannot be installed offline."
+        echo "The Clippy exercises will fail until you run 'rustup component add clippy'."
+    fi
+
+    if [[ $Checkout != "$Path" ]]
+    then
+        merge_into "$Checkout" "$Path"
+        rm -rf "$Checkout"
+    fi
+
+    echo "All done! Run 'rustlings' to get started."
+    exit 0
+fi
+
 echo "Cloning Rustlings at $Path..."
-git clone -q https://github.com/rust-lang/rustlings.git "$Path"
+git clone -q https://github.com/rust-lang/rustlings.git "$Checkout"
 
-cd "$Path"
+cd "$Checkout"
 
 Version=$(curl -s https://api.github.com/repos/rust-lang/rustlings/releases/latest | ${PY} -c "import json,sys;obj=json.load(sys.stdin);print(obj['tag_name']) if 'tag_name' in obj else sys.exit(f\"Error: {obj['message']}\");")
-CargoBin="${CARGO_HOME:-$HOME/.cargo}/bin"
-
 if [[ -z ${Version} ]]
 then
     echo "The latest tag version could not be fetched remotely."
@@ -181,4 +299,10 @@ then
     rustup component add clippy
 fi
 
+if [[ $Checkout != "$Path" ]]
+then
+    merge_into "$Checkout" "$Path"
+    rm -rf "$Checkout"
+fi
+
 echo "All done! Run 'rustlings' to get started."
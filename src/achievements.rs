@@ -0,0 +1,137 @@
+use console::style;
+use std::collections::BTreeMap;
+
+use crate::exercise::Exercise;
+use crate::state_file::{self, StateFile};
+
+const DAY: u64 = 24 * 60 * 60;
+
+pub enum Achievement {
+    FirstExercise,
+    TopicCompleted(String),
+    TenInOneDay,
+    FinishedWithoutHints,
+}
+
+impl Achievement {
+    // The key of the achievement in the state file
+    fn id(&self) -> String {
+        match self {
+            Self::FirstExercise => String::from("first_exercise"),
+            Self::TopicCompleted(topic) => format!("topic_{topic}"),
+            Self::TenInOneDay => String::from("ten_in_one_day"),
+            Self::FinishedWithoutHints => String::from("finished_without_hints"),
+        }
+    }
+
+    fn title(&self) -> String {
+        match self {
+            Self::FirstExercise => String::from("First steps"),
+            Self::TopicCompleted(topic) => format!("Master of {topic}"),
+            Self::TenInOneDay => String::from("On a roll"),
+            Self::FinishedWithoutHints => String::from("No hints needed"),
+        }
+    }
+
+    fn description(&self) -> String {
+        match self {
+            Self::FirstExercise => String::from("Complete your first exercise"),
+            Self::TopicCompleted(topic) => format!("Complete all exercises of the topic {topic}"),
+            Self::TenInOneDay => String::from("Complete 10 exercises in one day"),
+            Self::FinishedWithoutHints => {
+                String::from("Complete all exercises without looking at a hint")
+            }
+        }
+    }
+
+    // All achievements that can be unlocked in the course
+    fn all(exercises: &[Exercise]) -> Vec<Self> {
+        let mut topics: Vec<String> = Vec::new();
+        for exercise in exercises.iter().filter(|e| !e.optional) {
+            let topic = exercise.topic();
+            if !topic.is_empty() && !topics.iter().any(|t| t == topic) {
+                topics.push(topic.to_string());
+            }
+        }
+
+        let mut all = vec![Self::FirstExercise];
+        all.extend(topics.into_iter().map(Self::TopicCompleted));
+        all.extend([Self::TenInOneDay, Self::FinishedWithoutHints]);
+        all
+    }
+
+    fn is_reached(&self, exercises: &[Exercise], state: &StateFile) -> bool {
+        let required = || exercises.iter().filter(|e| !e.optional);
+        match self {
+            Self::FirstExercise => state.exercises.values().any(|r| r.completed_at.is_some()),
+            Self::TopicCompleted(topic) => required()
+                .filter(|e| e.topic() == topic)
+                .all(|e| e.looks_done(state)),
+            Self::TenInOneDay => {
+                let mut per_day = BTreeMap::new();
+                for completed_at in state.exercises.values().filter_map(|r| r.completed_at) {
+                    *per_day.entry(completed_at / DAY).or_insert(0) += 1;
+                }
+                per_day.values().any(|&count| count >= 10)
+            }
+            Self::FinishedWithoutHints => {
+                state.exercises.values().all(|r| r.hints_viewed == 0)
+                    && required().all(|e| e.looks_done(state))
+            }
+        }
+    }
+
+    fn toast(&self) {
+        let message = format!("Achievement unlocked: {}", self.title());
+        if crate::ui::no_emoji() {
+            notice!("{} {}", style("*").yellow(), style(message).yellow().bold());
+        } else {
+            notice!(
+                "{} {}",
+                style("🏆").yellow(),
+                style(message).yellow().bold()
+            );
+        }
+    }
+}
+
+// Record the newly unlocked achievements and announce them.
+pub fn unlock_new(exercises: &[Exercise]) {
+    let mut unlocked = Vec::new();
+    state_file::update(|state| {
+        for achievement in Achievement::all(exercises) {
+            if !state.achievements.contains_key(&achievement.id())
+                && achievement.is_reached(exercises, state)
+            {
+                state
+                    .achievements
+                    .insert(achievement.id(), state_file::now());
+                unlocked.push(achievement);
+            }
+        }
+    });
+
+    for achievement in unlocked {
+        achievement.toast();
+    }
+}
+
+// List the unlocked and locked achievements.
+pub fn list(exercises: &[Exercise]) {
+    let state = StateFile::load().unwrap_or_default();
+    let all = Achievement::all(exercises);
+    let unlocked = all
+        .iter()
+        .filter(|a| state.achievements.contains_key(&a.id()))
+        .count();
+
+    for achievement in &all {
+        let title = if state.achievements.contains_key(&achievement.id()) {
+            style(achievement.title()).green().bold()
+        } else {
+            style(achievement.title()).dim()
+        };
+        println!("{title:<30}\t{}", achievement.description());
+    }
+    println!("Unlocked {unlocked} / {} achievements.", all.len());
+}
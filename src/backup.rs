@@ -0,0 +1,70 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::exercise::Exercise;
+use crate::state_file;
+use crate::undo;
+
+// Copies of the exercises from before they were reset. Unlike the undo entries,
+// they are never dropped, so that significant work can't get lost.
+pub const BACKUP_DIR: &str = ".rustlings-backups";
+
+fn dir() -> PathBuf {
+    state_file::progress_path(BACKUP_DIR)
+}
+
+// The backups of the exercise like `vecs1-1700000000.rs`, the oldest first
+fn backups(exercise: &Exercise) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir()) else {
+        return Vec::new();
+    };
+    let prefix = format!("{}-", exercise.name);
+    let mut backups = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter_map(|path| {
+            let timestamp = path
+                .file_stem()?
+                .to_str()?
+                .strip_prefix(&prefix)?
+                .parse::<u64>()
+                .ok()?;
+            Some((timestamp, path))
+        })
+        .collect::<Vec<_>>();
+    backups.sort();
+    backups.into_iter().map(|(_, path)| path).collect()
+}
+
+// Copy the current version of the exercise into the backup directory.
+pub fn backup(exercise: &Exercise) -> Result<PathBuf> {
+    let dir = dir();
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    // Timestamps are unique unless the exercise is backed up twice in the same second.
+    let mut timestamp = state_file::now();
+    let path = loop {
+        let path = dir.join(format!("{}-{timestamp}.rs", exercise.name));
+        if !path.exists() {
+            break path;
+        }
+        timestamp += 1;
+    };
+    fs::copy(&exercise.path, &path)
+        .with_context(|| format!("Failed to back up {exercise} to {}", path.display()))?;
+    Ok(path)
+}
+
+// Bring back the latest backup of the exercise. The current version can be restored with `rustlings undo`.
+pub fn restore(exercise: &Exercise) -> Result<()> {
+    let Some(latest) = backups(exercise).pop() else {
+        bail!("There are no backups of {exercise} in {}", dir().display());
+    };
+    undo::push(&format!("restore {exercise}"), &[exercise]).context(
+        "Not restoring the backup because the current version couldn't be saved for undo",
+    )?;
+    fs::copy(&latest, &exercise.path)
+        .with_context(|| format!("Failed to restore {}", latest.display()))?;
+    println!("Restored {exercise} from {}", latest.display());
+    Ok(())
+}
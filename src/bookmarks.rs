@@ -0,0 +1,46 @@
+use crate::exercise::Exercise;
+use crate::state_file::{self, StateFile};
+
+// Bookmark the exercise or remove its bookmark if `remove` is set.
+pub fn bookmark(exercise: &Exercise, remove: bool) {
+    set_bookmark(exercise, remove);
+    if remove {
+        println!("Removed the bookmark of {}.", exercise.name);
+    } else {
+        println!("Bookmarked {}.", exercise.name);
+    }
+}
+
+// Like `bookmark`, but without printing anything, e.g. for the interactive list
+pub fn set_bookmark(exercise: &Exercise, remove: bool) {
+    state_file::update(|state| {
+        if remove {
+            state.bookmarks.remove(&exercise.name);
+        } else {
+            state.bookmarks.insert(exercise.name.clone());
+        }
+    });
+}
+
+pub fn is_bookmarked(exercise: &Exercise, state: &StateFile) -> bool {
+    state.bookmarks.contains(&exercise.name)
+}
+
+// List the bookmarked exercises in the order of the course.
+pub fn bookmarks(exercises: &[Exercise]) {
+    let state = StateFile::load().unwrap_or_default();
+    let bookmarked = exercises
+        .iter()
+        .filter(|e| is_bookmarked(e, &state))
+        .collect::<Vec<_>>();
+
+    if bookmarked.is_empty() {
+        println!(
+            "You didn't bookmark any exercises yet. Bookmark one with `rustlings bookmark <name>`."
+        );
+        return;
+    }
+    for exercise in bookmarked {
+        println!("{:<17}\t{exercise}", exercise.name);
+    }
+}
@@ -0,0 +1,209 @@
+use anyhow::{bail, Result};
+use indicatif::{ProgressBar, ProgressStyle};
+use notify_debouncer_mini::notify::RecursiveMode;
+use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsStr;
+use std::path::Path;
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use crate::exercise::{check_topic, Exercise};
+use crate::run::reset;
+use crate::state_file::{self, StateFile};
+use crate::verify::verify;
+
+// The outcome of a finished challenge
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ChallengeResult {
+    pub topic: String,
+    pub minutes: u64,
+    // The number of exercises completed in time
+    pub completed: usize,
+    pub total: usize,
+    // How long it took to complete the exercises
+    pub seconds: u64,
+    // Unix timestamp of when the challenge ended
+    pub finished_at: u64,
+}
+
+impl ChallengeResult {
+    // More completed exercises are better, then less time
+    fn beats(&self, other: &Self) -> bool {
+        (self.completed, other.seconds) > (other.completed, self.seconds)
+    }
+}
+
+// Solve the pending exercises of a topic before the time runs out.
+// If `reset_done` is set, the completed exercises of the topic are reset to be solved again.
+pub fn challenge(
+    exercises: &[Exercise],
+    topic: &str,
+    minutes: u64,
+    reset_done: bool,
+    verbose: bool,
+) -> Result<()> {
+    // The exercises at the root of `exercises/` have an empty topic, which isn't one to choose.
+    check_topic(topic, exercises).map_err(anyhow::Error::msg)?;
+    let topic_exercises = exercises
+        .iter()
+        .filter(|e| e.topic() == topic)
+        .collect::<Vec<_>>();
+
+    if reset_done {
+        let state = StateFile::load().unwrap_or_default();
+        for exercise in topic_exercises.iter().filter(|e| e.looks_done(&state)) {
+            if reset(exercise).is_err() {
+                bail!("Failed to reset {exercise}");
+            }
+        }
+    }
+
+    let state = StateFile::load().unwrap_or_default();
+    let challenge_exercises = topic_exercises
+        .into_iter()
+        .filter(|e| !e.looks_done(&state))
+        .collect::<Vec<_>>();
+    let total = challenge_exercises.len();
+    if total == 0 {
+        bail!("All exercises of the topic '{topic}' are done. Use --reset to solve them again.");
+    }
+
+    println!(
+        "Challenge: Solve {total} exercises of the topic '{topic}' in {minutes} minutes. Good luck!"
+    );
+
+    let (tx, rx) = channel();
+    let mut debouncer = new_debouncer(Duration::from_secs(1), tx)?;
+    debouncer
+        .watcher()
+        .watch(Path::new("./exercises"), RecursiveMode::Recursive)?;
+
+    let time_limit = Duration::from_secs(minutes * 60);
+    let start = Instant::now();
+    let verify_challenge = || {
+        let state = StateFile::load().unwrap_or_default();
+        let pending = challenge_exercises
+            .iter()
+            .copied()
+            .filter(|e| !e.looks_done(&state));
+        let num_done = total - pending.clone().count();
+        verify(pending, (num_done, total), verbose, false).is_ok()
+    };
+
+    let mut all_done = verify_challenge();
+    let mut countdown = countdown_bar(time_limit);
+    while !all_done {
+        let elapsed = start.elapsed();
+        if elapsed >= time_limit {
+            break;
+        }
+        countdown.set_position(elapsed.as_secs());
+        countdown.set_message(format_time(time_limit - elapsed));
+
+        match rx.recv_timeout(Duration::from_secs(1)) {
+            Ok(Ok(events)) => {
+                let edited = events.into_iter().any(|event| {
+                    event.kind == DebouncedEventKind::Any
+                        && event.path.extension() == Some(OsStr::new("rs"))
+                });
+                if edited {
+                    countdown.finish_and_clear();
+                    all_done = verify_challenge();
+                    countdown = countdown_bar(time_limit);
+                }
+            }
+            Ok(Err(e)) => println!("watch error: {e:?}"),
+            Err(RecvTimeoutError::Timeout) => (),
+            Err(e) => println!("watch error: {e:?}"),
+        }
+    }
+    countdown.finish_and_clear();
+
+    let state = StateFile::load().unwrap_or_default();
+    let result = ChallengeResult {
+        topic: topic.to_string(),
+        minutes,
+        completed: challenge_exercises
+            .iter()
+            .filter(|e| e.looks_done(&state))
+            .count(),
+        total,
+        seconds: start.elapsed().min(time_limit).as_secs(),
+        finished_at: state_file::now(),
+    };
+
+    if all_done {
+        success!(
+            "You completed all exercises in {}!",
+            format_time(Duration::from_secs(result.seconds))
+        );
+    } else {
+        let completed = format!("{} / {}", result.completed, result.total);
+        warn!("Time is up! You completed {} exercises.", completed);
+    }
+
+    let state = StateFile::load()?;
+    let previous_best = state
+        .challenges
+        .iter()
+        .filter(|r| r.topic == result.topic && r.minutes == result.minutes)
+        .reduce(|best, r| if r.beats(best) { r } else { best });
+    match previous_best {
+        Some(best) if !result.beats(best) => println!(
+            "Your personal best is {} / {} exercises in {}.",
+            best.completed,
+            best.total,
+            format_time(Duration::from_secs(best.seconds))
+        ),
+        _ => println!("This is a new personal best!"),
+    }
+    state_file::update(|state| state.challenges.push(result));
+    Ok(())
+}
+
+fn countdown_bar(time_limit: Duration) -> ProgressBar {
+    let bar = ProgressBar::new(time_limit.as_secs());
+    bar.set_style(
+        ProgressStyle::default_bar()
+            .template("Time left: {msg} [{bar:40.yellow}]")
+            .expect("Progressbar template should be valid!")
+            .progress_chars("#>-"),
+    );
+    bar
+}
+
+fn format_time(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn result(completed: usize, seconds: u64) -> ChallengeResult {
+        ChallengeResult {
+            topic: String::from("vecs"),
+            minutes: 30,
+            completed,
+            total: 2,
+            seconds,
+            finished_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_personal_best() {
+        assert!(result(2, 900).beats(&result(1, 100)));
+        assert!(result(2, 100).beats(&result(2, 900)));
+        assert!(!result(1, 100).beats(&result(2, 900)));
+        assert!(!result(2, 100).beats(&result(2, 100)));
+    }
+
+    #[test]
+    fn test_format_time() {
+        assert_eq!(format_time(Duration::from_secs(0)), "00:00");
+        assert_eq!(format_time(Duration::from_secs(1805)), "30:05");
+    }
+}
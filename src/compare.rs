@@ -0,0 +1,147 @@
+use anyhow::{bail, Context, Result};
+use regex::Regex;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::diff;
+use crate::exercise::Exercise;
+use crate::state_file::StateFile;
+
+// Constructs that often differ between solutions of the same exercise,
+// e.g. `match` in one and `if let` in the other
+const CONSTRUCTS: &[(&str, &str)] = &[
+    ("`match`", r"\bmatch\b"),
+    ("`if let`", r"\bif let\b"),
+    ("`while let`", r"\bwhile let\b"),
+    ("`for` loops", r"\bfor\b[^{]*\bin\b"),
+    ("`loop`", r"\bloop\b"),
+    ("closures", r"\|[^|]*\|"),
+    ("the `?` operator", r"\?[;.)\s]"),
+    ("`unwrap()`", r"\.unwrap\(\)"),
+    ("`expect()`", r"\.expect\("),
+    ("`clone()`", r"\.clone\(\)"),
+    ("`return`", r"\breturn\b"),
+];
+
+// Compare the solution of the learner with the official one from the `solutions` directory
+// to prompt reflection on other ways to solve the exercise.
+// Only exercises that are done and pass can be compared, so that the official solution isn't
+// a spoiler.
+pub fn compare(exercise: &Exercise) -> Result<()> {
+    let solution_path = exercise.solution_path();
+    let solution = match fs::read_to_string(&solution_path) {
+        Ok(solution) => solution,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            bail!("The course doesn't provide a solution for {exercise}")
+        }
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", solution_path.display()))
+        }
+    };
+
+    let passes = || {
+        exercise
+            .compile()
+            .ok()
+            .is_some_and(|compiled| compiled.run().is_ok())
+    };
+    let state = StateFile::load().unwrap_or_default();
+    if !exercise.looks_done(&state) || !passes() {
+        bail!("Solve {exercise} first, then compare your solution with the official one");
+    }
+
+    let source =
+        fs::read_to_string(&exercise.path).with_context(|| format!("Failed to read {exercise}"))?;
+    let (yours, official) = (normalize(&source), normalize(&solution));
+    let lines = diff::diff(&yours, &official);
+    if !diff::has_changes(&lines) {
+        println!("Your solution matches the official one, apart from formatting and comments.");
+        return Ok(());
+    }
+
+    let mut text = format!(
+        "Your solution of {exercise} (-) compared with the official solution (+), \
+        ignoring formatting and comments:\n\n{}",
+        diff::format(&lines)
+    );
+    let differences = structural_differences(&yours, &official);
+    if !differences.is_empty() {
+        text.push_str("\nStructural differences:\n");
+        for difference in differences {
+            text.push_str(&format!("  {difference}\n"));
+        }
+    }
+    text.push_str("\nWhat does each version do better?\n");
+    crate::ui::page(&text);
+
+    Ok(())
+}
+
+// Format the code with rustfmt if possible and remove comments and empty lines.
+fn normalize(source: &str) -> String {
+    let formatted = rustfmt(source).unwrap_or_else(|| source.to_string());
+    formatted
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| !line.is_empty() && !line.trim_start().starts_with("//"))
+        .map(|line| format!("{line}\n"))
+        .collect()
+}
+
+fn rustfmt(source: &str) -> Option<String> {
+    let mut child = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    child.stdin.take()?.write_all(source.as_bytes()).ok()?;
+    let output = child.wait_with_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+// How often both versions use the constructs that differ between them
+fn structural_differences(yours: &str, official: &str) -> Vec<String> {
+    CONSTRUCTS
+        .iter()
+        .filter_map(|(name, pattern)| {
+            let regex = Regex::new(pattern).unwrap();
+            let (in_yours, in_official) = (
+                regex.find_iter(yours).count(),
+                regex.find_iter(official).count(),
+            );
+            (in_yours != in_official).then(|| {
+                format!("{name}: {in_yours} in your solution, {in_official} in the official one")
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize_removes_comments() {
+        let source = "// I AM NOT DONE\n\nfn main() {\n    // Print it\n    println!(\"hi\");\n}\n";
+        assert_eq!(normalize(source), "fn main() {\n    println!(\"hi\");\n}\n");
+    }
+
+    #[test]
+    fn test_structural_differences() {
+        let yours = "match x {\n    Some(x) => x,\n    None => 0,\n}\n";
+        let official = "if let Some(x) = x {\n    x\n} else {\n    0\n}\n";
+        assert_eq!(
+            structural_differences(yours, official),
+            [
+                "`match`: 1 in your solution, 0 in the official one",
+                "`if let`: 0 in your solution, 1 in the official one",
+            ]
+        );
+    }
+}
@@ -0,0 +1,274 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::theme::Theme;
+
+// The defaults of the course next to `info.toml`. The config of the user overrides them,
+// the config of the Rustlings directory overrides both and command line flags override all.
+pub const COURSE_CONFIG_PATH: &str = "rustlings.toml";
+pub const WORKSPACE_CONFIG_PATH: &str = ".rustlings.toml";
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Deserialize, Default, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    // Whether to use colors. `auto` detects if the terminal supports them
+    pub color: Option<ColorChoice>,
+    // The colors and symbols of the status of exercises
+    pub theme: Option<Theme>,
+    // Only print ASCII characters, like `--ascii`
+    pub ascii: Option<bool>,
+    // The command that opens an exercise with the `edit` command in watch mode.
+    // Defaults to the `VISUAL` or `EDITOR` environment variable
+    pub editor: Option<String>,
+    // How long watch mode waits for more file changes before verifying
+    pub debounce_ms: Option<u64>,
+    // Ring the terminal bell when watch mode finished verifying
+    pub bell: Option<bool>,
+    // Show the outputs of the test exercises like `--nocapture`
+    pub nocapture: Option<bool>,
+    // Whether exercises are only done after removing the "I AM NOT DONE" comment
+    pub not_done_marker: Option<bool>,
+    // Fail the compilation of exercises that have warnings
+    pub deny_warnings: Option<bool>,
+    // Run the built exercises in a sandbox like `--sandbox`
+    pub sandbox: Option<bool>,
+    // Only the end of longer outputs of an exercise is shown
+    pub max_output_kib: Option<u64>,
+    // Store the progress in the data directory of the OS user instead of the Rustlings directory
+    pub per_user_state: Option<bool>,
+    // The oldest Rust version that the course works with.
+    // Takes precedence over the one in `info.toml`
+    pub minimum_rust_version: Option<String>,
+    // Default flags of watch mode
+    #[serde(default)]
+    pub watch: WatchConfig,
+    // Options for compiling the exercises
+    #[serde(default)]
+    pub build: BuildConfig,
+    // Resource limits for running the exercises
+    #[serde(default)]
+    pub limits: LimitsConfig,
+    // When the hints of the exercises unlock
+    #[serde(default)]
+    pub hints: HintsConfig,
+    // Custom names for the commands in watch mode, e.g. `h = "hint"`
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Default, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WatchConfig {
+    pub success_hints: Option<bool>,
+    pub minimal_redraw: Option<bool>,
+    pub background_check: Option<bool>,
+    pub style_tips: Option<bool>,
+    // Poll for file changes every this many milliseconds like `--poll`
+    pub poll_ms: Option<u64>,
+    // Show the progress in the title of the terminal like `--title`
+    pub title: Option<bool>,
+    // The template of the line below the output of the current exercise, see `footer.rs`
+    pub footer: Option<String>,
+    // Whether the footer shows the most used commands (default: true)
+    pub show_keys: Option<bool>,
+    // Glob patterns of files whose changes don't trigger a run, in addition to the defaults.
+    // The patterns of all config files apply
+    #[serde(default)]
+    pub ignore: Vec<String>,
+}
+
+#[derive(Deserialize, Default, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct BuildConfig {
+    // Passed to Cargo
+    pub jobs: Option<u32>,
+    pub offline: Option<bool>,
+    pub locked: Option<bool>,
+    // Extra flags for every compilation, e.g. `"-C opt-level=0"`
+    pub rustflags: Option<String>,
+    // Check the exercises for errors before building them, like `--fast-check`
+    pub fast_check: Option<bool>,
+    // What runs the tests of the test exercises
+    pub test_runner: Option<TestRunner>,
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum TestRunner {
+    // The test harness of the standard library
+    Libtest,
+    // cargo-nextest if it is installed
+    Nextest,
+}
+
+#[derive(Deserialize, Default, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LimitsConfig {
+    // The maximum memory of an exercise in MiB
+    pub memory_mb: Option<u64>,
+    // The maximum CPU time of an exercise in seconds
+    pub cpu_seconds: Option<u64>,
+}
+
+#[derive(Deserialize, Default, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct HintsConfig {
+    // The number of failed attempts before a hint unlocks
+    pub min_failures: Option<u32>,
+    // The minutes after the first failed attempt before a hint unlocks
+    pub min_minutes: Option<u64>,
+}
+
+impl Config {
+    // The values of `other` take precedence.
+    pub fn merge(mut self, other: Self) -> Self {
+        self.aliases.extend(other.aliases);
+        self.watch.ignore.extend(other.watch.ignore);
+        Self {
+            color: other.color.or(self.color),
+            theme: other.theme.or(self.theme),
+            ascii: other.ascii.or(self.ascii),
+            editor: other.editor.or(self.editor),
+            debounce_ms: other.debounce_ms.or(self.debounce_ms),
+            bell: other.bell.or(self.bell),
+            nocapture: other.nocapture.or(self.nocapture),
+            not_done_marker: other.not_done_marker.or(self.not_done_marker),
+            deny_warnings: other.deny_warnings.or(self.deny_warnings),
+            sandbox: other.sandbox.or(self.sandbox),
+            max_output_kib: other.max_output_kib.or(self.max_output_kib),
+            per_user_state: other.per_user_state.or(self.per_user_state),
+            minimum_rust_version: other.minimum_rust_version.or(self.minimum_rust_version),
+            watch: WatchConfig {
+                success_hints: other.watch.success_hints.or(self.watch.success_hints),
+                minimal_redraw: other.watch.minimal_redraw.or(self.watch.minimal_redraw),
+                background_check: other.watch.background_check.or(self.watch.background_check),
+                style_tips: other.watch.style_tips.or(self.watch.style_tips),
+                poll_ms: other.watch.poll_ms.or(self.watch.poll_ms),
+                title: other.watch.title.or(self.watch.title),
+                footer: other.watch.footer.or(self.watch.footer),
+                show_keys: other.watch.show_keys.or(self.watch.show_keys),
+                ignore: self.watch.ignore,
+            },
+            build: BuildConfig {
+                jobs: other.build.jobs.or(self.build.jobs),
+                offline: other.build.offline.or(self.build.offline),
+                locked: other.build.locked.or(self.build.locked),
+                rustflags: other.build.rustflags.or(self.build.rustflags),
+                fast_check: other.build.fast_check.or(self.build.fast_check),
+                test_runner: other.build.test_runner.or(self.build.test_runner),
+            },
+            limits: LimitsConfig {
+                memory_mb: other.limits.memory_mb.or(self.limits.memory_mb),
+                cpu_seconds: other.limits.cpu_seconds.or(self.limits.cpu_seconds),
+            },
+            hints: HintsConfig {
+                min_failures: other.hints.min_failures.or(self.hints.min_failures),
+                min_minutes: other.hints.min_minutes.or(self.hints.min_minutes),
+            },
+            aliases: self.aliases,
+        }
+    }
+
+    fn load_file(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => toml_edit::de::from_str(&content)
+                .with_context(|| format!("Failed to parse the config file {}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => {
+                Err(e).with_context(|| format!("Failed to read the config file {}", path.display()))
+            }
+        }
+    }
+
+    // Load the config of the course, the one of the user and the one of the Rustlings directory.
+    pub fn load() -> Result<Self> {
+        let course = Self::load_file(Path::new(COURSE_CONFIG_PATH))?;
+        let user = match user_config_path() {
+            Some(path) => Self::load_file(&path)?,
+            None => Self::default(),
+        };
+        let workspace = Self::load_file(Path::new(WORKSPACE_CONFIG_PATH))?;
+        Ok(course.merge(user).merge(workspace))
+    }
+
+    pub fn apply_color(&self) {
+        match self.color {
+            Some(ColorChoice::Always) => console::set_colors_enabled(true),
+            Some(ColorChoice::Never) => console::set_colors_enabled(false),
+            Some(ColorChoice::Auto) | None => (),
+        }
+    }
+}
+
+// `$XDG_CONFIG_HOME/rustlings/config.toml`, `~/.config/rustlings/config.toml`
+// or `%APPDATA%\rustlings\config.toml` on Windows
+fn user_config_path() -> Option<PathBuf> {
+    let config_dir = if cfg!(windows) {
+        PathBuf::from(env::var_os("APPDATA")?)
+    } else {
+        match env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(env::var_os("HOME")?).join(".config"),
+        }
+    };
+    Some(config_dir.join("rustlings").join("config.toml"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(content: &str) -> Config {
+        toml_edit::de::from_str(content).unwrap()
+    }
+
+    #[test]
+    fn test_merge() {
+        let user = parse(
+            r#"
+color = "never"
+debounce_ms = 500
+[watch]
+success_hints = true
+ignore = ["*.bak"]
+[aliases]
+h = "hint"
+"#,
+        );
+        let workspace = parse(
+            r#"
+debounce_ms = 200
+[watch]
+success_hints = false
+ignore = ["generated/"]
+[aliases]
+q = "quit"
+"#,
+        );
+
+        let config = user.merge(workspace);
+        assert_eq!(config.color, Some(ColorChoice::Never));
+        assert_eq!(config.debounce_ms, Some(200));
+        assert_eq!(config.watch.success_hints, Some(false));
+        assert_eq!(config.aliases.len(), 2);
+        assert_eq!(config.watch.ignore, ["*.bak", "generated/"]);
+    }
+
+    #[test]
+    fn test_unknown_keys_are_rejected() {
+        assert!(toml_edit::de::from_str::<Config>("colour = \"never\"").is_err());
+    }
+}
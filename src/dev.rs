@@ -0,0 +1,432 @@
+use anyhow::{bail, Context, Result};
+use notify_debouncer_mini::notify::{self, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
+use serde::Deserialize;
+use std::env;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::mpsc::channel;
+use std::time::Duration;
+use tracing::trace;
+
+use crate::exercise::{
+    for_each_in_parallel, num_cpus, topic_of, uses_not_done_marker, Exercise, ExerciseList, Mode,
+    State,
+};
+use crate::state_file::StateFile;
+use crate::template::{self, Variables};
+use crate::variants;
+use crate::verify::verify;
+use crate::watcher::is_ignored;
+
+// Parse info.toml again, since course authors edit it while working on the exercises.
+fn load_course() -> Result<ExerciseList> {
+    let info_file = fs::read_to_string("info.toml").context("Failed to read info.toml")?;
+    toml_edit::de::from_str(&info_file).context("Failed to parse info.toml")
+}
+
+// The exercise files in `exercises/` that aren't listed in info.toml
+fn unlisted_files(exercises: &[Exercise]) -> Vec<PathBuf> {
+    let mut unlisted = Vec::new();
+    let mut dirs = vec![PathBuf::from("exercises")];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                dirs.push(path);
+            } else if path.extension() == Some(OsStr::new("rs"))
+                && !exercises.iter().any(|e| path.ends_with(&e.path))
+            {
+                unlisted.push(path);
+            }
+        }
+    }
+    unlisted.sort();
+    unlisted
+}
+
+// Reload the course and verify its pending exercises
+fn reload_and_verify(verbose: bool) {
+    let exercises = match load_course() {
+        Ok(list) => list.exercises,
+        Err(e) => {
+            warn!("{:#}", e);
+            return;
+        }
+    };
+
+    let missing = exercises
+        .iter()
+        .filter(|e| !e.path.exists())
+        .map(|e| e.path.display().to_string())
+        .collect::<Vec<_>>();
+    if !missing.is_empty() {
+        warn!(
+            "The files of these exercises don't exist: {}",
+            missing.join(", ")
+        );
+    }
+    let unlisted = unlisted_files(&exercises);
+    if !unlisted.is_empty() {
+        let unlisted = unlisted
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>();
+        println!(
+            "These exercise files aren't listed in info.toml yet: {}\n",
+            unlisted.join(", ")
+        );
+    }
+
+    let required = exercises
+        .iter()
+        .filter(|e| !e.optional && e.path.exists())
+        .collect::<Vec<_>>();
+    let state = StateFile::load().unwrap_or_default();
+    let pending = required.iter().copied().filter(|e| !e.looks_done(&state));
+    let num_done = required.iter().filter(|e| e.looks_done(&state)).count();
+    if verify(pending, (num_done, required.len()), verbose, false).is_ok() {
+        success!("All {} exercises of the course are done", required.len());
+    }
+}
+
+// Watch mode for course authors. Changes of info.toml and new exercise files are
+// picked up without restarting, which regular watch mode doesn't do.
+pub fn watch(verbose: bool, debounce: Duration, ignore: &[String]) -> notify::Result<()> {
+    let (tx, rx) = channel();
+    let mut debouncer = new_debouncer(debounce, tx)?;
+    debouncer
+        .watcher()
+        .watch(Path::new("./exercises"), RecursiveMode::Recursive)?;
+    // Editors save info.toml by writing a new file and renaming it, which ends a watch of the
+    // file itself. The directory is watched instead and the events are filtered by name below.
+    debouncer
+        .watcher()
+        .watch(Path::new("."), RecursiveMode::NonRecursive)?;
+
+    let clear_screen = || {
+        if io::stdout().is_terminal() {
+            crate::ui::clear_screen();
+        }
+    };
+    clear_screen();
+    reload_and_verify(verbose);
+    println!("Watching the course for changes. Press Ctrl+C to exit.");
+
+    for events in rx {
+        let events = match events {
+            Ok(events) => events,
+            Err(e) => {
+                println!("watch error: {e:?}");
+                continue;
+            }
+        };
+        for event in &events {
+            trace!("File event {:?} for {}", event.kind, event.path.display());
+        }
+        let relevant = events.iter().any(|event| {
+            event.kind == DebouncedEventKind::Any
+                && (event.path.extension() == Some(OsStr::new("rs"))
+                    || event.path.file_name() == Some(OsStr::new("info.toml")))
+                && !is_ignored(&event.path, ignore)
+        });
+        if relevant {
+            clear_screen();
+            reload_and_verify(verbose);
+        }
+    }
+    Ok(())
+}
+
+// The outcome of checking the official solution of an exercise
+enum SolutionCheck {
+    Passed,
+    Missing,
+    // Why the solution failed
+    Failed(String),
+}
+
+// Run the solution with the checks of its exercise
+fn check_solution(exercise: &Exercise) -> SolutionCheck {
+    let mut solution = exercise.clone();
+    solution.path = exercise.solution_path();
+    if !solution.path.exists() {
+        return SolutionCheck::Missing;
+    }
+    if exercise.params.is_empty() {
+        return check_solution_file(exercise, &solution);
+    }
+
+    // The solution of the course still has the parameters, so a variant of it is checked.
+    let variant_path = env::temp_dir().join(format!(
+        "rustlings_variant_{}_{}.rs",
+        process::id(),
+        exercise.name
+    ));
+    let written = fs::read_to_string(&solution.path)
+        .and_then(|source| fs::write(&variant_path, variants::expand(exercise, &source, 0)));
+    if let Err(e) = written {
+        return SolutionCheck::Failed(format!("Failed to write a variant of the solution: {e}"));
+    }
+    solution.path = variant_path;
+    let check = check_solution_file(exercise, &solution);
+    let _ = fs::remove_file(&solution.path);
+    check
+}
+
+fn check_solution_file(exercise: &Exercise, solution: &Exercise) -> SolutionCheck {
+    if uses_not_done_marker() && solution.state() != State::Done {
+        return SolutionCheck::Failed(String::from(
+            "The solution still contains the \"I AM NOT DONE\" comment",
+        ));
+    }
+
+    let compiled = match solution.compile() {
+        Ok(compiled) => compiled,
+        Err(output) => {
+            return SolutionCheck::Failed(format!("Compiling failed:\n{}", output.stderr))
+        }
+    };
+    match compiled.run() {
+        // Every test of the passing solution passed, so parts that aren't done don't have any.
+        Ok(output) => {
+            let done = exercise.parts_done(&output);
+            let without_tests: Vec<&str> = exercise
+                .parts
+                .iter()
+                .map(String::as_str)
+                .filter(|part| !done.contains(part))
+                .collect();
+            if without_tests.is_empty() {
+                SolutionCheck::Passed
+            } else {
+                SolutionCheck::Failed(format!(
+                    "No tests were found in the modules of the parts {}",
+                    without_tests.join(", ")
+                ))
+            }
+        }
+        Err(output) => SolutionCheck::Failed(format!(
+            "Running failed:\n{}\n{}",
+            output.stdout.trim_end(),
+            output.stderr.trim_end()
+        )),
+    }
+}
+
+// Check the solutions of all exercises in parallel, so that course repositories
+// can make sure in CI that their solutions are correct.
+pub fn run_all_solutions(exercises: &[Exercise]) -> Result<()> {
+    let checks = for_each_in_parallel(exercises, num_cpus(), check_solution);
+
+    let (mut passed, mut failed, mut missing) = (0, 0, Vec::new());
+    for (exercise, check) in exercises.iter().zip(checks) {
+        match check {
+            SolutionCheck::Passed => passed += 1,
+            SolutionCheck::Missing => missing.push(exercise.name.as_str()),
+            SolutionCheck::Failed(reason) => {
+                failed += 1;
+                warn!("The solution of {} failed", exercise.name);
+                println!("{}\n", reason.trim_end());
+            }
+        }
+    }
+    if !missing.is_empty() {
+        println!(
+            "These exercises don't have a solution: {}",
+            missing.join(", ")
+        );
+    }
+    println!("{passed} / {} solutions passed", passed + failed);
+
+    if failed > 0 {
+        bail!("{failed} solutions failed");
+    }
+    Ok(())
+}
+
+// Create a new exercise from a template and add it to the end of info.toml.
+// Without a template, a minimal exercise for the mode is created.
+pub fn new(path: &Path, mode: Mode, template: Option<&Path>) -> Result<()> {
+    let course = load_course()?;
+    // The path is absolute if the command was run in a subdirectory of the course.
+    let current_dir = env::current_dir().context("Failed to get the current directory")?;
+    let path = path.strip_prefix(&current_dir).unwrap_or(path);
+    let Some(name) = path.file_stem().and_then(OsStr::to_str) else {
+        bail!("{} isn't the path of a Rust file", path.display());
+    };
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+    if course.exercises.iter().any(|e| e.name == name) {
+        bail!("info.toml already contains an exercise called {name}");
+    }
+
+    let template = match template {
+        Some(template) => fs::read_to_string(template)
+            .with_context(|| format!("Failed to read the template {}", template.display()))?,
+        None => match mode {
+            Mode::Test => template::TEST_TEMPLATE,
+            Mode::Compile | Mode::Clippy => template::COMPILE_TEMPLATE,
+        }
+        .to_string(),
+    };
+    let source = template::expand(
+        &template,
+        &Variables {
+            course_name: course.course_name.as_deref().unwrap_or("Rustlings"),
+            exercise_name: name,
+            topic: topic_of(path),
+        },
+    );
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    fs::write(path, source).with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let mode = format!("{mode:?}").to_lowercase();
+    let entry = format!(
+        "\n[[exercises]]\nname = \"{name}\"\npath = \"{}\"\nmode = \"{mode}\"\nhint = \"\"\"\"\"\"\n",
+        path.display()
+    );
+    let mut info_file = fs::OpenOptions::new()
+        .append(true)
+        .open("info.toml")
+        .context("Failed to open info.toml")?;
+    io::Write::write_all(&mut info_file, entry.as_bytes()).context("Failed to write info.toml")?;
+
+    println!(
+        "Created {} and added it to the end of info.toml",
+        path.display()
+    );
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    workspace: Option<Workspace>,
+}
+
+#[derive(Deserialize)]
+struct Workspace {
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+// The Cargo workspace in a parent directory of the course, e.g. of a teaching monorepo,
+// and the path of the course in it, unless the workspace excludes the course.
+// The packages that Rustlings generates are workspaces of their own, but Cargo commands in the
+// enclosing workspace still trip over them.
+fn enclosing_workspace() -> Option<(PathBuf, PathBuf)> {
+    let course_dir = env::current_dir().ok()?.canonicalize().ok()?;
+    for dir in course_dir.ancestors().skip(1) {
+        let manifest_path = dir.join("Cargo.toml");
+        let Ok(content) = fs::read_to_string(&manifest_path) else {
+            continue;
+        };
+        // Packages without a workspace are part of the workspace further up.
+        let Some(workspace) = toml_edit::de::from_str::<Manifest>(&content)
+            .ok()
+            .and_then(|manifest| manifest.workspace)
+        else {
+            continue;
+        };
+        let relative = course_dir.strip_prefix(dir).ok()?.to_path_buf();
+        if workspace
+            .exclude
+            .iter()
+            .any(|excluded| relative.starts_with(excluded))
+        {
+            return None;
+        }
+        return Some((manifest_path, relative));
+    }
+    None
+}
+
+// Check that the exercises don't pass as they are, since learners would get them for free.
+// The "I AM NOT DONE" comment is ignored, only compiling and running the exercise counts.
+// Exercises and solutions that still contain template variables are reported as well.
+pub fn check(exercises: &[Exercise]) -> Result<()> {
+    let mut unexpanded = 0;
+    for path in exercises
+        .iter()
+        .flat_map(|e| [e.path.clone(), e.solution_path()])
+    {
+        let Ok(source) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let placeholders = template::placeholders(&source);
+        if !placeholders.is_empty() {
+            unexpanded += 1;
+            let message = format!(
+                "{} contains the template variables {}",
+                path.display(),
+                placeholders.join(", ")
+            );
+            warn!("{}", message);
+        }
+    }
+
+    if let Some((manifest_path, relative)) = enclosing_workspace() {
+        warn!(
+            "The course is inside the Cargo workspace of {}",
+            manifest_path.display()
+        );
+        println!(
+            "Add `exclude = [\"{}\"]` to its `[workspace]` table, so that Cargo commands in the workspace ignore the packages of the exercises.",
+            relative.display()
+        );
+    }
+
+    let unsolved = exercises
+        .iter()
+        .filter(|e| !e.passes_unmodified)
+        .cloned()
+        .collect::<Vec<_>>();
+    let passing = for_each_in_parallel(&unsolved, num_cpus(), |exercise| {
+        exercise
+            .compile()
+            .is_ok_and(|compiled| compiled.run().is_ok())
+    });
+
+    let passing = unsolved
+        .iter()
+        .zip(passing)
+        .filter(|(_, passing)| *passing)
+        .map(|(exercise, _)| exercise)
+        .collect::<Vec<_>>();
+    for exercise in &passing {
+        warn!("{} passes without any edits", exercise);
+    }
+    println!(
+        "{} / {} exercises need to be edited to pass",
+        unsolved.len() - passing.len(),
+        unsolved.len()
+    );
+
+    if !passing.is_empty() {
+        bail!("Fix the exercises that pass unmodified or add `passes_unmodified = true` to them in info.toml");
+    }
+    if unexpanded > 0 {
+        bail!("Replace the template variables, e.g. by creating the exercises with `rustlings dev new`");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_load_course() {
+        let list = load_course().unwrap();
+        assert!(!list.exercises.is_empty());
+    }
+}
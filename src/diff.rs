@@ -0,0 +1,132 @@
+use console::style;
+
+use crate::theme;
+
+// A line of a diff between an old and a new text
+#[derive(PartialEq, Eq, Debug)]
+pub enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+// The lines of both texts aligned by their longest common subsequence
+pub fn diff<'a>(old: &'a str, new: &'a str) -> Vec<Line<'a>> {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+
+    // lengths[i][j] is the length of the longest common subsequence of old[i..] and new[j..].
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(Line::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            lines.push(Line::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|line| Line::Removed(line)));
+    lines.extend(new[j..].iter().map(|line| Line::Added(line)));
+    lines
+}
+
+// Whether the diff contains any changes
+pub fn has_changes(lines: &[Line]) -> bool {
+    lines.iter().any(|line| !matches!(line, Line::Same(_)))
+}
+
+fn format_line(line: &Line) -> String {
+    match line {
+        Line::Same(line) => format!("  {line}"),
+        Line::Removed(line) => theme::bad(format!("- {line}")).to_string(),
+        Line::Added(line) => theme::good(format!("+ {line}")).to_string(),
+    }
+}
+
+// The diff with `-` for removed lines in red and `+` for added lines in green
+pub fn format(lines: &[Line]) -> String {
+    let mut text = String::new();
+    for line in lines {
+        text.push_str(&format_line(line));
+        text.push('\n');
+    }
+    text
+}
+
+// Like `format`, but only with the given number of unchanged lines around the changes.
+// Skipped lines are replaced with `...`.
+pub fn format_changes(lines: &[Line], context: usize) -> String {
+    let mut shown = vec![false; lines.len()];
+    for (ind, line) in lines.iter().enumerate() {
+        if !matches!(line, Line::Same(_)) {
+            let end = (ind + context + 1).min(lines.len());
+            shown[ind.saturating_sub(context)..end].fill(true);
+        }
+    }
+
+    let mut text = String::new();
+    let mut skipped = false;
+    for (line, shown) in lines.iter().zip(shown) {
+        if !shown {
+            skipped = true;
+            continue;
+        }
+        if skipped {
+            text.push_str(&format!("{}\n", style("...").dim()));
+            skipped = false;
+        }
+        text.push_str(&format_line(line));
+        text.push('\n');
+    }
+    if skipped {
+        text.push_str(&format!("{}\n", style("...").dim()));
+    }
+    text
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_diff() {
+        let lines = diff("a\nb\nc\n", "a\nc\nd\n");
+        assert_eq!(
+            lines,
+            [
+                Line::Same("a"),
+                Line::Removed("b"),
+                Line::Same("c"),
+                Line::Added("d"),
+            ]
+        );
+        assert!(has_changes(&lines));
+        assert!(!has_changes(&diff("a\nb", "a\nb")));
+    }
+
+    #[test]
+    fn test_format_changes() {
+        let lines = diff("a\nb\nc\nd\ne\nf\n", "a\nb\nc\nD\ne\nf\n");
+        assert_eq!(
+            console::strip_ansi_codes(&format_changes(&lines, 1)),
+            "...\n  c\n- d\n+ D\n  e\n...\n"
+        );
+    }
+}
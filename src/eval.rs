@@ -0,0 +1,147 @@
+use console::style;
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::process;
+
+use crate::exercise::Exercise;
+use crate::state_file::{self, StateFile};
+
+// A quick-fire exercise where the learner types an expression that must evaluate to the
+// expected value, e.g. to practice operators, shadowing and types.
+// This is deserialized from the `expressions` array in the info.toml file.
+#[derive(Deserialize, Clone, Debug)]
+pub struct Expression {
+    // Name of the expression exercise
+    pub name: String,
+    // What the learner should write, e.g. "Multiply `x` by 3 without using `*`"
+    pub task: String,
+    // Statements that run before the learner's code, e.g. `let x = 5;`
+    #[serde(default)]
+    pub setup: String,
+    // The `Debug` output of the value that the code must evaluate to, e.g. `15` or `"hi"`
+    pub expected: String,
+    #[serde(default)]
+    pub hint: String,
+}
+
+// How the code of the learner evaluated
+pub enum Evaluation {
+    Correct,
+    // The `Debug` output of the value
+    Wrong(String),
+    // The output of the compiler or of the panic
+    Failed(String),
+}
+
+impl Expression {
+    pub fn is_solved(&self, state: &StateFile) -> bool {
+        state.expressions_solved.contains(&self.name)
+    }
+
+    // The program that prints the value of the learner's code. The code is a block,
+    // so that it can contain statements before the expression.
+    fn source(&self, code: &str) -> String {
+        format!(
+            "fn main() {{\n{}\nlet value = {{\n{code}\n}};\nprintln!(\"{{value:?}}\");\n}}\n",
+            self.setup
+        )
+    }
+
+    // Wrap the code in a generated crate and run it like an exercise
+    pub fn evaluate(&self, code: &str) -> Evaluation {
+        let path = env::temp_dir().join(format!(
+            "rustlings_expression_{}_{}.rs",
+            process::id(),
+            self.name
+        ));
+        if let Err(e) = fs::write(&path, self.source(code)) {
+            return Evaluation::Failed(format!("Failed to write {}: {e}", path.display()));
+        }
+        let exercise = Exercise::program(&self.name, path.clone());
+        let result = exercise.compile().and_then(|compiled| compiled.run());
+        let _ = fs::remove_file(&path);
+        match result {
+            Ok(output) if output.stdout.trim() == self.expected.trim() => Evaluation::Correct,
+            Ok(output) => Evaluation::Wrong(output.stdout.trim().to_string()),
+            Err(output) => Evaluation::Failed(format!("{}{}", output.stdout, output.stderr)),
+        }
+    }
+}
+
+// The expression exercises that weren't solved yet
+pub fn unsolved(expressions: &[Expression]) -> Vec<&Expression> {
+    let state = StateFile::load().unwrap_or_default();
+    expressions
+        .iter()
+        .filter(|expression| !expression.is_solved(&state))
+        .collect()
+}
+
+// Ask for code until it evaluates to the expected value. An empty line skips the exercise
+// and `hint` shows its hint. Returns whether the exercise was solved.
+pub fn ask(expression: &Expression, input: &mut impl BufRead) -> io::Result<bool> {
+    println!("\n{}", style(&expression.name).bold());
+    println!("{}", expression.task);
+    if !expression.setup.is_empty() {
+        println!("\n{}", style(expression.setup.trim()).dim());
+    }
+
+    loop {
+        print!("\n>> ");
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(false);
+        }
+        let code = line.trim();
+        match code {
+            "" => {
+                println!("Skipped {}.", expression.name);
+                return Ok(false);
+            }
+            "hint" if expression.hint.is_empty() => println!("There is no hint for this one."),
+            "hint" => println!("{}", expression.hint),
+            _ => match expression.evaluate(code) {
+                Evaluation::Correct => {
+                    state_file::update(|state| {
+                        state.expressions_solved.insert(expression.name.clone());
+                    });
+                    success!("Solved {}!", expression.name);
+                    return Ok(true);
+                }
+                Evaluation::Wrong(value) => {
+                    warn!("That evaluates to {}. Try again!", value);
+                }
+                Evaluation::Failed(output) => {
+                    warn!(
+                        "That doesn't work for {}. Here's the output:",
+                        expression.name
+                    );
+                    println!("{}", output.trim_end());
+                }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_source() {
+        let expression = Expression {
+            name: String::from("shadowing1"),
+            task: String::new(),
+            setup: String::from("let x = 5;"),
+            expected: String::from("15"),
+            hint: String::new(),
+        };
+        assert_eq!(
+            expression.source("let x = x * 3; x"),
+            "fn main() {\nlet x = 5;\nlet value = {\nlet x = x * 3; x\n};\nprintln!(\"{value:?}\");\n}\n"
+        );
+    }
+}
@@ -0,0 +1,342 @@
+use anyhow::{bail, Context, Result};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs;
+use std::time::Instant;
+
+use crate::exercise::{last_timings, Exercise, ExerciseOutput, Mode, TestCounts};
+use crate::state_file::{self, StateFile};
+use crate::tamper;
+
+// The instructor places this file in the Rustlings directory to run an exam.
+// Hints are disabled from then on until the exam is graded.
+pub const EXAM_FILE_PATH: &str = "exam.toml";
+pub const REPORT_PATH: &str = "exam-report.json";
+pub const DISABLED_HINT: &str = "Hints are disabled during the exam.";
+
+#[derive(Deserialize)]
+pub struct Exam {
+    // The names of the graded exercises
+    pub exercises: Vec<String>,
+    // The percentage of the points that is required to pass the exam
+    pub pass_threshold: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ExerciseResult {
+    pub name: String,
+    pub passed: bool,
+    // How long compiling and running the exercise took
+    pub seconds: f64,
+    // The passing tests of test exercises that compile
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tests: Option<TestCounts>,
+    // The parts of `seconds` spent compiling and running the exercise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compile_seconds: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_seconds: Option<f64>,
+    // Whether the exercise was passed without viewing its hint
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint_free: Option<bool>,
+    // The earned and the possible points according to the rubric in info.toml
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_points: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ExamReport {
+    pub results: Vec<ExerciseResult>,
+    pub passed: usize,
+    pub total: usize,
+    // Unix timestamp of the grading
+    pub graded_at: u64,
+    // The weighted total score
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub points: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_points: Option<u32>,
+    // Whether the points reached the pass threshold of the exam
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exam_passed: Option<bool>,
+}
+
+// The signature proves that the report was created with the instructor's key. It proves nothing
+// if the key is on the machine of a student, so only the instructor grades.
+#[derive(Serialize, Deserialize)]
+struct SignedReport {
+    report: ExamReport,
+    signature: String,
+}
+
+pub fn load_exam() -> Result<Option<Exam>> {
+    match fs::read_to_string(EXAM_FILE_PATH) {
+        Ok(content) => Ok(Some(
+            toml_edit::de::from_str(&content)
+                .with_context(|| format!("Failed to parse {EXAM_FILE_PATH}"))?,
+        )),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {EXAM_FILE_PATH}")),
+    }
+}
+
+// Grade the exercises of the exam and write the signed report.
+// An exercise passes if it doesn't contain the "I AM NOT DONE" comment anymore
+// and compiles and runs (or tests) successfully.
+pub fn grade(exam: &Exam, exercises: &[Exercise], key: &str) -> Result<()> {
+    let state = StateFile::load().unwrap_or_default();
+    let mut results = Vec::with_capacity(exam.exercises.len());
+    for name in &exam.exercises {
+        let Some(exercise) = exercises.iter().find(|e| &e.name == name) else {
+            bail!("The exam contains the unknown exercise '{name}'");
+        };
+
+        let start = Instant::now();
+        let output = exercise.compile().ok().map(|compiled| compiled.run());
+        let seconds = start.elapsed().as_secs_f64();
+        let timings = last_timings();
+        // Removing tests to pass an exercise doesn't count in an exam.
+        let removed_tests = match &output {
+            Some(Ok(output) | Err(output)) => tamper::removed_tests(exercise, output),
+            None => Vec::new(),
+        };
+        let submitted = exercise.looks_done(&state) && removed_tests.is_empty();
+        let passed = submitted && output.as_ref().is_some_and(Result::is_ok);
+        let tests = match (&exercise.mode, &output) {
+            (Mode::Test, Some(Ok(output) | Err(output))) => output.test_counts(),
+            _ => None,
+        };
+
+        let (points, max_points) = rubric_points(
+            exercise,
+            submitted,
+            passed,
+            output.as_ref().map(|output| match output {
+                Ok(output) | Err(output) => output,
+            }),
+        );
+
+        let description = match tests {
+            Some(tests) => format!("{exercise} ({tests})"),
+            None => exercise.to_string(),
+        };
+        if passed {
+            success!("Passed {}", description);
+        } else if !removed_tests.is_empty() {
+            warn!("Failed {}", description);
+            println!("Its tests were removed: {}", removed_tests.join(", "));
+        } else {
+            warn!("Failed {}", description);
+        }
+        results.push(ExerciseResult {
+            name: name.clone(),
+            passed,
+            seconds,
+            tests,
+            compile_seconds: timings.compile.map(|d| d.as_secs_f64()),
+            run_seconds: timings.run.map(|d| d.as_secs_f64()),
+            hint_free: Some(
+                passed
+                    && state
+                        .exercises
+                        .get(name)
+                        .map_or(true, |record| record.hints_viewed == 0),
+            ),
+            points: Some(points),
+            max_points: Some(max_points),
+        });
+    }
+
+    let points = results.iter().filter_map(|result| result.points).sum();
+    let max_points = results.iter().filter_map(|result| result.max_points).sum();
+    let report = ExamReport {
+        passed: results.iter().filter(|result| result.passed).count(),
+        total: results.len(),
+        results,
+        graded_at: state_file::now(),
+        points: Some(points),
+        max_points: Some(max_points),
+        exam_passed: exam
+            .pass_threshold
+            .map(|threshold| percentage(points, max_points) >= threshold),
+    };
+    println!(
+        "Score: {} / {} exercises passed, {points} / {max_points} points ({:.1} %)",
+        report.passed,
+        report.total,
+        percentage(points, max_points),
+    );
+    match (exam.pass_threshold, report.exam_passed) {
+        (Some(threshold), Some(true)) => {
+            success!("The pass threshold of {} % was reached", threshold)
+        }
+        (Some(threshold), Some(false)) => {
+            warn!("The pass threshold of {} % wasn't reached", threshold)
+        }
+        _ => (),
+    }
+
+    let signature = sign(&report, key)?;
+    let content = serde_json::to_string_pretty(&SignedReport { report, signature })?;
+    fs::write(REPORT_PATH, content).with_context(|| format!("Failed to write {REPORT_PATH}"))?;
+    println!("The signed report was written to {REPORT_PATH}");
+    state_file::update(|state| state.exam_started_at = None);
+
+    Ok(())
+}
+
+// The points that the exercise earned and its possible points.
+// An exercise earns its `points` if it passed. With `test_points`, it earns the points
+// of its passing tests instead, as long as it doesn't contain the "I AM NOT DONE" comment anymore.
+fn rubric_points(
+    exercise: &Exercise,
+    submitted: bool,
+    passed: bool,
+    output: Option<&ExerciseOutput>,
+) -> (u32, u32) {
+    if exercise.test_points.is_empty() {
+        let points = exercise.points.unwrap_or(1);
+        return (if passed { points } else { 0 }, points);
+    }
+
+    let max_points = exercise.test_points.values().sum();
+    let passed_tests = match output {
+        Some(output) if submitted => output.passed_tests(),
+        _ => return (0, max_points),
+    };
+    let points = exercise
+        .test_points
+        .iter()
+        .filter(|(name, _)| {
+            passed_tests.iter().any(|test| {
+                test == name
+                    || test
+                        .strip_suffix(name.as_str())
+                        .is_some_and(|p| p.ends_with("::"))
+            })
+        })
+        .map(|(_, points)| points)
+        .sum();
+    (points, max_points)
+}
+
+fn percentage(points: u32, max_points: u32) -> f64 {
+    if max_points == 0 {
+        return 100.0;
+    }
+    f64::from(points) * 100.0 / f64::from(max_points)
+}
+
+// Check that the report at the given path wasn't modified after grading.
+pub fn check_report(path: &str, key: &str) -> Result<ExamReport> {
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read {path}"))?;
+    let signed: SignedReport =
+        serde_json::from_str(&content).with_context(|| format!("Failed to parse {path}"))?;
+
+    if !verify(&signed.report, key, &signed.signature)? {
+        bail!("The signature of {path} is invalid. The report was modified or signed with another key.");
+    }
+
+    Ok(signed.report)
+}
+
+fn mac(report: &ExamReport, key: &str) -> Result<Hmac<Sha256>> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes())?;
+    mac.update(serde_json::to_string(report)?.as_bytes());
+    Ok(mac)
+}
+
+fn sign(report: &ExamReport, key: &str) -> Result<String> {
+    Ok(mac(report, key)?
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect())
+}
+
+// The signature is compared in constant time, so that the time of the check doesn't reveal
+// how much of a forged signature is correct.
+fn verify(report: &ExamReport, key: &str, signature: &str) -> Result<bool> {
+    let Some(bytes) = (0..signature.len())
+        .step_by(2)
+        .map(|ind| {
+            let byte = signature.get(ind..ind + 2)?;
+            u8::from_str_radix(byte, 16).ok()
+        })
+        .collect::<Option<Vec<u8>>>()
+    else {
+        return Ok(false);
+    };
+    Ok(mac(report, key)?.verify_slice(&bytes).is_ok())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn exercise() -> Exercise {
+        Exercise::for_test("example", "example.rs", Mode::Test)
+    }
+
+    #[test]
+    fn test_rubric_points() {
+        let mut exercise = exercise();
+        assert_eq!(rubric_points(&exercise, true, true, None), (1, 1));
+        exercise.points = Some(3);
+        assert_eq!(rubric_points(&exercise, true, false, None), (0, 3));
+
+        exercise.test_points = [(String::from("first"), 2), (String::from("second"), 3)].into();
+        let output = ExerciseOutput {
+            stdout: String::from("test tests::first ... ok\ntest tests::second ... FAILED\n"),
+            stderr: String::new(),
+        };
+        assert_eq!(rubric_points(&exercise, true, false, Some(&output)), (2, 5));
+        assert_eq!(
+            rubric_points(&exercise, false, false, Some(&output)),
+            (0, 5)
+        );
+    }
+
+    #[test]
+    fn test_percentage() {
+        assert_eq!(percentage(3, 4), 75.0);
+        assert_eq!(percentage(0, 0), 100.0);
+    }
+
+    #[test]
+    fn test_signed_report() {
+        let report = ExamReport {
+            results: vec![ExerciseResult {
+                name: String::from("example"),
+                passed: true,
+                seconds: 1.5,
+                tests: Some(TestCounts {
+                    passed: 2,
+                    total: 2,
+                }),
+                compile_seconds: Some(1.2),
+                run_seconds: Some(0.3),
+                hint_free: Some(true),
+                points: Some(2),
+                max_points: Some(2),
+            }],
+            passed: 1,
+            total: 1,
+            graded_at: 0,
+            points: Some(2),
+            max_points: Some(2),
+            exam_passed: None,
+        };
+        let signature = sign(&report, "key").unwrap();
+        assert_eq!(signature.len(), 64);
+        assert_ne!(signature, sign(&report, "other key").unwrap());
+        assert!(verify(&report, "key", &signature).unwrap());
+        assert!(!verify(&report, "other key", &signature).unwrap());
+        assert!(!verify(&report, "key", &signature[2..]).unwrap());
+        assert!(!verify(&report, "key", "not hex").unwrap());
+    }
+}
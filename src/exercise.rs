@@ -1,19 +1,228 @@
-use serde::Deserialize;
+use clap::ValueEnum;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::cell::Cell;
+use std::collections::{BTreeMap, VecDeque};
+use std::env;
 use std::fmt::{self, Display, Formatter};
 use std::fs::{self, remove_file, File};
-use std::io::{self, BufRead, BufReader};
-use std::path::PathBuf;
-use std::process::{self, exit, Command, Stdio};
-use std::{array, env, mem};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, exit, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use std::{array, mem};
+use tracing::trace;
 use winnow::ascii::{space0, Caseless};
 use winnow::combinator::opt;
 use winnow::Parser;
 
-const RUSTC_COLOR_ARGS: &[&str] = &["--color", "always"];
+use crate::eval::Expression;
+use crate::exit_code::{self, Failure};
+use crate::glossary::GlossaryEntry;
+use crate::mutants;
+use crate::predict::Prediction;
+use crate::quiz::Quiz;
+use crate::regions;
+use crate::rules::{self, Rules};
+use crate::sandbox::Sandbox;
+use crate::shutdown::{Limits, TrackedCommand};
+use crate::state_file::StateFile;
+use crate::variants::Param;
+
 const RUSTC_EDITION_ARGS: &[&str] = &["--edition", "2021"];
 const RUSTC_NO_DEBUG_ARGS: &[&str] = &["-C", "strip=debuginfo"];
 const CONTEXT: usize = 2;
-const CLIPPY_CARGO_TOML_PATH: &str = "./exercises/22_clippy/Cargo.toml";
+// The Cargo packages that Clippy lints the Clippy exercises in, one per exercise
+pub const CLIPPY_DIR: &str = ".rustlings-clippy";
+// The Cargo packages that cargo-nextest needs to run the tests of an exercise
+pub const NEXTEST_DIR: &str = ".rustlings-nextest";
+// Where Cargo builds the packages of Rustlings if `CARGO_TARGET_DIR` isn't set
+pub const DEFAULT_TARGET_DIR: &str = ".rustlings-target";
+// The complete output of the last run of an exercise
+pub const FULL_OUTPUT_PATH: &str = ".rustlings-output.txt";
+pub const DEFAULT_MAX_OUTPUT_KIB: u64 = 1024;
+// The full output of a run is cut off after this many bytes per stream, so that an exercise
+// that prints in an endless loop can't fill up the disk
+const MAX_FULL_OUTPUT_BYTES: u64 = 16 * 1024 * 1024;
+pub const SOLUTIONS_DIR: &str = "solutions";
+
+// The target directory of the Cargo packages that Rustlings generates. A `CARGO_TARGET_DIR`
+// of the user is honored, but with a subdirectory, so that cleaning the packages of the
+// exercises doesn't remove the builds of other projects.
+pub fn cargo_target_dir() -> PathBuf {
+    match env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir).join("rustlings"),
+        _ => PathBuf::from(DEFAULT_TARGET_DIR),
+    }
+}
+
+// Options that apply to the compilation of every exercise.
+// They are set once at startup from the config and the command line.
+#[derive(Default, Debug)]
+pub struct BuildOptions {
+    // Pass `-D warnings` to rustc
+    pub deny_warnings: bool,
+    // Passed to Cargo for the exercises that are built with it
+    pub jobs: Option<u32>,
+    pub offline: bool,
+    pub locked: bool,
+    // Extra flags for rustc, also passed to Cargo in `RUSTFLAGS`
+    pub rustflags: Vec<String>,
+    // Only check the exercises for errors first and build them once the check passes
+    pub fast_check: bool,
+    // Run the tests with cargo-nextest instead of the libtest harness
+    pub nextest: bool,
+    // Run the built exercises in this sandbox
+    pub sandbox: Option<Sandbox>,
+    // Resource limits of the built exercises
+    pub memory_limit_mb: Option<u64>,
+    pub cpu_limit_seconds: Option<u64>,
+    // Only the end of longer outputs is kept, `DEFAULT_MAX_OUTPUT_KIB` by default
+    pub max_output_kib: Option<u64>,
+}
+
+static BUILD_OPTIONS: OnceLock<BuildOptions> = OnceLock::new();
+
+// Whether exercises are only done after removing the "I AM NOT DONE" comment.
+// Courses can turn it off so that exercises are done once they pass.
+static NOT_DONE_MARKER: AtomicBool = AtomicBool::new(true);
+
+pub fn set_not_done_marker(enabled: bool) {
+    NOT_DONE_MARKER.store(enabled, Ordering::Relaxed);
+}
+
+pub fn uses_not_done_marker() -> bool {
+    NOT_DONE_MARKER.load(Ordering::Relaxed)
+}
+
+pub fn set_build_options(options: BuildOptions) {
+    let _ = BUILD_OPTIONS.set(options);
+}
+
+fn build_options() -> &'static BuildOptions {
+    BUILD_OPTIONS.get_or_init(BuildOptions::default)
+}
+
+// Only the resource limits that this system supports. The others are left out with a
+// warning instead of failing every run, e.g. `ulimit -v` on macOS. Windows applies the
+// limits with a job object, and other systems don't support any.
+pub fn supported_limits(memory_mb: Option<u64>, cpu_seconds: Option<u64>) -> Limits {
+    let supported = |limit: u64, what: &str, ulimit: String| {
+        let supported = cfg!(windows)
+            || (cfg!(unix)
+                && Command::new("sh")
+                    .args(["-c", &ulimit])
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .status()
+                    .is_ok_and(|status| status.success()));
+        if !supported {
+            warn!(
+                "The {} limit isn't supported on this system, so the exercises are run without it",
+                what
+            );
+        }
+        supported.then_some(limit)
+    };
+    Limits {
+        memory_mb: memory_mb
+            .and_then(|limit| supported(limit, "memory", format!("ulimit -v {}", limit * 1024))),
+        cpu_seconds: cpu_seconds
+            .and_then(|limit| supported(limit, "CPU time", format!("ulimit -S -t {limit}"))),
+        timeout: None,
+    }
+}
+
+// The limits that Windows applies to the built exercises, see `ulimit_script` for Unix
+fn job_limits(timeout: Option<Duration>) -> Limits {
+    let options = build_options();
+    Limits {
+        memory_mb: options.memory_limit_mb,
+        cpu_seconds: options.cpu_limit_seconds,
+        timeout,
+    }
+}
+
+// A shell script that applies the resource limits with `ulimit` before running
+// the program in its arguments. Only available on Unix.
+fn ulimit_script() -> Option<String> {
+    let options = build_options();
+    if !cfg!(unix) || (options.memory_limit_mb.is_none() && options.cpu_limit_seconds.is_none()) {
+        return None;
+    }
+
+    let mut script = String::new();
+    if let Some(memory_limit_mb) = options.memory_limit_mb {
+        script.push_str(&format!("ulimit -v {} && ", memory_limit_mb * 1024));
+    }
+    if let Some(cpu_limit_seconds) = options.cpu_limit_seconds {
+        // The soft limit sends SIGXCPU, which tells exceeding the time apart from other
+        // reasons of being killed. The hard limit kills exercises that ignore it.
+        script.push_str(&format!(
+            "ulimit -S -t {cpu_limit_seconds} && ulimit -H -t {} && ",
+            cpu_limit_seconds + 1
+        ));
+    }
+    script.push_str("exec \"$@\"");
+    Some(script)
+}
+
+// The CPU time limit of `ulimit -t` stops the process with SIGXCPU.
+#[cfg(unix)]
+fn exceeded_cpu_time(status: ExitStatus) -> bool {
+    std::os::unix::process::ExitStatusExt::signal(&status) == Some(libc::SIGXCPU)
+}
+
+#[cfg(not(unix))]
+fn exceeded_cpu_time(_status: ExitStatus) -> bool {
+    false
+}
+
+// The command that runs the built exercise with the resource limits,
+// in the sandbox if there is one
+fn exercise_command() -> Command {
+    let program = temp_file();
+    let script = ulimit_script();
+    let launcher = if script.is_some() { "sh" } else { &program };
+
+    let mut cmd = match build_options().sandbox {
+        Some(sandbox) => sandbox.command(launcher),
+        None => Command::new(launcher),
+    };
+    if let Some(script) = script {
+        cmd.args(["-c", &script, "sh", &program]);
+    }
+    cmd
+}
+
+// Apply the build options to a Cargo command.
+fn cargo_build_options(cmd: &mut Command) -> &mut Command {
+    let options = build_options();
+    if let Some(jobs) = options.jobs {
+        cmd.args(["--jobs", &jobs.to_string()]);
+    }
+    if options.offline {
+        cmd.arg("--offline");
+    }
+    if options.locked {
+        cmd.arg("--locked");
+    }
+    if !options.rustflags.is_empty() {
+        let mut rustflags = std::env::var("RUSTFLAGS").unwrap_or_default();
+        for flag in &options.rustflags {
+            if !rustflags.is_empty() {
+                rustflags.push(' ');
+            }
+            rustflags.push_str(flag);
+        }
+        cmd.env("RUSTFLAGS", rustflags);
+    }
+    cmd
+}
 
 // Checks if the line contains the "I AM NOT DONE" comment.
 fn contains_not_done_comment(input: &str) -> bool {
@@ -28,6 +237,178 @@ fn contains_not_done_comment(input: &str) -> bool {
         .is_ok()
 }
 
+// A blank of a fill-in-the-blank exercise, e.g. `__???__` or `__???return_type__`
+// with the id `return_type`
+#[derive(PartialEq, Eq, Debug)]
+pub struct Blank {
+    pub line: usize,
+    pub id: Option<String>,
+}
+
+pub fn blanks(source: &str) -> Vec<Blank> {
+    let regex = Regex::new(r"__\?\?\?(\w*?)__").unwrap();
+    source
+        .lines()
+        .enumerate()
+        .flat_map(|(ind, line)| {
+            regex.captures_iter(line).map(move |captures| Blank {
+                line: ind + 1,
+                id: Some(captures[1].to_string()).filter(|id| !id.is_empty()),
+            })
+        })
+        .collect()
+}
+
+// Colored compiler output is only requested if the terminal supports it.
+// The legacy Windows console would print the raw escape codes otherwise.
+fn rustc_color_args() -> [&'static str; 2] {
+    if console::colors_enabled() {
+        ["--color", "always"]
+    } else {
+        ["--color", "never"]
+    }
+}
+
+// How long compiling and running the last exercise took
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Timings {
+    pub compile: Option<Duration>,
+    pub run: Option<Duration>,
+}
+
+impl Display for Timings {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match (self.compile, self.run) {
+            (Some(compile), Some(run)) => write!(
+                f,
+                "Compiled in {:.2}s, ran in {:.2}s",
+                compile.as_secs_f64(),
+                run.as_secs_f64()
+            ),
+            (Some(compile), None) => write!(f, "Compiled in {:.2}s", compile.as_secs_f64()),
+            _ => Ok(()),
+        }
+    }
+}
+
+thread_local! {
+    // Per thread since the background checks compile exercises at the same time as watch mode
+    static LAST_TIMINGS: Cell<Timings> = Cell::default();
+}
+
+// The timings of the last exercise that was compiled on this thread
+pub fn last_timings() -> Timings {
+    LAST_TIMINGS.get()
+}
+
+// Receives an output of a running exercise. Only its end is kept in memory, and the
+// beginning is written to the file with the full output if there is one.
+struct OutputSink<'a> {
+    tail: VecDeque<u8>,
+    limit: usize,
+    truncated: bool,
+    full_output: Option<&'a Mutex<File>>,
+    written: u64,
+}
+
+impl<'a> OutputSink<'a> {
+    fn new(limit: usize, full_output: Option<&'a Mutex<File>>) -> Self {
+        Self {
+            tail: VecDeque::new(),
+            limit,
+            truncated: false,
+            full_output,
+            written: 0,
+        }
+    }
+
+    fn cut_off(&self) -> bool {
+        self.written >= MAX_FULL_OUTPUT_BYTES
+    }
+}
+
+impl Write for OutputSink<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(full_output) = self.full_output {
+            let len = buf
+                .len()
+                .min(MAX_FULL_OUTPUT_BYTES.saturating_sub(self.written) as usize);
+            // Failing to write the full output mustn't stop reading the output of the exercise.
+            let mut file = full_output.lock().unwrap_or_else(|e| e.into_inner());
+            let _ = file.write_all(&buf[..len]);
+            self.written += len as u64;
+        }
+
+        self.tail.extend(buf);
+        let excess = self.tail.len().saturating_sub(self.limit);
+        if excess > 0 {
+            self.tail.drain(..excess);
+            self.truncated = true;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+// Run the command with its outputs passed through memory instead of buffering them completely.
+// Only the end of each output is kept. The main thread, which runs the exercises of watch mode
+// and `run`, writes the full outputs to `FULL_OUTPUT_PATH`. The worker threads of `-j` and the
+// background checks don't, since they would overwrite each other's output.
+// The command is killed after the timeout.
+fn capture(
+    exercise: &Exercise,
+    cmd: &mut Command,
+    timeout: Option<Duration>,
+) -> io::Result<(ExitStatus, ExerciseOutput)> {
+    let full_output = if thread::current().name() == Some("main") {
+        let mut file = File::create(FULL_OUTPUT_PATH)?;
+        writeln!(file, "Output of {exercise}:")?;
+        Some(Mutex::new(file))
+    } else {
+        None
+    };
+    let max_output_kib = build_options()
+        .max_output_kib
+        .unwrap_or(DEFAULT_MAX_OUTPUT_KIB);
+    let limit = (max_output_kib * 1024) as usize;
+    let mut stdout = OutputSink::new(limit, full_output.as_ref());
+    let mut stderr = OutputSink::new(limit, full_output.as_ref());
+    let status = cmd.stdin(Stdio::null()).tracked_limited_capture(
+        job_limits(timeout),
+        &mut stdout,
+        &mut stderr,
+    )?;
+
+    if let Some(full_output) = &full_output {
+        if stdout.cut_off() || stderr.cut_off() {
+            let mut file = full_output.lock().unwrap_or_else(|e| e.into_inner());
+            let _ = writeln!(
+                file,
+                "\n[The output was cut off after {} MiB]",
+                MAX_FULL_OUTPUT_BYTES / 1024 / 1024
+            );
+        }
+    }
+    let [stdout, stderr] = [stdout, stderr].map(|mut sink| {
+        let mut text = String::new();
+        if sink.truncated {
+            text.push_str(&format!(
+                "[output truncated (showing last {max_output_kib} KiB)"
+            ));
+            if full_output.is_some() {
+                text.push_str(&format!(", the full output is in {FULL_OUTPUT_PATH}"));
+            }
+            text.push_str("]\n");
+        }
+        text.push_str(&String::from_utf8_lossy(sink.tail.make_contiguous()));
+        text
+    });
+    Ok((status, ExerciseOutput { stdout, stderr }))
+}
+
 // Get a temporary file name that is hopefully unique
 #[inline]
 fn temp_file() -> String {
@@ -40,7 +421,7 @@ fn temp_file() -> String {
 }
 
 // The mode of the exercise.
-#[derive(Deserialize, Copy, Clone, Debug)]
+#[derive(Deserialize, ValueEnum, Copy, Clone, Debug)]
 #[serde(rename_all = "lowercase")]
 pub enum Mode {
     // Indicates that the exercise should be compiled as a binary
@@ -53,12 +434,29 @@ pub enum Mode {
 
 #[derive(Deserialize)]
 pub struct ExerciseList {
+    // The name of the course for the `{{course_name}}` variable of exercise templates
+    pub course_name: Option<String>,
+    // The oldest Rust version that all exercises work with
+    pub minimum_rust_version: Option<String>,
+    // Missing in new courses that are created with `rustlings dev new`
+    #[serde(default)]
     pub exercises: Vec<Exercise>,
+    #[serde(default)]
+    pub quizzes: Vec<Quiz>,
+    #[serde(default)]
+    pub expressions: Vec<Expression>,
+    #[serde(default)]
+    pub predictions: Vec<Prediction>,
+    // Course-specific notes shown with `rustlings explain`, by error code
+    #[serde(default)]
+    pub explanations: BTreeMap<String, String>,
+    #[serde(default)]
+    pub glossary: Vec<GlossaryEntry>,
 }
 
 // A representation of a rustlings exercise.
 // This is deserialized from the accompanying info.toml file
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Clone, Debug)]
 pub struct Exercise {
     // Name of the exercise
     pub name: String,
@@ -68,6 +466,72 @@ pub struct Exercise {
     pub mode: Mode,
     // The hint text associated with the exercise
     pub hint: String,
+    // Bonus exercises can be worked on, but aren't required to finish the course
+    #[serde(default)]
+    pub optional: bool,
+    // Former names of the exercise, kept so that renamed exercises can still be found
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    // Overrides the global `deny_warnings` option for this exercise
+    #[serde(default)]
+    pub deny_warnings: Option<bool>,
+    // Lints that are allowed in this exercise, e.g. `["dead_code"]`
+    #[serde(default)]
+    pub allow: Vec<String>,
+    // A test exercise with this table is built without the libtest harness,
+    // like a Cargo test with `harness = false`
+    #[serde(default)]
+    pub harness: Option<CustomHarness>,
+    // The weight of the exercise when grading an exam (default: 1)
+    #[serde(default)]
+    pub points: Option<u32>,
+    // The points of individual tests by name. When grading an exam, the exercise earns
+    // the points of its passing tests instead of `points`
+    #[serde(default)]
+    pub test_points: BTreeMap<String, u32>,
+    // The names of the parts of a longer test exercise, in the order in which they are meant
+    // to be solved. The tests of a part are in a module with its name, e.g. `mod parsing`
+    #[serde(default)]
+    pub parts: Vec<String>,
+    // Values that differ between learners, e.g. `{{count}}` in the exercise and its solution
+    #[serde(default)]
+    pub params: BTreeMap<String, Param>,
+    // For exercises about writing tests: the tests only pass if they also catch
+    // simple mutations of the implementation
+    #[serde(default)]
+    pub mutants: bool,
+    // Exercises like `intro1` are meant to pass before they're edited.
+    // `rustlings dev check` reports the other exercises that do
+    #[serde(default)]
+    pub passes_unmodified: bool,
+    // Free-form labels to filter the exercises by in the interactive list, e.g. `["traits"]`
+    #[serde(default)]
+    pub tags: Vec<String>,
+    // Constructs that the code must or must not use, e.g. `require = ["match"]`
+    #[serde(default)]
+    pub rules: Rules,
+}
+
+// How to run a test exercise that brings its own test framework
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CustomHarness {
+    // The arguments for the built exercise
+    #[serde(default)]
+    pub args: Vec<String>,
+    // A regex that the output must match for the tests to pass.
+    // Otherwise, the tests pass if the exercise exits successfully
+    pub success_output: Option<String>,
+}
+
+impl CustomHarness {
+    fn passed(&self, success: bool, output: &ExerciseOutput) -> Result<bool, regex::Error> {
+        let Some(pattern) = &self.success_output else {
+            return Ok(success);
+        };
+        let regex = Regex::new(pattern)?;
+        Ok(regex.is_match(&output.stdout) || regex.is_match(&output.stderr))
+    }
 }
 
 // An enum to track of the state of an Exercise.
@@ -100,12 +564,17 @@ pub struct CompiledExercise<'a> {
 impl<'a> CompiledExercise<'a> {
     // Run the compiled exercise
     pub fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
-        self.exercise.run()
+        self.exercise.run(None)
+    }
+
+    // Run the compiled exercise and stop it after the timeout, which counts as a failure
+    pub fn run_with_timeout(&self, timeout: Duration) -> Result<ExerciseOutput, ExerciseOutput> {
+        self.exercise.run(Some(timeout))
     }
 }
 
 // A representation of an already executed binary
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct ExerciseOutput {
     // The textual contents of the standard output of the binary
     pub stdout: String,
@@ -113,6 +582,97 @@ pub struct ExerciseOutput {
     pub stderr: String,
 }
 
+impl ExerciseOutput {
+    // Parses the summary of the test harness, e.g.
+    // `test result: FAILED. 3 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out`
+    pub fn test_counts(&self) -> Option<TestCounts> {
+        self.libtest_counts().or_else(|| self.nextest_counts())
+    }
+
+    // The names of the passing tests, e.g. `tests::you_can_assert`
+    pub fn passed_tests(&self) -> Vec<&str> {
+        self.test_results()
+            .into_iter()
+            .filter(|&(_, passed)| passed)
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    // The names of the tests that ran and whether they passed
+    pub fn test_results(&self) -> Vec<(&str, bool)> {
+        let libtest = self.stdout.lines().filter_map(|line| {
+            let line = line.strip_prefix("test ")?;
+            if let Some(name) = line.strip_suffix(" ... ok") {
+                Some((name.trim(), true))
+            } else {
+                Some((line.strip_suffix(" ... FAILED")?.trim(), false))
+            }
+        });
+        let nextest = self.stderr.lines().filter_map(|line| {
+            let line = line.trim_start();
+            let passed = line.starts_with("PASS [");
+            if !passed && !line.starts_with("FAIL [") && !line.starts_with("SIGSEGV [") {
+                return None;
+            }
+            Some((line.rsplit(' ').next()?, passed))
+        });
+        libtest.chain(nextest).collect()
+    }
+
+    fn libtest_counts(&self) -> Option<TestCounts> {
+        let summary = self
+            .stdout
+            .lines()
+            .find_map(|line| line.strip_prefix("test result: "))?;
+        let count = |kind: &str| {
+            summary.split(';').find_map(|part| {
+                part.trim()
+                    .rsplit_once(' ')
+                    .filter(|(_, k)| *k == kind)
+                    .and_then(|(n, _)| n.rsplit(' ').next()?.parse::<usize>().ok())
+            })
+        };
+        let passed = count("passed")?;
+        let failed = count("failed")?;
+        Some(TestCounts {
+            passed,
+            total: passed + failed,
+        })
+    }
+
+    // cargo-nextest prints one line per test to stderr, e.g.
+    // `        PASS [   0.003s] exercise tests::you_can_assert`
+    fn nextest_counts(&self) -> Option<TestCounts> {
+        let mut counts = TestCounts {
+            passed: 0,
+            total: 0,
+        };
+        for line in self.stderr.lines() {
+            let line = line.trim_start();
+            if line.starts_with("PASS [") {
+                counts.passed += 1;
+                counts.total += 1;
+            } else if line.starts_with("FAIL [") || line.starts_with("SIGSEGV [") {
+                counts.total += 1;
+            }
+        }
+        (counts.total > 0).then_some(counts)
+    }
+}
+
+// How many tests of a test exercise pass
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TestCounts {
+    pub passed: usize,
+    pub total: usize,
+}
+
+impl Display for TestCounts {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}/{} tests passing", self.passed, self.total)
+    }
+}
+
 struct FileHandle;
 
 impl Drop for FileHandle {
@@ -122,72 +682,286 @@ impl Drop for FileHandle {
 }
 
 impl Exercise {
-    pub fn compile(&self) -> Result<CompiledExercise, ExerciseOutput> {
+    // An exercise like one in info.toml with only a name, a path and a mode, for unit tests
+    #[cfg(test)]
+    pub fn for_test(name: &str, path: &str, mode: Mode) -> Self {
+        Self {
+            name: String::from(name),
+            path: PathBuf::from(path),
+            mode,
+            hint: String::new(),
+            optional: false,
+            aliases: Vec::new(),
+            harness: None,
+            deny_warnings: None,
+            allow: Vec::new(),
+            points: None,
+            test_points: BTreeMap::new(),
+            parts: Vec::new(),
+            params: BTreeMap::new(),
+            mutants: false,
+            passes_unmodified: false,
+            tags: Vec::new(),
+            rules: Rules::default(),
+        }
+    }
+
+    // Additional rustc arguments from the build options and the exercise
+    fn rustc_build_args(&self) -> Vec<&str> {
+        let mut args = Vec::new();
+        let options = build_options();
+        if self.deny_warnings.unwrap_or(options.deny_warnings) {
+            args.extend(["-D", "warnings"]);
+        }
+        args.extend(options.rustflags.iter().map(String::as_str));
+        // Later lint levels take precedence over `-D warnings`.
+        args.extend(self.allow_args());
+        args
+    }
+
+    fn allow_args(&self) -> impl Iterator<Item = &str> {
+        self.allow.iter().flat_map(|lint| ["-A", lint.as_str()])
+    }
+
+    // The blanks are reported before compiling, since the compiler errors about them
+    // would only be confusing.
+    fn check_blanks(&self) -> Result<(), ExerciseOutput> {
+        let Ok(source) = fs::read_to_string(&self.path) else {
+            return Ok(());
+        };
+        let blanks = blanks(&source);
+        if blanks.is_empty() {
+            return Ok(());
+        }
+
+        let mut stderr = match blanks.len() {
+            1 => format!("{} still has a blank to fill in:\n", self.path.display()),
+            n => format!("{} still has {n} blanks to fill in:\n", self.path.display()),
+        };
+        for blank in blanks {
+            let id = blank.id.unwrap_or_default();
+            stderr.push_str(&format!("  line {}: __???{id}__\n", blank.line));
+        }
+        stderr.push_str("Replace each blank with your code.\n");
+        Err(ExerciseOutput {
+            stdout: String::new(),
+            stderr,
+        })
+    }
+
+    // Like `cargo check`, only emitting the metadata skips the code generation,
+    // which is much faster than building the exercise.
+    fn check(&self) -> Result<(), ExerciseOutput> {
+        let mut cmd = Command::new("rustc");
+        if let (Mode::Test, None) = (self.mode, &self.harness) {
+            cmd.arg("--test");
+        }
+        let output = cmd
+            .args([
+                self.path.to_str().unwrap(),
+                "--emit=metadata",
+                "-o",
+                &temp_file(),
+            ])
+            .args(rustc_color_args())
+            .args(RUSTC_EDITION_ARGS)
+            .args(self.rustc_build_args())
+            .tracked_output()
+            .expect("Failed to run 'check' command.");
+        clean();
+
+        if output.status.success() {
+            Ok(())
+        } else {
+            Err(ExerciseOutput {
+                stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            })
+        }
+    }
+
+    // cargo-nextest only runs the tests of Cargo packages.
+    // The package of the exercise uses it as its library.
+    fn nextest_command(&self) -> Command {
+        let dir = Path::new(NEXTEST_DIR).join(&self.name);
+        let path = self
+            .path
+            .canonicalize()
+            .expect("Failed to find the exercise file");
+        // JSON strings are valid TOML basic strings.
+        let cargo_toml = format!(
+            r#"[package]
+name = "exercise"
+version = "0.0.1"
+edition = "2021"
+[lib]
+path = {}
+doctest = false
+[workspace]
+"#,
+            serde_json::to_string(&path).unwrap()
+        );
+        fs::create_dir_all(&dir)
+            .and_then(|()| fs::write(dir.join("Cargo.toml"), cargo_toml))
+            .expect("Failed to write the Cargo.toml file for cargo-nextest.");
+
+        let mut cmd = Command::new("cargo");
+        cmd.args(["nextest", "run", "--manifest-path"])
+            .arg(dir.join("Cargo.toml"))
+            .arg("--target-dir")
+            .arg(cargo_target_dir())
+            .args([
+                "--color",
+                "never",
+                "--no-fail-fast",
+                "--success-output",
+                "final",
+            ]);
+        cargo_build_options(&mut cmd);
+        cmd
+    }
+
+    // Each Clippy exercise has its own package, so that they can be linted in parallel.
+    // The binary is the file at the path of the exercise, e.g. the solution in
+    // `rustlings dev run-all-solutions`. Returns the path of the manifest.
+    fn clippy_manifest(&self) -> PathBuf {
+        let dir = Path::new(CLIPPY_DIR).join(&self.name);
+        let path = self
+            .path
+            .canonicalize()
+            .expect("Failed to find the exercise file");
+        // The empty workspace keeps the package out of a Cargo workspace that the
+        // course might be in, e.g. the one of a teaching monorepo.
+        // JSON strings are valid TOML basic strings.
+        let cargo_toml = format!(
+            r#"[package]
+name = "{}"
+version = "0.0.1"
+edition = "2021"
+[[bin]]
+name = "{}"
+path = {}
+[workspace]
+"#,
+            self.name,
+            self.name,
+            serde_json::to_string(&path).unwrap()
+        );
+        let cargo_toml_error_msg = if crate::ui::no_emoji() {
+            "Failed to write Clippy Cargo.toml file."
+        } else {
+            "Failed to write 📎 Clippy 📎 Cargo.toml file."
+        };
+        fs::create_dir_all(&dir)
+            .and_then(|()| fs::write(dir.join("Cargo.toml"), cargo_toml))
+            .expect(cargo_toml_error_msg);
+        dir.join("Cargo.toml")
+    }
+
+    pub fn compile(&self) -> Result<CompiledExercise<'_>, ExerciseOutput> {
+        let start = Instant::now();
+        let result = self.build();
+        LAST_TIMINGS.set(Timings {
+            compile: Some(start.elapsed()),
+            run: None,
+        });
+        trace!(
+            "Compiling {} {} after {:?}",
+            self.name,
+            if result.is_ok() {
+                "succeeded"
+            } else {
+                "failed"
+            },
+            start.elapsed()
+        );
+        exit_code::record(result.is_err().then_some(Failure::Compilation));
+        result
+    }
+
+    fn build(&self) -> Result<CompiledExercise<'_>, ExerciseOutput> {
+        self.check_blanks()?;
+        if let Some(message) = regions::check(self) {
+            return Err(ExerciseOutput {
+                stdout: String::new(),
+                stderr: message,
+            });
+        }
+        // Clippy exercises are built with Cargo anyway.
+        if build_options().fast_check && !matches!(self.mode, Mode::Clippy) {
+            self.check()?;
+        }
+
         let cmd = match self.mode {
-            Mode::Compile => Command::new("rustc")
-                .args([self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
+            Mode::Test if self.harness.is_none() && build_options().nextest => {
+                self.nextest_command().arg("--no-run").tracked_output()
+            }
+            Mode::Test if self.harness.is_none() => Command::new("rustc")
+                .args(["--test", self.path.to_str().unwrap(), "-o", &temp_file()])
+                .args(rustc_color_args())
                 .args(RUSTC_EDITION_ARGS)
                 .args(RUSTC_NO_DEBUG_ARGS)
-                .output(),
-            Mode::Test => Command::new("rustc")
-                .args(["--test", self.path.to_str().unwrap(), "-o", &temp_file()])
-                .args(RUSTC_COLOR_ARGS)
+                .args(self.rustc_build_args())
+                .tracked_output(),
+            // Test exercises with a custom harness are built like binaries.
+            Mode::Compile | Mode::Test => Command::new("rustc")
+                .args([self.path.to_str().unwrap(), "-o", &temp_file()])
+                .args(rustc_color_args())
                 .args(RUSTC_EDITION_ARGS)
                 .args(RUSTC_NO_DEBUG_ARGS)
-                .output(),
+                .args(self.rustc_build_args())
+                .tracked_output(),
             Mode::Clippy => {
-                let cargo_toml = format!(
-                    r#"[package]
-name = "{}"
-version = "0.0.1"
-edition = "2021"
-[[bin]]
-name = "{}"
-path = "{}.rs""#,
-                    self.name, self.name, self.name
-                );
-                let cargo_toml_error_msg = if env::var("NO_EMOJI").is_ok() {
-                    "Failed to write Clippy Cargo.toml file."
-                } else {
-                    "Failed to write 📎 Clippy 📎 Cargo.toml file."
-                };
-                fs::write(CLIPPY_CARGO_TOML_PATH, cargo_toml).expect(cargo_toml_error_msg);
+                let manifest_path = self.clippy_manifest();
                 // To support the ability to run the clippy exercises, build
                 // an executable, in addition to running clippy. With a
                 // compilation failure, this would silently fail. But we expect
                 // clippy to reflect the same failure while compiling later.
                 Command::new("rustc")
                     .args([self.path.to_str().unwrap(), "-o", &temp_file()])
-                    .args(RUSTC_COLOR_ARGS)
+                    .args(rustc_color_args())
                     .args(RUSTC_EDITION_ARGS)
                     .args(RUSTC_NO_DEBUG_ARGS)
+                    .args(self.rustc_build_args())
                     .stdin(Stdio::null())
                     .stdout(Stdio::null())
                     .stderr(Stdio::null())
-                    .status()
+                    .tracked_status()
                     .expect("Failed to compile!");
                 // Due to an issue with Clippy, a cargo clean is required to catch all lints.
                 // See https://github.com/rust-lang/rust-clippy/issues/2604
                 // This is already fixed on Clippy's master branch. See this issue to track merging into Cargo:
                 // https://github.com/rust-lang/rust-clippy/issues/3837
                 Command::new("cargo")
-                    .args(["clean", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
+                    .args(["clean", "--manifest-path"])
+                    .arg(&manifest_path)
+                    .args(["--package", &self.name, "--target-dir"])
+                    .arg(cargo_target_dir())
+                    .args(rustc_color_args())
                     .stdin(Stdio::null())
                     .stdout(Stdio::null())
                     .stderr(Stdio::null())
-                    .status()
+                    .tracked_status()
                     .expect("Failed to run 'cargo clean'");
-                Command::new("cargo")
-                    .args(["clippy", "--manifest-path", CLIPPY_CARGO_TOML_PATH])
-                    .args(RUSTC_COLOR_ARGS)
+                cargo_build_options(Command::new("cargo").args(["clippy", "--manifest-path"]))
+                    .arg(&manifest_path)
+                    .arg("--target-dir")
+                    .arg(cargo_target_dir())
+                    .args(rustc_color_args())
                     .args(["--", "-D", "warnings", "-D", "clippy::float_cmp"])
-                    .output()
+                    .args(self.allow_args())
+                    .tracked_output()
             }
         }
         .expect("Failed to run 'compile' command.");
 
+        if let Some(message) = rules::check(self).filter(|_| cmd.status.success()) {
+            clean();
+            return Err(ExerciseOutput {
+                stdout: String::new(),
+                stderr: message,
+            });
+        }
         if cmd.status.success() {
             Ok(CompiledExercise {
                 exercise: self,
@@ -202,22 +976,74 @@ path = "{}.rs""#,
         }
     }
 
-    fn run(&self) -> Result<ExerciseOutput, ExerciseOutput> {
-        let arg = match self.mode {
-            Mode::Test => "--show-output",
-            _ => "",
+    fn run(&self, timeout: Option<Duration>) -> Result<ExerciseOutput, ExerciseOutput> {
+        let mut cmd = match (self.mode, &self.harness) {
+            (Mode::Test, Some(harness)) => {
+                let mut cmd = exercise_command();
+                cmd.args(&harness.args);
+                cmd
+            }
+            (Mode::Test, None) if build_options().nextest => self.nextest_command(),
+            (Mode::Test, None) => {
+                let mut cmd = exercise_command();
+                cmd.arg("--show-output");
+                cmd
+            }
+            _ => {
+                let mut cmd = exercise_command();
+                cmd.arg("");
+                cmd
+            }
         };
-        let cmd = Command::new(temp_file())
-            .arg(arg)
-            .output()
-            .expect("Failed to run 'run' command");
+        let start = Instant::now();
+        let (status, mut output) =
+            capture(self, &mut cmd, timeout).expect("Failed to run 'run' command");
+        trace!(
+            "Running {} exited with {status} after {:?}",
+            self.name,
+            start.elapsed()
+        );
+        LAST_TIMINGS.set(Timings {
+            run: Some(start.elapsed()),
+            ..LAST_TIMINGS.get()
+        });
 
-        let output = ExerciseOutput {
-            stdout: String::from_utf8_lossy(&cmd.stdout).to_string(),
-            stderr: String::from_utf8_lossy(&cmd.stderr).to_string(),
-        };
+        // Exceeding the CPU time limit kills the process with a signal.
+        let stopped = status.code().is_none() && ulimit_script().is_some();
+        if stopped {
+            output.stderr.push_str(
+                "\nThe exercise was stopped. It might have exceeded the configured resource limits.\n",
+            );
+        }
 
-        if cmd.status.success() {
+        let passed = match (self.mode, &self.harness) {
+            (Mode::Test, Some(harness)) => harness
+                .passed(status.success(), &output)
+                .unwrap_or_else(|e| {
+                    output.stderr.push_str(&format!(
+                        "\nInvalid `success_output` of the exercise: {e}\n"
+                    ));
+                    false
+                }),
+            _ => status.success(),
+        };
+        if passed && self.mutants {
+            // Testing the mutants shouldn't count as the run time of the exercise.
+            let timings = LAST_TIMINGS.get();
+            let survivor = mutants::surviving(self, timings.run.unwrap_or_default());
+            LAST_TIMINGS.set(timings);
+            if let Some(mutation) = survivor {
+                output.stdout.push_str(&format!("\n{mutation}\n"));
+                exit_code::record(Some(Failure::Tests));
+                return Err(output);
+            }
+        }
+        exit_code::record(match (passed, stopped && exceeded_cpu_time(status)) {
+            (true, _) => None,
+            (false, true) => Some(Failure::Timeout),
+            (false, false) => Some(Failure::Tests),
+        });
+        if passed {
             Ok(output)
         } else {
             Err(output)
@@ -322,13 +1148,74 @@ path = "{}.rs""#,
         }
     }
 
+    // The topic is the name of the exercise's directory without the number prefix,
+    // e.g. `vecs` for `exercises/05_vecs/vecs1.rs`.
+    // Exercises directly in `exercises/` like the quizzes don't have a topic.
+    pub fn topic(&self) -> &str {
+        topic_of(&self.path)
+    }
+
+    // The official solution mirrors the exercise in the `solutions` directory,
+    // e.g. `solutions/05_vecs/vecs1.rs` for `exercises/05_vecs/vecs1.rs`.
+    pub fn solution_path(&self) -> PathBuf {
+        Path::new(SOLUTIONS_DIR).join(self.path.strip_prefix("exercises").unwrap_or(&self.path))
+    }
+
+    // A program that isn't an exercise of the course, but is compiled and run like one,
+    // e.g. the code of an expression exercise. Warnings don't matter in these programs.
+    pub fn program(name: &str, path: PathBuf) -> Self {
+        Self {
+            name: name.to_string(),
+            path,
+            mode: Mode::Compile,
+            hint: String::new(),
+            optional: false,
+            aliases: Vec::new(),
+            deny_warnings: Some(false),
+            allow: vec![String::from("warnings")],
+            harness: None,
+            points: None,
+            test_points: BTreeMap::new(),
+            parts: Vec::new(),
+            params: BTreeMap::new(),
+            mutants: false,
+            passes_unmodified: false,
+            tags: Vec::new(),
+            rules: Rules::default(),
+        }
+    }
+
+    // The parts whose tests ran and all passed. A part without tests is never done.
+    pub fn parts_done(&self, output: &ExerciseOutput) -> Vec<&str> {
+        let results = output.test_results();
+        self.parts
+            .iter()
+            .filter(|part| {
+                let mut tests = results
+                    .iter()
+                    .filter(|(test, _)| test.split("::").any(|module| module == *part))
+                    .peekable();
+                tests.peek().is_some() && tests.all(|&(_, passed)| passed)
+            })
+            .map(String::as_str)
+            .collect()
+    }
+
     // Check that the exercise looks to be solved using self.state()
     // This is not the best way to check since
     // the user can just remove the "I AM NOT DONE" string from the file
     // without actually having solved anything.
     // The only other way to truly check this would to compile and run
     // the exercise; which would be both costly and counterintuitive
-    pub fn looks_done(&self) -> bool {
+    // Courses without the comment use the last verification from the state file instead,
+    // which callers load once for all the exercises they check.
+    pub fn looks_done(&self, state: &StateFile) -> bool {
+        if !uses_not_done_marker() {
+            return state
+                .exercises
+                .get(&self.name)
+                .is_some_and(|record| record.completed_at.is_some() && !record.regressed);
+        }
         self.state() == State::Done
     }
 }
@@ -339,6 +1226,89 @@ impl Display for Exercise {
     }
 }
 
+// The topic of an exercise at the given path, see `Exercise::topic`
+pub fn topic_of(path: &Path) -> &str {
+    let dir = path
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .and_then(|dir| dir.to_str())
+        .unwrap_or_default();
+    if dir == "exercises" {
+        return "";
+    }
+    dir.trim_start_matches(|c: char| c.is_ascii_digit())
+        .trim_start_matches('_')
+}
+
+// The topics of the exercises in the order of the course
+pub fn topics(exercises: &[Exercise]) -> Vec<&str> {
+    let mut topics = Vec::new();
+    for exercise in exercises {
+        let topic = exercise.topic();
+        if !topic.is_empty() && !topics.contains(&topic) {
+            topics.push(topic);
+        }
+    }
+    topics
+}
+
+// Check that one of the exercises has exactly the given topic, otherwise return a message
+// that lists the valid ones
+pub fn check_topic(topic: &str, exercises: &[Exercise]) -> Result<(), String> {
+    let topics = topics(exercises);
+    if topics.contains(&topic) {
+        return Ok(());
+    }
+    if topics.is_empty() {
+        return Err(format!(
+            "Unknown topic '{topic}', the exercises have no topics"
+        ));
+    }
+    Err(format!(
+        "Unknown topic '{topic}'. The topics are: {}",
+        topics.join(", ")
+    ))
+}
+
+// The number of exercises that are compiled at the same time by default
+pub fn num_cpus() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+// Run the function for each exercise on up to `workers` threads and return the results
+// in the order of the exercises
+pub fn for_each_in_parallel<T: Send>(
+    exercises: &[Exercise],
+    workers: usize,
+    f: impl Fn(&Exercise) -> T + Sync,
+) -> Vec<T> {
+    let results = exercises
+        .iter()
+        .map(|_| Mutex::new(None))
+        .collect::<Vec<_>>();
+    let next = AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for _ in 0..workers.min(exercises.len()) {
+            scope.spawn(|| loop {
+                let ind = next.fetch_add(1, Ordering::Relaxed);
+                let Some(exercise) = exercises.get(ind) else {
+                    break;
+                };
+                *results[ind].lock().unwrap() = Some(f(exercise));
+            });
+        }
+    });
+    results
+        .into_iter()
+        .map(|result| {
+            result
+                .into_inner()
+                .unwrap()
+                .expect("every exercise was processed")
+        })
+        .collect()
+}
+
 #[inline]
 fn clean() {
     let _ignored = remove_file(temp_file());
@@ -349,15 +1319,124 @@ mod test {
     use super::*;
     use std::path::Path;
 
+    #[test]
+    fn test_timings_display() {
+        let timings = Timings {
+            compile: Some(Duration::from_millis(1250)),
+            run: None,
+        };
+        assert_eq!(timings.to_string(), "Compiled in 1.25s");
+        let timings = Timings {
+            run: Some(Duration::from_millis(30)),
+            ..timings
+        };
+        assert_eq!(timings.to_string(), "Compiled in 1.25s, ran in 0.03s");
+    }
+
+    #[test]
+    fn test_nextest_counts() {
+        let output = ExerciseOutput {
+            stdout: String::new(),
+            stderr: String::from(
+                "    Starting 3 tests across 1 binary
+        PASS [   0.003s] exercise tests::first
+        FAIL [   0.004s] exercise tests::second
+        PASS [   0.003s] exercise tests::third
+------------
+     Summary [   0.005s] 3 tests run: 2 passed, 1 failed, 0 skipped
+",
+            ),
+        };
+        assert_eq!(
+            output.test_counts(),
+            Some(TestCounts {
+                passed: 2,
+                total: 3
+            })
+        );
+        assert_eq!(output.passed_tests(), ["tests::first", "tests::third"]);
+    }
+
+    #[test]
+    fn test_passed_tests() {
+        let output = ExerciseOutput {
+            stdout: String::from(
+                "running 2 tests
+test tests::first ... ok
+test tests::second ... FAILED
+",
+            ),
+            stderr: String::new(),
+        };
+        assert_eq!(output.passed_tests(), ["tests::first"]);
+    }
+
+    #[test]
+    fn test_blanks() {
+        let source = "fn add(a: i32, b: i32) -> __???return_type__ {\n    a __???__ b\n}\n";
+        assert_eq!(
+            blanks(source),
+            [
+                Blank {
+                    line: 1,
+                    id: Some(String::from("return_type"))
+                },
+                Blank { line: 2, id: None }
+            ]
+        );
+        assert!(blanks("let x = 5;").is_empty());
+    }
+
+    #[test]
+    fn test_parts_done() {
+        let exercise: Exercise = toml_edit::de::from_str(
+            "name = \"parts\"\npath = \"parts.rs\"\nmode = \"test\"\nhint = \"\"\nparts = [\"parsing\", \"validation\", \"report\"]",
+        )
+        .unwrap();
+        let output = ExerciseOutput {
+            stdout: String::from(
+                "running 3 tests
+test tests::parsing::empty ... ok
+test tests::parsing::numbers ... ok
+test tests::validation::negative ... FAILED
+",
+            ),
+            stderr: String::new(),
+        };
+        assert_eq!(exercise.parts_done(&output), ["parsing"]);
+        assert!(exercise.parts_done(&ExerciseOutput::default()).is_empty());
+    }
+
+    #[test]
+    fn test_test_counts() {
+        let output = |stdout: &str| ExerciseOutput {
+            stdout: String::from(stdout),
+            stderr: String::new(),
+        };
+        assert_eq!(
+            output("running 5 tests\n\ntest result: FAILED. 3 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s\n")
+                .test_counts(),
+            Some(TestCounts { passed: 3, total: 5 })
+        );
+        assert_eq!(
+            output("test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out\n")
+                .test_counts(),
+            Some(TestCounts {
+                passed: 1,
+                total: 1
+            })
+        );
+        assert_eq!(output("thread 'main' panicked").test_counts(), None);
+    }
+
     #[test]
     fn test_clean() {
         File::create(temp_file()).unwrap();
-        let exercise = Exercise {
-            name: String::from("example"),
-            path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
-            mode: Mode::Compile,
-            hint: String::from(""),
-        };
+        let exercise = Exercise::for_test(
+            "example",
+            "tests/fixture/state/pending_exercise.rs",
+            Mode::Compile,
+        );
         let compiled = exercise.compile().unwrap();
         drop(compiled);
         assert!(!Path::new(&temp_file()).exists());
@@ -369,13 +1448,9 @@ mod test {
         [Mode::Compile, Mode::Test] // Clippy doesn't like to test
             .iter()
             .for_each(|mode| {
-                let exercise = Exercise {
-                    name: String::from("example"),
-                    // We want a file that does actually compile
-                    path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
-                    mode: *mode,
-                    hint: String::from(""),
-                };
+                // We want a file that does actually compile
+                let exercise =
+                    Exercise::for_test("example", "tests/fixture/state/pending_exercise.rs", *mode);
                 let _ = exercise.compile().unwrap();
                 assert!(!Path::new(&format!("{}.pdb", temp_file())).exists());
             });
@@ -383,12 +1458,11 @@ mod test {
 
     #[test]
     fn test_pending_state() {
-        let exercise = Exercise {
-            name: "pending_exercise".into(),
-            path: PathBuf::from("tests/fixture/state/pending_exercise.rs"),
-            mode: Mode::Compile,
-            hint: String::new(),
-        };
+        let exercise = Exercise::for_test(
+            "pending_exercise",
+            "tests/fixture/state/pending_exercise.rs",
+            Mode::Compile,
+        );
 
         let state = exercise.state();
         let expected = vec![
@@ -424,28 +1498,55 @@ mod test {
 
     #[test]
     fn test_finished_exercise() {
-        let exercise = Exercise {
-            name: "finished_exercise".into(),
-            path: PathBuf::from("tests/fixture/state/finished_exercise.rs"),
-            mode: Mode::Compile,
-            hint: String::new(),
-        };
+        let exercise = Exercise::for_test(
+            "finished_exercise",
+            "tests/fixture/state/finished_exercise.rs",
+            Mode::Compile,
+        );
 
         assert_eq!(exercise.state(), State::Done);
     }
 
     #[test]
     fn test_exercise_with_output() {
-        let exercise = Exercise {
-            name: "exercise_with_output".into(),
-            path: PathBuf::from("tests/fixture/success/testSuccess.rs"),
-            mode: Mode::Test,
-            hint: String::new(),
-        };
+        let exercise = Exercise::for_test(
+            "exercise_with_output",
+            "tests/fixture/success/testSuccess.rs",
+            Mode::Test,
+        );
         let out = exercise.compile().unwrap().run().unwrap();
         assert!(out.stdout.contains("THIS TEST TOO SHALL PASS"));
     }
 
+    #[test]
+    fn test_topic() {
+        let exercise = |path: &str| Exercise::for_test("example", path, Mode::Compile);
+
+        assert_eq!(exercise("exercises/05_vecs/vecs1.rs").topic(), "vecs");
+        assert_eq!(
+            exercise("exercises/06_move_semantics/move_semantics1.rs").topic(),
+            "move_semantics"
+        );
+        assert_eq!(exercise("exercises/quiz1.rs").topic(), "");
+        assert_eq!(exercise("quiz1.rs").topic(), "");
+
+        let exercises = [
+            exercise("exercises/05_vecs/vecs1.rs"),
+            exercise("exercises/quiz1.rs"),
+            exercise("exercises/06_move_semantics/move_semantics1.rs"),
+            exercise("exercises/05_vecs/vecs2.rs"),
+        ];
+        assert_eq!(topics(&exercises), ["vecs", "move_semantics"]);
+        assert!(check_topic("vecs", &exercises).is_ok());
+        assert_eq!(
+            check_topic("vec", &exercises),
+            Err(String::from(
+                "Unknown topic 'vec'. The topics are: vecs, move_semantics"
+            ))
+        );
+        assert!(check_topic("", &exercises).is_err());
+    }
+
     #[test]
     fn test_not_done() {
         assert!(contains_not_done_comment("// I AM NOT DONE"));
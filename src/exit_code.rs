@@ -0,0 +1,60 @@
+use std::cell::Cell;
+
+// The exit codes of Rustlings, so that scripts and CI pipelines can tell failures apart
+// without parsing the output. They are documented in the README and must not change.
+// Invalid command line arguments exit with 2 like every program that uses clap, and Ctrl+C
+// exits with 130 like in shells, i.e. 128 plus the number of the signal.
+
+// Any other failure, e.g. an unknown exercise or an exercise that isn't done yet
+pub const FAILURE: i32 = 1;
+// An exercise doesn't compile, or Clippy has lints for it
+pub const COMPILATION_FAILED: i32 = 3;
+// An exercise compiles, but its tests fail or it exits with an error
+pub const TESTS_FAILED: i32 = 4;
+// An exercise was stopped since it exceeded the CPU time limit of the config
+pub const TIMEOUT: i32 = 5;
+// info.toml or a config file is missing or invalid
+pub const COURSE_ERROR: i32 = 6;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Failure {
+    Compilation,
+    Tests,
+    Timeout,
+}
+
+impl Failure {
+    pub fn code(self) -> i32 {
+        match self {
+            Self::Compilation => COMPILATION_FAILED,
+            Self::Tests => TESTS_FAILED,
+            Self::Timeout => TIMEOUT,
+        }
+    }
+}
+
+thread_local! {
+    // Per thread like the timings of the exercises
+    static LAST_FAILURE: Cell<Option<Failure>> = const { Cell::new(None) };
+}
+
+// Called by every compilation and run of an exercise, `None` if it succeeded
+pub fn record(failure: Option<Failure>) {
+    LAST_FAILURE.set(failure);
+}
+
+// How the last exercise that was compiled or run on this thread failed
+pub fn last_failure() -> Option<Failure> {
+    LAST_FAILURE.get()
+}
+
+// The exit code for the last exercise. Exercises that passed but aren't done yet,
+// e.g. since they still contain the "I AM NOT DONE" comment, exit with `FAILURE`.
+pub fn of_last_exercise() -> i32 {
+    last_failure().map_or(FAILURE, Failure::code)
+}
+
+// Exit with the code of the last exercise
+pub fn exit_for_last_exercise() -> ! {
+    std::process::exit(of_last_exercise())
+}
@@ -0,0 +1,168 @@
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+use std::process::Command;
+
+use crate::state_file::{self, StateFile};
+
+// Remember the error codes of a failed compilation for `rustlings explain`
+// and count them for `rustlings errors`.
+pub fn record_error_codes(exercise: &str, stderr: &str) {
+    let codes = error_codes(stderr);
+    state_file::update(|state| {
+        let record = state.record(exercise);
+        for code in &codes {
+            *record.error_codes.entry(code.clone()).or_default() += 1;
+        }
+        state.last_error_codes = codes;
+    });
+}
+
+// The error codes with their total count and the exercises that reported them,
+// the most frequent first
+fn aggregate(state: &StateFile) -> Vec<(&str, u32, Vec<&str>)> {
+    let mut codes: BTreeMap<&str, (u32, Vec<&str>)> = BTreeMap::new();
+    for (exercise, record) in &state.exercises {
+        for (code, count) in &record.error_codes {
+            let (total, exercises) = codes.entry(code).or_default();
+            *total += count;
+            exercises.push(exercise);
+        }
+    }
+
+    let mut codes = codes
+        .into_iter()
+        .map(|(code, (total, exercises))| (code, total, exercises))
+        .collect::<Vec<_>>();
+    // Stable sort, so codes with the same count stay in order.
+    codes.sort_by_key(|(_, total, _)| std::cmp::Reverse(*total));
+    codes
+}
+
+// Print which error codes were encountered most often and where.
+pub fn errors() -> Result<()> {
+    let state = StateFile::load()?;
+    let codes = aggregate(&state);
+    if codes.is_empty() {
+        println!("No error codes were recorded yet.");
+        return Ok(());
+    }
+
+    println!("{:<7}\t{:<5}\tExercises", "Code", "Count");
+    for (code, total, exercises) in codes {
+        println!("{code:<7}\t{total:<5}\t{}", exercises.join(", "));
+    }
+    println!("\nRun `rustlings explain <code>` to learn more about an error.");
+    Ok(())
+}
+
+// Show the explanations of the given error code or of the errors of the last failed compilation.
+// The course can add notes to the explanations in the `explanations` table of the info.toml file.
+pub fn explain(code: Option<&str>, notes: &BTreeMap<String, String>) -> Result<()> {
+    let codes = match code {
+        Some(code) => match normalize(code) {
+            Some(code) => vec![code],
+            None => bail!("'{code}' is not an error code like E0382"),
+        },
+        None => {
+            let codes = StateFile::load()?.last_error_codes;
+            if codes.is_empty() {
+                bail!("The last failed compilation didn't report any error codes");
+            }
+            codes
+        }
+    };
+
+    let mut text = String::new();
+    for code in &codes {
+        let output = Command::new("rustc")
+            .args(["--explain", code])
+            .output()
+            .context("Failed to run `rustc --explain`")?;
+        if !output.status.success() {
+            bail!("rustc doesn't know the error code {code}");
+        }
+
+        if let Some(note) = notes.get(code) {
+            text.push_str(&format!(
+                "Note from the course about {code}:\n{}\n\n",
+                note.trim()
+            ));
+        }
+        text.push_str(&String::from_utf8_lossy(&output.stdout));
+        text.push('\n');
+    }
+    crate::ui::page(&text);
+
+    Ok(())
+}
+
+// Accepts `E0382`, `e0382` and `382`.
+fn normalize(code: &str) -> Option<String> {
+    let digits = code.strip_prefix(['E', 'e']).unwrap_or(code);
+    if digits.is_empty() || digits.len() > 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    Some(format!("E{digits:0>4}"))
+}
+
+// The unique codes of errors like `error[E0382]: borrow of moved value` in their order.
+fn error_codes(stderr: &str) -> Vec<String> {
+    let mut codes: Vec<String> = Vec::new();
+    for (_, rest) in stderr
+        .match_indices("error[E")
+        .map(|(ind, _)| stderr.split_at(ind + 6))
+    {
+        let Some((code, _)) = rest.split_once(']') else {
+            continue;
+        };
+        if normalize(code).as_deref() == Some(code) && !codes.iter().any(|c| c == code) {
+            codes.push(code.to_string());
+        }
+    }
+    codes
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("E0382").as_deref(), Some("E0382"));
+        assert_eq!(normalize("e0382").as_deref(), Some("E0382"));
+        assert_eq!(normalize("382").as_deref(), Some("E0382"));
+        assert_eq!(normalize("E"), None);
+        assert_eq!(normalize("borrow"), None);
+    }
+
+    #[test]
+    fn test_aggregate() {
+        let mut state = StateFile::default();
+        state
+            .record("move_semantics1")
+            .error_codes
+            .extend([(String::from("E0382"), 2), (String::from("E0308"), 1)]);
+        state
+            .record("move_semantics2")
+            .error_codes
+            .insert(String::from("E0382"), 3);
+
+        let codes = aggregate(&state);
+        assert_eq!(codes.len(), 2);
+        assert_eq!(
+            codes[0],
+            ("E0382", 5, vec!["move_semantics1", "move_semantics2"])
+        );
+        assert_eq!(codes[1], ("E0308", 1, vec!["move_semantics1"]));
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let stderr = "error[E0382]: borrow of moved value: `vec0`
+error: aborting due to previous error
+error[E0308]: mismatched types
+error[E0382]: borrow of moved value: `vec1`";
+        assert_eq!(error_codes(stderr), ["E0382", "E0308"]);
+        assert!(error_codes("error: expected `;`").is_empty());
+    }
+}
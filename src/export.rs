@@ -0,0 +1,178 @@
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use std::fmt::Write;
+use std::fs;
+
+use crate::exercise::Exercise;
+use crate::graph::topics;
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum ExportFormat {
+    /// A single Markdown document
+    Markdown,
+    /// A standalone HTML page that can be printed
+    Html,
+}
+
+// An exercise as it appears in the workbook
+struct Page {
+    name: String,
+    description: String,
+    code: String,
+    solution: Option<String>,
+}
+
+// Split the exercise into the description from its leading comment and the starter code.
+// The file name, the hint instructions and the "I AM NOT DONE" comment are left out.
+fn split(source: &str) -> (String, String) {
+    let mut description = Vec::new();
+    let mut lines = source.lines().peekable();
+    while let Some(line) = lines.next_if(|line| line.trim().is_empty() || line.starts_with("//")) {
+        let line = line.trim_start_matches("//").trim();
+        if (line.ends_with(".rs") && !line.contains(' '))
+            || line == "I AM NOT DONE"
+            || line.starts_with("Execute `rustlings hint")
+            || line == "hint."
+        {
+            continue;
+        }
+        description.push(line);
+    }
+
+    let description = description.join("\n").trim().to_string();
+    let code = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+    (description, code)
+}
+
+fn page(exercise: &Exercise, with_solutions: bool) -> Result<Page> {
+    let source =
+        fs::read_to_string(&exercise.path).with_context(|| format!("Failed to read {exercise}"))?;
+    let (description, code) = split(&source);
+    let solution = with_solutions
+        .then(|| fs::read_to_string(exercise.solution_path()).ok())
+        .flatten()
+        .map(|solution| split(&solution).1);
+    Ok(Page {
+        name: exercise.name.clone(),
+        description,
+        code,
+        solution,
+    })
+}
+
+fn markdown(title: &str, topics: &[(&str, Vec<Page>)]) -> String {
+    let mut out = format!("# {title}\n");
+    for (topic, pages) in topics {
+        if !topic.is_empty() {
+            let _ = write!(out, "\n## {topic}\n");
+        }
+        for page in pages {
+            let _ = write!(out, "\n### {}\n\n", page.name);
+            if !page.description.is_empty() {
+                let _ = write!(out, "{}\n\n", page.description);
+            }
+            let _ = writeln!(out, "```rust\n{}\n```", page.code);
+            if let Some(solution) = &page.solution {
+                let _ = writeln!(out, "\nSolution:\n\n```rust\n{solution}\n```");
+            }
+        }
+    }
+    out
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn html(title: &str, topics: &[(&str, Vec<Page>)]) -> String {
+    let title = escape(title);
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+        <style>pre {{ background: #f6f8fa; padding: 1em; }} .exercise {{ break-inside: avoid; }}</style>\n\
+        </head>\n<body>\n<h1>{title}</h1>\n"
+    );
+    for (topic, pages) in topics {
+        if !topic.is_empty() {
+            let _ = writeln!(out, "<h2>{}</h2>", escape(topic));
+        }
+        for page in pages {
+            let _ = writeln!(
+                out,
+                "<section class=\"exercise\">\n<h3>{}</h3>",
+                escape(&page.name)
+            );
+            for paragraph in page.description.split("\n\n").filter(|p| !p.is_empty()) {
+                let _ = writeln!(out, "<p>{}</p>", escape(paragraph));
+            }
+            let _ = writeln!(out, "<pre><code>{}</code></pre>", escape(&page.code));
+            if let Some(solution) = &page.solution {
+                let _ = writeln!(
+                    out,
+                    "<p>Solution:</p>\n<pre><code>{}</code></pre>",
+                    escape(solution)
+                );
+            }
+            out.push_str("</section>\n");
+        }
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+// Render the course as a workbook with the description and starter code of each exercise,
+// and optionally the official solutions, for offline study and handouts.
+pub fn export(
+    exercises: &[Exercise],
+    title: &str,
+    format: ExportFormat,
+    with_solutions: bool,
+) -> Result<String> {
+    let mut pages = Vec::new();
+    for (topic, topic_exercises) in topics(exercises) {
+        let topic_pages = topic_exercises
+            .into_iter()
+            .map(|exercise| page(exercise, with_solutions))
+            .collect::<Result<Vec<_>>>()?;
+        pages.push((topic, topic_pages));
+    }
+
+    Ok(match format {
+        ExportFormat::Markdown => markdown(title, &pages),
+        ExportFormat::Html => html(title, &pages),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let source = "// vecs1.rs
+//
+// Create a `Vec`.
+//
+// Execute `rustlings hint vecs1` or use the `hint` watch subcommand for a
+// hint.
+
+// I AM NOT DONE
+
+fn main() {}
+";
+        assert_eq!(
+            split(source),
+            (
+                String::from("Create a `Vec`."),
+                String::from("fn main() {}")
+            )
+        );
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("Vec<&str>"), "Vec&lt;&amp;str&gt;");
+    }
+}
@@ -0,0 +1,104 @@
+use regex::{Captures, Regex};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::exercise::Exercise;
+
+// The line below the output of the current exercise in watch mode. It can be changed with
+// `footer` in the `[watch]` section of the config, e.g. to leave out the commands or to show
+// the topic. The variables are written like the ones of exercise templates, e.g. `{{done}}`.
+// An empty footer isn't shown.
+pub const DEFAULT_FOOTER: &str = "{{done}}/{{total}} done | {{path}} | {{keys}}";
+
+const VARIABLES: &[&str] = &[
+    "done", "total", "percent", "exercise", "path", "topic", "keys",
+];
+
+// The most used commands of watch mode
+const KEYS: &str = "Type 'hint', 'edit' or 'help'";
+
+// Experienced users can hide the commands to leave more space for the output of the compiler,
+// with `show_keys = false` in the config or the `keys` command of watch mode.
+static SHOW_KEYS: AtomicBool = AtomicBool::new(true);
+
+pub fn set_show_keys(show: bool) {
+    SHOW_KEYS.store(show, Ordering::Relaxed);
+}
+
+pub fn show_keys() -> bool {
+    SHOW_KEYS.load(Ordering::Relaxed)
+}
+
+// Returns whether the commands are shown now.
+pub fn toggle_keys() -> bool {
+    !SHOW_KEYS.fetch_xor(true, Ordering::Relaxed)
+}
+
+pub struct Footer<'a> {
+    pub num_done: usize,
+    pub total: usize,
+    pub exercise: &'a Exercise,
+}
+
+pub fn render(template: &str, footer: &Footer) -> String {
+    render_with_keys(template, footer, show_keys())
+}
+
+// Without the keys, separators like ` | ` at the end are removed as well.
+fn render_with_keys(template: &str, footer: &Footer, show_keys: bool) -> String {
+    let regex = Regex::new(&format!(r"\{{\{{\s*({})\s*\}}\}}", VARIABLES.join("|"))).unwrap();
+    let text = regex
+        .replace_all(template, |captures: &Captures| match &captures[1] {
+            "done" => footer.num_done.to_string(),
+            "total" => footer.total.to_string(),
+            "percent" => (footer.num_done * 100)
+                .checked_div(footer.total)
+                .unwrap_or(100)
+                .to_string(),
+            "exercise" => footer.exercise.name.clone(),
+            "path" => footer.exercise.path.display().to_string(),
+            "topic" => footer.exercise.topic().to_string(),
+            _ if show_keys => KEYS.to_string(),
+            _ => String::new(),
+        })
+        .into_owned();
+    if show_keys {
+        text
+    } else {
+        text.trim_end_matches([' ', '|']).to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+
+    #[test]
+    fn test_render() {
+        let exercise = Exercise::for_test(
+            "iterators3",
+            "exercises/18_iterators/iterators3.rs",
+            Mode::Test,
+        );
+        let footer = Footer {
+            num_done: 42,
+            total: 96,
+            exercise: &exercise,
+        };
+        assert_eq!(
+            render(DEFAULT_FOOTER, &footer),
+            "42/96 done | exercises/18_iterators/iterators3.rs | Type 'hint', 'edit' or 'help'"
+        );
+        assert_eq!(
+            render(
+                "{{ topic }}: {{exercise}} ({{percent}}%) {{unknown}}",
+                &footer
+            ),
+            "iterators: iterators3 (43%) {{unknown}}"
+        );
+        assert_eq!(
+            render_with_keys(DEFAULT_FOOTER, &footer, false),
+            "42/96 done | exercises/18_iterators/iterators3.rs"
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use crate::exercise::Exercise;
+
+// Exercise names are easy to mistype, e.g. `Iterators 3` or `move-semantics2` for
+// `move_semantics2`. The case and all characters except letters and digits are ignored.
+fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+// The number of inserted, removed or replaced characters to get from `a` to `b`
+fn distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+// The exercise whose name matches `name` when ignoring the case and separators, or the names
+// of up to three similar exercises to suggest instead.
+pub fn find<'a>(name: &str, exercises: &'a [Exercise]) -> Result<&'a Exercise, Vec<&'a str>> {
+    let normalized = normalize(name);
+    let mut matches = exercises
+        .iter()
+        .filter(|e| normalize(&e.name) == normalized);
+    if let (Some(exercise), None) = (matches.next(), matches.next()) {
+        return Ok(exercise);
+    }
+
+    let max_distance = (normalized.len() / 3).max(2);
+    let mut similar: Vec<(usize, &str)> = exercises
+        .iter()
+        .map(|e| (distance(&normalized, &normalize(&e.name)), e.name.as_str()))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .collect();
+    similar.sort_by_key(|&(distance, _)| distance);
+    Err(similar.into_iter().take(3).map(|(_, name)| name).collect())
+}
+
+// E.g. ` Did you mean 'vecs1' or 'vecs2'?`
+pub fn did_you_mean(suggestions: &[&str]) -> String {
+    let quoted: Vec<String> = suggestions.iter().map(|name| format!("'{name}'")).collect();
+    match quoted.as_slice() {
+        [] => String::new(),
+        [only] => format!(" Did you mean {only}?"),
+        [rest @ .., last] => format!(" Did you mean {} or {last}?", rest.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+
+    fn exercises(names: &[&str]) -> Vec<Exercise> {
+        names
+            .iter()
+            .map(|name| Exercise::for_test(name, &format!("exercises/{name}.rs"), Mode::Compile))
+            .collect()
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("vecs1", "vecs1"), 0);
+        assert_eq!(distance("vec1", "vecs1"), 1);
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_find() {
+        let exercises = exercises(&["vecs1", "vecs2", "move_semantics2", "iterators3"]);
+        assert_eq!(find("Iterators 3", &exercises).unwrap().name, "iterators3");
+        assert_eq!(
+            find("move-semantics2", &exercises).unwrap().name,
+            "move_semantics2"
+        );
+        assert_eq!(find("vec1", &exercises).unwrap_err(), ["vecs1", "vecs2"]);
+        assert!(find("traits4", &exercises).unwrap_err().is_empty());
+    }
+
+    #[test]
+    fn test_did_you_mean() {
+        assert_eq!(did_you_mean(&[]), "");
+        assert_eq!(did_you_mean(&["vecs1"]), " Did you mean 'vecs1'?");
+        assert_eq!(
+            did_you_mean(&["vecs1", "vecs2", "vecs3"]),
+            " Did you mean 'vecs1', 'vecs2' or 'vecs3'?"
+        );
+    }
+}
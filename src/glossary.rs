@@ -0,0 +1,73 @@
+use console::style;
+use serde::Deserialize;
+
+// A term explained in the glossary of the course.
+// This is deserialized from the `glossary` array in the info.toml file.
+#[derive(Deserialize, Clone, Debug)]
+pub struct GlossaryEntry {
+    pub term: String,
+    pub definition: String,
+    // The topics of the exercises that the term is relevant for, e.g. `move_semantics`
+    #[serde(default)]
+    pub topics: Vec<String>,
+}
+
+// Show the entries whose term or definition contains the search term,
+// or the whole glossary if there is no search term.
+pub fn glossary(entries: &[GlossaryEntry], search: Option<&str>) -> Result<(), ()> {
+    let matches = match search {
+        Some(search) => {
+            let search = search.to_lowercase();
+            let mut matches = entries
+                .iter()
+                .filter(|entry| {
+                    entry.term.to_lowercase().contains(&search)
+                        || entry.definition.to_lowercase().contains(&search)
+                })
+                .collect::<Vec<_>>();
+            // Matching terms are more relevant than matching definitions.
+            matches.sort_by_key(|entry| !entry.term.to_lowercase().contains(&search));
+            matches
+        }
+        None => entries.iter().collect(),
+    };
+
+    if matches.is_empty() {
+        match search {
+            Some(search) => println!("The glossary doesn't mention '{search}'."),
+            None => println!("This course doesn't have a glossary."),
+        }
+        return Err(());
+    }
+
+    crate::ui::page(&render(&matches));
+    Ok(())
+}
+
+// Show the entries relevant for the topic of an exercise.
+pub fn glossary_for_topic(entries: &[GlossaryEntry], topic: &str) {
+    let matches = entries
+        .iter()
+        .filter(|entry| entry.topics.iter().any(|t| t == topic))
+        .collect::<Vec<_>>();
+
+    if matches.is_empty() {
+        println!("The glossary doesn't have entries for the topic '{topic}'.");
+    } else {
+        crate::ui::page(&render(&matches));
+    }
+}
+
+fn render(entries: &[&GlossaryEntry]) -> String {
+    entries
+        .iter()
+        .map(|entry| {
+            format!(
+                "{}\n{}\n",
+                style(&entry.term).bold().underlined(),
+                entry.definition.trim()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
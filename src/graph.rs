@@ -0,0 +1,164 @@
+use clap::ValueEnum;
+use std::fmt::Write;
+
+use crate::exercise::Exercise;
+use crate::quiz::Quiz;
+use crate::state_file::StateFile;
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum GraphFormat {
+    /// Graphviz DOT, e.g. for `dot -Tsvg`
+    Dot,
+    /// Mermaid flowchart, e.g. for Markdown files on GitHub
+    Mermaid,
+}
+
+// The exercises grouped by topic in the order of the course
+pub fn topics(exercises: &[Exercise]) -> Vec<(&str, Vec<&Exercise>)> {
+    let mut topics: Vec<(&str, Vec<&Exercise>)> = Vec::new();
+    for exercise in exercises {
+        match topics
+            .iter_mut()
+            .find(|(topic, _)| *topic == exercise.topic())
+        {
+            Some((_, topic_exercises)) => topic_exercises.push(exercise),
+            None => topics.push((exercise.topic(), vec![exercise])),
+        }
+    }
+    topics
+}
+
+// The structure of the course: the exercises grouped by topic and connected in the
+// recommended order, with the quizzes after their exercises.
+// Quizzes get the prefix `quiz_` since they can have the same name as an exercise.
+// Done exercises are green, pending ones red and optional ones have a dashed border.
+pub fn graph(exercises: &[Exercise], quizzes: &[Quiz], format: GraphFormat) -> String {
+    let state = StateFile::load().unwrap_or_default();
+    match format {
+        GraphFormat::Dot => dot(exercises, quizzes, &state),
+        GraphFormat::Mermaid => mermaid(exercises, quizzes, &state),
+    }
+}
+
+fn dot(exercises: &[Exercise], quizzes: &[Quiz], state: &StateFile) -> String {
+    let mut out =
+        String::from("digraph rustlings {\n    rankdir=LR;\n    node [shape=box, style=filled];\n");
+
+    let node = |exercise: &Exercise| {
+        let color = if exercise.looks_done(state) {
+            "palegreen"
+        } else {
+            "lightpink"
+        };
+        let style = if exercise.optional {
+            ", style=\"filled,dashed\""
+        } else {
+            ""
+        };
+        format!("\"{}\" [fillcolor={color}{style}];", exercise.name)
+    };
+    for (topic, topic_exercises) in topics(exercises) {
+        if topic.is_empty() {
+            for exercise in topic_exercises {
+                let _ = writeln!(out, "    {}", node(exercise));
+            }
+            continue;
+        }
+
+        let _ = writeln!(
+            out,
+            "    subgraph \"cluster_{topic}\" {{\n        label=\"{topic}\";"
+        );
+        for exercise in topic_exercises {
+            let _ = writeln!(out, "        {}", node(exercise));
+        }
+        out.push_str("    }\n");
+    }
+
+    for pair in exercises.windows(2) {
+        let _ = writeln!(out, "    \"{}\" -> \"{}\";", pair[0].name, pair[1].name);
+    }
+    for quiz in quizzes {
+        let _ = writeln!(
+            out,
+            "    \"quiz_{0}\" [label=\"{0}\", shape=diamond, fillcolor=lightyellow];\n    \"{1}\" -> \"quiz_{0}\";",
+            quiz.name, quiz.after
+        );
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn mermaid(exercises: &[Exercise], quizzes: &[Quiz], state: &StateFile) -> String {
+    let mut out = String::from("flowchart LR\n");
+
+    let node = |exercise: &Exercise| {
+        let class = match (exercise.looks_done(state), exercise.optional) {
+            (true, false) => "done",
+            (false, false) => "pending",
+            (true, true) => "optionalDone",
+            (false, true) => "optionalPending",
+        };
+        format!("{0}[\"{0}\"]:::{class}", exercise.name)
+    };
+    for (topic, topic_exercises) in topics(exercises) {
+        if topic.is_empty() {
+            for exercise in topic_exercises {
+                let _ = writeln!(out, "    {}", node(exercise));
+            }
+            continue;
+        }
+
+        let _ = writeln!(out, "    subgraph {topic}");
+        for exercise in topic_exercises {
+            let _ = writeln!(out, "        {}", node(exercise));
+        }
+        out.push_str("    end\n");
+    }
+
+    for pair in exercises.windows(2) {
+        let _ = writeln!(out, "    {} --> {}", pair[0].name, pair[1].name);
+    }
+    for quiz in quizzes {
+        let _ = writeln!(
+            out,
+            "    quiz_{0}{{\"{0}\"}}:::quiz\n    {1} --> quiz_{0}",
+            quiz.name, quiz.after
+        );
+    }
+
+    out.push_str(
+        "    classDef done fill:#9f9
+    classDef pending fill:#f99
+    classDef optionalDone fill:#9f9,stroke-dasharray: 5 5
+    classDef optionalPending fill:#f99,stroke-dasharray: 5 5
+    classDef quiz fill:#ff9
+",
+    );
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+
+    fn exercise(name: &str, path: &str) -> Exercise {
+        Exercise::for_test(name, path, Mode::Compile)
+    }
+
+    #[test]
+    fn test_topics() {
+        let exercises = [
+            exercise("vecs1", "exercises/05_vecs/vecs1.rs"),
+            exercise("quiz1", "exercises/quiz1.rs"),
+            exercise("vecs2", "exercises/05_vecs/vecs2.rs"),
+        ];
+        let topics = topics(&exercises);
+        assert_eq!(topics.len(), 2);
+        assert_eq!(topics[0].0, "vecs");
+        assert_eq!(topics[0].1.len(), 2);
+        assert_eq!(topics[1].0, "");
+    }
+}
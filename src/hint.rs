@@ -0,0 +1,112 @@
+use crate::state_file::{self, ExerciseRecord, StateFile};
+
+// A course can require learners to try an exercise on their own before its hint is shown.
+// The hint unlocks after one of the configured conditions is met.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct HintGate {
+    // The number of failed verifications
+    pub min_failures: Option<u32>,
+    // The minutes since the first failed verification
+    pub min_minutes: Option<u64>,
+}
+
+impl HintGate {
+    // Why the hint is still locked, or `None` if it can be shown
+    fn locked(&self, record: Option<&ExerciseRecord>, now: u64) -> Option<String> {
+        if self.min_failures.is_none() && self.min_minutes.is_none() {
+            return None;
+        }
+
+        let failures = record.map_or(0, |record| record.failures);
+        let first_failed_at = record.and_then(|record| record.first_failed_at);
+        let mut conditions = Vec::new();
+        if let Some(min_failures) = self.min_failures {
+            if failures >= min_failures {
+                return None;
+            }
+            conditions.push(format!(
+                "{min_failures} failed attempts ({failures} so far)"
+            ));
+        }
+        if let Some(min_minutes) = self.min_minutes {
+            match first_failed_at {
+                Some(first_failed_at) => {
+                    let unlocks_at = first_failed_at + min_minutes * 60;
+                    if now >= unlocks_at {
+                        return None;
+                    }
+                    conditions.push(format!(
+                        "{min_minutes} minutes of trying ({} minutes left)",
+                        (unlocks_at - now).div_ceil(60)
+                    ));
+                }
+                None => conditions.push(format!(
+                    "{min_minutes} minutes after the first failed attempt"
+                )),
+            }
+        }
+
+        Some(format!(
+            "Try it on your own first! The hint unlocks after {}.",
+            conditions.join(" or ")
+        ))
+    }
+}
+
+// Show the hint of the exercise if it is unlocked and record that it was viewed.
+pub fn show_hint(name: &str, hint: &str, gate: HintGate) {
+    println!("{}", unlocked_hint(name, hint, gate));
+}
+
+// The hint if it is unlocked, or why it is still locked
+pub fn unlocked_hint(name: &str, hint: &str, gate: HintGate) -> String {
+    unlock_hint(name, hint, gate).unwrap_or_else(|reason| reason)
+}
+
+// Like `unlocked_hint`, but the reason why the hint is still locked is the error.
+pub fn unlock_hint(name: &str, hint: &str, gate: HintGate) -> Result<String, String> {
+    let state = StateFile::load().unwrap_or_default();
+    if let Some(reason) = gate.locked(state.exercises.get(name), state_file::now()) {
+        return Err(reason);
+    }
+
+    state_file::record_hint(name);
+    Ok(hint.to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_locked() {
+        let record = ExerciseRecord {
+            failures: 2,
+            first_failed_at: Some(1000),
+            ..Default::default()
+        };
+
+        assert!(HintGate::default().locked(None, 0).is_none());
+
+        let gate = HintGate {
+            min_failures: Some(3),
+            min_minutes: None,
+        };
+        assert!(gate.locked(Some(&record), 1000).is_some());
+        assert!(gate.locked(None, 1000).is_some());
+
+        let gate = HintGate {
+            min_failures: Some(2),
+            min_minutes: Some(10),
+        };
+        assert!(gate.locked(Some(&record), 1000).is_none());
+
+        let gate = HintGate {
+            min_failures: None,
+            min_minutes: Some(10),
+        };
+        let reason = gate.locked(Some(&record), 1000 + 5 * 60).unwrap();
+        assert!(reason.contains("5 minutes left"));
+        assert!(gate.locked(Some(&record), 1000 + 10 * 60).is_none());
+    }
+}
@@ -0,0 +1,257 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crate::exercise::{last_timings, Exercise};
+use crate::state_file::{self, StateFile};
+
+// Every run of an exercise is appended to this file as one JSON object per line.
+pub const HISTORY_PATH: &str = ".rustlings-history.jsonl";
+
+fn path() -> PathBuf {
+    state_file::progress_path(HISTORY_PATH)
+}
+
+// Runs with a longer pause between them belong to different sessions.
+const SESSION_GAP: u64 = 30 * 60;
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Outcome {
+    // The exercise compiles and runs (or its tests pass)
+    Passed,
+    // The exercise doesn't compile or fails
+    Failed,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct HistoryEntry {
+    pub exercise: String,
+    // The command that ran the exercise, e.g. `verify` or `run`
+    pub command: String,
+    pub outcome: Outcome,
+    // How long compiling and running the exercise took
+    pub seconds: f64,
+    // The parts of `seconds` spent compiling and running the exercise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compile_seconds: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_seconds: Option<f64>,
+    // Unix timestamp of when the run finished
+    pub timestamp: u64,
+}
+
+// Append a run that started at `start` to the history.
+// Like the state file, the history must not get in the way of the exercises,
+// so errors are only reported.
+pub fn record(exercise: &str, command: &str, outcome: Outcome, start: Instant) {
+    let timings = last_timings();
+    let entry = HistoryEntry {
+        exercise: exercise.to_string(),
+        command: command.to_string(),
+        outcome,
+        seconds: start.elapsed().as_secs_f64(),
+        compile_seconds: timings.compile.map(|d| d.as_secs_f64()),
+        run_seconds: timings.run.map(|d| d.as_secs_f64()),
+        timestamp: state_file::now(),
+    };
+
+    let result = serde_json::to_string(&entry)
+        .map_err(io::Error::from)
+        .and_then(|line| {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path())?
+                .write_all(format!("{line}\n").as_bytes())
+        });
+    if let Err(e) = result {
+        println!("Failed to append to {}: {e}", path().display());
+    }
+}
+
+pub fn load() -> Result<Vec<HistoryEntry>> {
+    let path = path();
+    let content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(ind, line)| {
+            serde_json::from_str(line)
+                .with_context(|| format!("Failed to parse line {} of {}", ind + 1, path.display()))
+        })
+        .collect()
+}
+
+// Replace the former names of renamed exercises by their current ones, see `state_file::rename_aliases`.
+pub fn rename_exercises(renames: &[(&str, &str)]) {
+    let Ok(mut entries) = load() else {
+        return;
+    };
+    let mut changed = false;
+    for entry in &mut entries {
+        if let Some((_, name)) = renames.iter().find(|(alias, _)| *alias == entry.exercise) {
+            entry.exercise = name.to_string();
+            changed = true;
+        }
+    }
+    if !changed {
+        return;
+    }
+
+    let result = entries
+        .iter()
+        .map(|entry| serde_json::to_string(entry).map(|line| format!("{line}\n")))
+        .collect::<Result<String, _>>()
+        .map_err(io::Error::from)
+        .and_then(|content| fs::write(path(), content));
+    if let Err(e) = result {
+        println!("Failed to rewrite {}: {e}", path().display());
+    }
+}
+
+// Print the last `limit` runs, optionally only of one exercise.
+pub fn history(exercise: Option<&str>, limit: usize) -> Result<()> {
+    let entries = load()?;
+    let entries = entries
+        .iter()
+        .filter(|entry| exercise.map_or(true, |name| entry.exercise == name))
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        println!("No runs were recorded yet.");
+        return Ok(());
+    }
+
+    println!(
+        "{:<19}\t{:<17}\t{:<7}\t{:<7}\tSeconds",
+        "Time (UTC)", "Exercise", "Command", "Outcome"
+    );
+    for entry in &entries[entries.len().saturating_sub(limit)..] {
+        let outcome = match entry.outcome {
+            Outcome::Passed => "passed",
+            Outcome::Failed => "failed",
+        };
+        println!(
+            "{:<19}\t{:<17}\t{:<7}\t{outcome:<7}\t{:.1}",
+            format_timestamp(entry.timestamp),
+            entry.exercise,
+            entry.command,
+            entry.seconds
+        );
+    }
+
+    Ok(())
+}
+
+// The runs of the last session
+fn last_session(entries: &[HistoryEntry]) -> &[HistoryEntry] {
+    let start = entries
+        .windows(2)
+        .rposition(|pair| pair[1].timestamp.saturating_sub(pair[0].timestamp) > SESSION_GAP)
+        .map_or(0, |ind| ind + 1);
+    &entries[start..]
+}
+
+// A summary of the previous session like
+// "Last time: completed 4 exercises, stopped at iterators3, 62% done".
+pub fn resume_summary(exercises: &[Exercise]) -> Option<String> {
+    let entries = load().ok()?;
+    let session = last_session(&entries);
+    let (first, last) = (session.first()?, session.last()?);
+
+    let state = StateFile::load().unwrap_or_default();
+    let mut completed: Vec<&str> = Vec::new();
+    for entry in session {
+        let completed_in_session = state
+            .exercises
+            .get(&entry.exercise)
+            .and_then(|record| record.completed_at)
+            .is_some_and(|completed_at| {
+                (first.timestamp.saturating_sub(first.seconds as u64)..=last.timestamp)
+                    .contains(&completed_at)
+            });
+        if completed_in_session && !completed.contains(&entry.exercise.as_str()) {
+            completed.push(&entry.exercise);
+        }
+    }
+
+    let required = exercises.iter().filter(|e| !e.optional).count();
+    let done = exercises
+        .iter()
+        .filter(|e| !e.optional && e.looks_done(&state))
+        .count();
+    let percentage = done * 100 / required.max(1);
+
+    Some(format!(
+        "Last time: completed {} exercises, stopped at {}, {percentage}% done",
+        completed.len(),
+        last.exercise
+    ))
+}
+
+// Formats a Unix timestamp as `YYYY-MM-DD HH:MM:SS` in UTC.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86400;
+    let seconds = timestamp % 86400;
+
+    // Convert the days since 1970-01-01 to a date in the proleptic Gregorian calendar.
+    // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02}",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn entry(timestamp: u64) -> HistoryEntry {
+        HistoryEntry {
+            exercise: String::from("vecs1"),
+            command: String::from("verify"),
+            outcome: Outcome::Passed,
+            seconds: 1.0,
+            compile_seconds: None,
+            run_seconds: None,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_last_session() {
+        let entries = [entry(0), entry(100), entry(10_000), entry(11_000)];
+        assert_eq!(last_session(&entries).len(), 2);
+        assert_eq!(last_session(&entries[..2]).len(), 2);
+        assert!(last_session(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00:00");
+        assert_eq!(format_timestamp(1_700_000_000), "2023-11-14 22:13:20");
+    }
+}
@@ -0,0 +1,101 @@
+use serde::Serialize;
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// `--json` prints a single JSON document instead of the normal output of `run`, `verify`,
+// `list`, `stats` and `hint`, for editor plugins, grading scripts and dashboards.
+// The schema is documented in the README. Fields are only ever added, never renamed or removed.
+static JSON: AtomicBool = AtomicBool::new(false);
+
+pub fn enable() {
+    JSON.store(true, Ordering::Relaxed);
+    console::set_colors_enabled(false);
+}
+
+pub fn enabled() -> bool {
+    JSON.load(Ordering::Relaxed)
+}
+
+// An exercise that was run or verified
+#[derive(Serialize)]
+pub struct ExerciseResult<'a> {
+    pub name: &'a str,
+    pub path: &'a Path,
+    // Like the results of `--porcelain`, e.g. `passed` or `compilation-failed`
+    pub result: &'static str,
+    pub seconds: f64,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+// The output of `run` and `verify`
+#[derive(Serialize)]
+pub struct Results<'a> {
+    pub passed: bool,
+    pub exercises: Vec<ExerciseResult<'a>>,
+}
+
+#[derive(Serialize)]
+pub struct ListedExercise<'a> {
+    pub name: &'a str,
+    pub path: &'a Path,
+    pub topic: &'a str,
+    // `pending`, `done`, `optional` or `broken`
+    pub status: String,
+    pub optional: bool,
+}
+
+// The output of `list`
+#[derive(Serialize)]
+pub struct List<'a> {
+    pub done: usize,
+    pub total: usize,
+    pub exercises: Vec<ListedExercise<'a>>,
+}
+
+// The output of `stats`
+#[derive(Serialize)]
+pub struct Stats {
+    pub done: usize,
+    pub total: usize,
+    pub seconds_spent: u64,
+    pub failed_verifications: u32,
+    pub hints_viewed: u32,
+}
+
+// The output of `hint`. Either the hint or the reason why it is still locked is set.
+#[derive(Serialize)]
+pub struct Hint<'a> {
+    pub name: &'a str,
+    pub hint: Option<String>,
+    pub locked: Option<String>,
+}
+
+// Like `list`, a closed pipe, e.g. to `head`, isn't an error.
+pub fn print(value: &impl Serialize) {
+    let json = serde_json::to_string_pretty(value).expect("the output can be serialized");
+    if let Err(e) = writeln!(io::stdout(), "{json}") {
+        if e.kind() != io::ErrorKind::BrokenPipe {
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_hint_schema() {
+        let hint = Hint {
+            name: "vecs1",
+            hint: None,
+            locked: Some(String::from("Try once more")),
+        };
+        assert_eq!(
+            serde_json::to_string(&hint).unwrap(),
+            r#"{"name":"vecs1","hint":null,"locked":"Try once more"}"#
+        );
+    }
+}
@@ -0,0 +1,984 @@
+use anyhow::{bail, Result};
+use console::{style, Alignment, Key, Term};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, IsTerminal};
+
+use crate::bookmarks;
+use crate::exercise::Exercise;
+use crate::footer;
+use crate::hint::{unlocked_hint, HintGate};
+use crate::recheck::{self, is_regressed};
+use crate::run::reset;
+use crate::state_file::{self, StateFile};
+use crate::theme;
+use crate::ui;
+
+// The status of an exercise in `rustlings list`
+pub fn status(exercise: &Exercise, state: &StateFile) -> &'static str {
+    if exercise.looks_done(state) {
+        if is_regressed(exercise, state) {
+            "Broken"
+        } else {
+            "Done"
+        }
+    } else if exercise.optional {
+        "Optional"
+    } else {
+        "Pending"
+    }
+}
+
+const KEYS: &str = "j/k move | space select | v range | r reset | p mark pending | f first selected | \
+     b bookmark | s source | h hint | : go to | 1-5 all/pending/done/current/bookmarked | t topic | \
+     # tag | x no filter | ? keys | q quit";
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusFilter {
+    Pending,
+    Done,
+    // The exercises of the topic that watch mode is working on
+    Current,
+    Bookmarked,
+}
+
+// The exercises that the interactive list shows. It is stored in the state file, so that the
+// list shows the same exercises the next time.
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+pub struct ListFilter {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<StatusFilter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+impl ListFilter {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    // E.g. `pending | topic vecs`
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        match self.status {
+            Some(StatusFilter::Pending) => parts.push(String::from("pending")),
+            Some(StatusFilter::Done) => parts.push(String::from("done")),
+            Some(StatusFilter::Current) => parts.push(String::from("current topic")),
+            Some(StatusFilter::Bookmarked) => parts.push(String::from("bookmarked")),
+            None => (),
+        }
+        if let Some(topic) = &self.topic {
+            parts.push(format!("topic {topic}"));
+        }
+        if let Some(tag) = &self.tag {
+            parts.push(format!("tag {tag}"));
+        }
+        parts.join(" | ")
+    }
+}
+
+// The item after `current` in `items`, or `None` after the last one
+fn next_of(items: &[String], current: Option<&String>) -> Option<String> {
+    match current {
+        None => items.first().cloned(),
+        Some(current) => items
+            .iter()
+            .skip_while(|item| *item != current)
+            .nth(1)
+            .cloned(),
+    }
+}
+
+// The list of `rustlings list --interactive`. Several exercises can be selected with space or
+// as a range with `v`, to reset them or to mark them as pending at once. Instructors use it to
+// prepare a machine for the next student and learners to redo a whole topic.
+struct ListUi<'a> {
+    exercises: &'a [Exercise],
+    statuses: Vec<&'static str>,
+    // Whether each exercise is bookmarked
+    bookmarked: Vec<bool>,
+    // The topic of the first pending exercise
+    current_topic: Option<String>,
+    filter: ListFilter,
+    // The indices of the exercises that match the filter
+    visible: Vec<usize>,
+    // The position in `visible`
+    cursor: usize,
+    // The first row that is shown if the list is longer than the terminal
+    offset: usize,
+    // The indices of the selected exercises
+    selected: BTreeSet<usize>,
+    // The position in `visible` where the range that is being selected with `v` started
+    range_start: Option<usize>,
+    message: String,
+    // Whether the source of the exercise under the cursor is shown next to the list
+    preview: bool,
+    hint_gate: HintGate,
+    // The exercise whose hint is shown below the list and the hint. The hint isn't updated
+    // when the cursor moves, since every shown hint is recorded as viewed.
+    hint: Option<(usize, String)>,
+    // The input of the prompt that is opened with `:` to go to an exercise by number or name
+    goto: Option<String>,
+    // The exercises that `r` resets once the reset is confirmed with `y`
+    reset_prompt: Option<Vec<usize>>,
+}
+
+impl<'a> ListUi<'a> {
+    fn new(exercises: &'a [Exercise], hint_gate: HintGate) -> Self {
+        let state = StateFile::load().unwrap_or_default();
+        let mut list = Self::with_filter(exercises, hint_gate, state.list_filter);
+        list.update_statuses();
+        list
+    }
+
+    fn with_filter(exercises: &'a [Exercise], hint_gate: HintGate, filter: ListFilter) -> Self {
+        Self {
+            exercises,
+            statuses: vec!["Pending"; exercises.len()],
+            bookmarked: vec![false; exercises.len()],
+            current_topic: None,
+            filter,
+            visible: Vec::new(),
+            cursor: 0,
+            offset: 0,
+            selected: BTreeSet::new(),
+            range_start: None,
+            message: String::new(),
+            preview: true,
+            hint_gate,
+            hint: None,
+            goto: None,
+            reset_prompt: None,
+        }
+    }
+
+    fn update_statuses(&mut self) {
+        let state = StateFile::load().unwrap_or_default();
+        self.statuses = self
+            .exercises
+            .iter()
+            .map(|exercise| status(exercise, &state))
+            .collect();
+        self.bookmarked = self
+            .exercises
+            .iter()
+            .map(|exercise| bookmarks::is_bookmarked(exercise, &state))
+            .collect();
+        self.apply_filter();
+    }
+
+    // Updates the visible exercises and keeps the cursor on the same exercise if it is still shown.
+    fn apply_filter(&mut self) {
+        self.current_topic = self
+            .exercises
+            .iter()
+            .zip(&self.statuses)
+            .find(|(e, &status)| !e.optional && status == "Pending")
+            .map(|(e, _)| e.topic().to_string());
+        let current = self.current();
+        self.visible = (0..self.exercises.len())
+            .filter(|&ind| self.matches(ind))
+            .collect();
+        self.cursor = current
+            .and_then(|current| self.visible.iter().position(|&ind| ind >= current))
+            .unwrap_or(0);
+        self.range_start = None;
+    }
+
+    fn matches(&self, ind: usize) -> bool {
+        let exercise = &self.exercises[ind];
+        let status_matches = match self.filter.status {
+            None => true,
+            Some(StatusFilter::Pending) => matches!(self.statuses[ind], "Pending" | "Optional"),
+            Some(StatusFilter::Done) => matches!(self.statuses[ind], "Done" | "Broken"),
+            Some(StatusFilter::Current) => self.current_topic.as_deref() == Some(exercise.topic()),
+            Some(StatusFilter::Bookmarked) => self.bookmarked[ind],
+        };
+        status_matches
+            && self
+                .filter
+                .topic
+                .as_ref()
+                .map_or(true, |topic| exercise.topic() == topic)
+            && self
+                .filter
+                .tag
+                .as_ref()
+                .map_or(true, |tag| exercise.tags.contains(tag))
+    }
+
+    fn set_filter(&mut self, filter: ListFilter) {
+        self.filter = filter;
+        self.apply_filter();
+        let filter = self.filter.clone();
+        state_file::update(|state| state.list_filter = filter);
+    }
+
+    fn set_status_filter(&mut self, status: Option<StatusFilter>) {
+        let filter = ListFilter {
+            status,
+            ..self.filter.clone()
+        };
+        self.set_filter(filter);
+    }
+
+    // Cycles through the topics and no topic filter
+    fn next_topic(&mut self) {
+        let mut topics: Vec<String> = Vec::new();
+        for exercise in self.exercises {
+            let topic = exercise.topic();
+            if !topic.is_empty() && !topics.iter().any(|t| t == topic) {
+                topics.push(topic.to_string());
+            }
+        }
+        let topic = next_of(&topics, self.filter.topic.as_ref());
+        self.set_filter(ListFilter {
+            topic,
+            ..self.filter.clone()
+        });
+    }
+
+    // Cycles through the tags of the exercises and no tag filter
+    fn next_tag(&mut self) {
+        let tags: Vec<String> = self
+            .exercises
+            .iter()
+            .flat_map(|e| e.tags.iter().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        if tags.is_empty() {
+            self.message = String::from("The exercises don't have tags");
+            return;
+        }
+        let tag = next_of(&tags, self.filter.tag.as_ref());
+        self.set_filter(ListFilter {
+            tag,
+            ..self.filter.clone()
+        });
+    }
+
+    // The index of the exercise under the cursor
+    fn current(&self) -> Option<usize> {
+        self.visible.get(self.cursor).copied()
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        let last = self.visible.len().saturating_sub(1);
+        self.cursor = self.cursor.saturating_add_signed(delta).min(last);
+    }
+
+    // The indices of the exercises in the range that is being selected
+    fn range(&self) -> &[usize] {
+        match self.range_start {
+            Some(start) if !self.visible.is_empty() => {
+                &self.visible[start.min(self.cursor)..=start.max(self.cursor)]
+            }
+            _ => &[],
+        }
+    }
+
+    fn is_selected(&self, ind: usize) -> bool {
+        self.selected.contains(&ind) || self.range().contains(&ind)
+    }
+
+    // Space selects the exercise under the cursor or the range that was started with `v`.
+    fn toggle(&mut self) {
+        if self.range_start.is_some() {
+            self.selected.extend(self.range().to_vec());
+            self.range_start = None;
+        } else if let Some(current) = self.current() {
+            if !self.selected.remove(&current) {
+                self.selected.insert(current);
+            }
+        }
+    }
+
+    // The selected exercises, or the one under the cursor if none is selected
+    fn targets(&self) -> Vec<usize> {
+        let targets: Vec<usize> = (0..self.exercises.len())
+            .filter(|&ind| self.is_selected(ind))
+            .collect();
+        if targets.is_empty() {
+            self.current().into_iter().collect()
+        } else {
+            targets
+        }
+    }
+
+    fn finish_action(&mut self, message: String) {
+        self.selected.clear();
+        self.range_start = None;
+        self.update_statuses();
+        self.message = message;
+    }
+
+    // Resetting discards the work on the exercises, so it is only done after a confirmation.
+    fn ask_reset(&mut self) {
+        let targets = self.targets();
+        if !targets.is_empty() {
+            self.reset_prompt = Some(targets);
+        }
+    }
+
+    fn reset_targets(&mut self, targets: Vec<usize>) {
+        let failed = targets
+            .iter()
+            .filter(|&&ind| reset(&self.exercises[ind]).is_err())
+            .count();
+        let message = if failed == 0 {
+            format!(
+                "Reset {} exercises. `rustlings undo` brings back the last one.",
+                targets.len()
+            )
+        } else {
+            format!("Failed to reset {failed} of {} exercises", targets.len())
+        };
+        self.finish_action(message);
+    }
+
+    fn mark_targets_pending(&mut self) {
+        let targets: Vec<usize> = self
+            .targets()
+            .into_iter()
+            .filter(|&ind| matches!(self.statuses[ind], "Done" | "Broken"))
+            .collect();
+        let failed = targets
+            .iter()
+            .filter(|&&ind| recheck::mark_pending(&self.exercises[ind]).is_err())
+            .count();
+        let message = if failed == 0 {
+            format!("Marked {} done exercises as pending", targets.len())
+        } else {
+            format!("Failed to mark {failed} of {} exercises", targets.len())
+        };
+        self.finish_action(message);
+    }
+
+    // Bookmarks the exercise under the cursor or removes its bookmark
+    fn toggle_bookmark(&mut self) {
+        let Some(current) = self.current() else {
+            return;
+        };
+        let exercise = &self.exercises[current];
+        let remove = self.bookmarked[current];
+        bookmarks::set_bookmark(exercise, remove);
+        self.message = if remove {
+            format!("Removed the bookmark of {}", exercise.name)
+        } else {
+            format!("Bookmarked {}", exercise.name)
+        };
+        self.bookmarked[current] = !remove;
+        self.apply_filter();
+    }
+
+    // Shows the hint of the exercise under the cursor, or hides it if it is already shown
+    fn toggle_hint(&mut self) {
+        let Some(current) = self.current() else {
+            return;
+        };
+        self.hint = match self.hint {
+            Some((ind, _)) if ind == current => None,
+            _ => {
+                let exercise = &self.exercises[current];
+                let hint = unlocked_hint(&exercise.name, &exercise.hint, self.hint_gate);
+                Some((current, hint))
+            }
+        };
+    }
+
+    fn jump_to_first_selected(&mut self) {
+        if self.selected.is_empty() && self.range_start.is_none() {
+            self.message = String::from("No exercise is selected");
+            return;
+        }
+        match self.visible.iter().position(|&ind| self.is_selected(ind)) {
+            Some(position) => self.cursor = position,
+            None => self.message = String::from("The selected exercises are hidden by the filter"),
+        }
+    }
+
+    // The exercise with the number (starting at 1) or the name
+    fn find(&self, input: &str) -> Option<usize> {
+        let input = input.trim();
+        if let Ok(number) = input.parse::<usize>() {
+            return (1..=self.exercises.len())
+                .contains(&number)
+                .then(|| number - 1);
+        }
+        self.exercises
+            .iter()
+            .position(|e| e.name == input)
+            .or_else(|| {
+                self.exercises
+                    .iter()
+                    .position(|e| e.aliases.iter().any(|alias| alias == input))
+            })
+    }
+
+    // An exercise that is hidden by the filter is shown by removing the filter.
+    fn go_to(&mut self, ind: usize) {
+        if !self.visible.contains(&ind) {
+            self.set_filter(ListFilter::default());
+        }
+        if let Some(position) = self.visible.iter().position(|&i| i == ind) {
+            self.cursor = position;
+        }
+    }
+
+    fn handle_goto(&mut self, key: Key, mut input: String) {
+        match key {
+            Key::Enter => match self.find(&input) {
+                Some(ind) => self.go_to(ind),
+                None => self.message = format!("There is no exercise {}", input.trim()),
+            },
+            Key::Escape | Key::CtrlC => (),
+            Key::Backspace => {
+                input.pop();
+                self.goto = Some(input);
+            }
+            Key::Char(c) => {
+                input.push(c);
+                self.goto = Some(input);
+            }
+            _ => self.goto = Some(input),
+        }
+    }
+
+    // Returns `false` when the list is closed.
+    fn handle(&mut self, key: Key, page: usize) -> bool {
+        self.message.clear();
+        if let Some(input) = self.goto.take() {
+            self.handle_goto(key, input);
+            return true;
+        }
+        if let Some(targets) = self.reset_prompt.take() {
+            match key {
+                Key::Char('y' | 'Y') => self.reset_targets(targets),
+                _ => self.message = String::from("Nothing was reset"),
+            }
+            return true;
+        }
+        match key {
+            Key::ArrowDown | Key::Char('j') => self.move_by(1),
+            Key::ArrowUp | Key::Char('k') => self.move_by(-1),
+            Key::PageDown => self.move_by(page as isize),
+            Key::PageUp => self.move_by(-(page as isize)),
+            Key::Home | Key::Char('g') => self.cursor = 0,
+            Key::End | Key::Char('G') => self.cursor = self.visible.len().saturating_sub(1),
+            Key::Char(' ') => self.toggle(),
+            Key::Char('v') => {
+                self.range_start = match self.range_start {
+                    Some(_) => None,
+                    None => Some(self.cursor),
+                };
+            }
+            Key::Escape => {
+                self.selected.clear();
+                self.range_start = None;
+            }
+            Key::Char('r') => self.ask_reset(),
+            Key::Char('p') => self.mark_targets_pending(),
+            Key::Char('f') => self.jump_to_first_selected(),
+            Key::Char('b') => self.toggle_bookmark(),
+            Key::Char('s') => self.preview = !self.preview,
+            Key::Char('h') => self.toggle_hint(),
+            Key::Char(':') => self.goto = Some(String::new()),
+            Key::Char('1') => self.set_status_filter(None),
+            Key::Char('2') => self.set_status_filter(Some(StatusFilter::Pending)),
+            Key::Char('3') => self.set_status_filter(Some(StatusFilter::Done)),
+            Key::Char('4') => self.set_status_filter(Some(StatusFilter::Current)),
+            Key::Char('5') => self.set_status_filter(Some(StatusFilter::Bookmarked)),
+            Key::Char('t') => self.next_topic(),
+            Key::Char('#') => self.next_tag(),
+            Key::Char('x') => self.set_filter(ListFilter::default()),
+            Key::Char('?') => {
+                footer::toggle_keys();
+            }
+            Key::Char('q') | Key::CtrlC => return false,
+            _ => (),
+        }
+        true
+    }
+
+    // The lines of the screen with the given size
+    fn render(&mut self, height: usize, width: usize) -> Vec<String> {
+        let mut footer = Vec::new();
+        if !self.message.is_empty() {
+            footer.push(style(&self.message).bold().to_string());
+        }
+        if let Some(input) = &self.goto {
+            footer.push(format!(
+                "Go to the exercise with the number or name: {input}"
+            ));
+        } else if let Some(targets) = &self.reset_prompt {
+            footer.push(format!(
+                "Reset {} exercises and discard your changes? [y/N]",
+                targets.len()
+            ));
+        } else if footer::show_keys() {
+            footer.extend(
+                wrap(KEYS, width)
+                    .into_iter()
+                    .map(|line| style(line).dim().to_string()),
+            );
+        }
+        let mut body = height.saturating_sub(footer.len());
+        if let Some(hint) = self.hint_lines(body / 2, width) {
+            footer.splice(0..0, hint);
+            body = height.saturating_sub(footer.len());
+        }
+
+        let mut lines = if self.preview && width >= MIN_PREVIEW_WIDTH {
+            let preview_width = width / 2;
+            let list_width = width - preview_width - 3;
+            let separator = style(ui::glyph("│", "|")).dim();
+            self.list_lines(body, list_width)
+                .into_iter()
+                .zip(self.preview_lines(body, preview_width))
+                .map(|(list, preview)| {
+                    let list = console::pad_str(&list, list_width, Alignment::Left, None);
+                    format!("{list} {separator} {preview}")
+                })
+                .collect()
+        } else {
+            self.list_lines(body, width)
+        };
+        lines.extend(footer);
+        lines
+    }
+
+    // The header and the rows of the list
+    fn list_lines(&mut self, height: usize, width: usize) -> Vec<String> {
+        let rows = height.saturating_sub(1).max(1);
+        if self.cursor < self.offset {
+            self.offset = self.cursor;
+        } else if self.cursor >= self.offset + rows {
+            self.offset = self.cursor + 1 - rows;
+        }
+
+        let mut header = format!("  {:>4} {:<17}   {:<9} {}", "#", "Name", "Status", "Path");
+        if !self.filter.is_empty() {
+            header = format!("{header}  [{}]", self.filter.describe());
+        }
+        let mut lines = vec![style(console::truncate_str(&header, width, ""))
+            .bold()
+            .to_string()];
+        if self.visible.is_empty() {
+            lines.push(String::from(
+                "No exercises match the filter. Press x to show all.",
+            ));
+        }
+        for position in (self.offset..self.visible.len()).take(rows) {
+            let ind = self.visible[position];
+            let exercise = &self.exercises[ind];
+            let cursor = if position == self.cursor { '>' } else { ' ' };
+            let mark = if self.is_selected(ind) { '*' } else { ' ' };
+            let bookmark = if self.bookmarked[ind] {
+                ui::glyph("★", "b")
+            } else {
+                " "
+            };
+            let status = self.statuses[ind];
+            let line = format!(
+                "{cursor}{mark}{:>4} {:<17} {bookmark} {} {}",
+                ind + 1,
+                exercise.name,
+                theme::list_status(status, status == "Done"),
+                exercise.path.display(),
+            );
+            let line = console::truncate_str(&line, width, "");
+            lines.push(if position == self.cursor {
+                style(line).reverse().to_string()
+            } else {
+                line.into_owned()
+            });
+        }
+        lines.resize(height, String::new());
+        lines
+    }
+
+    // The panel with the hint, at most `height` lines
+    fn hint_lines(&self, height: usize, width: usize) -> Option<Vec<String>> {
+        let (ind, hint) = self.hint.as_ref()?;
+        let title = format!("Hint of {} (h to hide)", self.exercises[*ind].name);
+        let separator = ui::glyph("─", "-").repeat(width);
+        let mut lines = vec![
+            style(separator).dim().to_string(),
+            style(console::truncate_str(&title, width, ""))
+                .bold()
+                .to_string(),
+        ];
+        lines.extend(wrap(hint.trim(), width));
+        lines.truncate(height.max(3));
+        Some(lines)
+    }
+
+    // The beginning of the source of the exercise under the cursor
+    fn preview_lines(&self, height: usize, width: usize) -> Vec<String> {
+        let Some(current) = self.current() else {
+            return vec![String::new(); height];
+        };
+        let exercise = &self.exercises[current];
+        let title = console::truncate_str(&exercise.to_string(), width, "").into_owned();
+        let mut lines = vec![style(title).bold().to_string()];
+        match fs::read_to_string(&exercise.path) {
+            Ok(source) => lines.extend(source.lines().take(height).map(|line| {
+                let line = line.replace('\t', "    ");
+                highlight(&console::truncate_str(&line, width, ""))
+            })),
+            Err(e) => {
+                let error = format!("Failed to read the exercise: {e}");
+                lines.push(console::truncate_str(&error, width, "").into_owned());
+            }
+        }
+        lines.resize(height, String::new());
+        lines
+    }
+}
+
+// Breaks the lines of the text at spaces so that they fit into `width` columns
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split(' ') {
+            if !line.is_empty()
+                && console::measure_text_width(&line) + 1 + console::measure_text_width(word)
+                    > width
+            {
+                lines.push(std::mem::take(&mut line));
+            } else if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(console::truncate_str(&line, width, "").into_owned());
+    }
+    lines
+}
+
+// The preview of the source is wider than this many columns, so it is only shown next to
+// the list in wide terminals.
+const MIN_PREVIEW_WIDTH: usize = 100;
+
+const KEYWORDS: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "else", "enum", "false", "fn", "for", "if",
+    "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self",
+    "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "dyn",
+];
+
+#[derive(PartialEq, Eq, Debug)]
+enum Token {
+    Comment,
+    Keyword,
+    Str,
+    Other,
+}
+
+// Splits a line of Rust code into the parts that are highlighted.
+// Comments and strings that span several lines aren't recognized.
+fn tokens(line: &str) -> Vec<(Token, &str)> {
+    let mut tokens: Vec<(Token, &str)> = Vec::new();
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let (token, len) = if rest.starts_with("//") {
+            (Token::Comment, rest.len())
+        } else if c == '"' {
+            let mut escaped = false;
+            let end = rest[1..]
+                .char_indices()
+                .find(|&(_, c)| {
+                    let end = c == '"' && !escaped;
+                    escaped = c == '\\' && !escaped;
+                    end
+                })
+                .map_or(rest.len(), |(ind, _)| ind + 2);
+            (Token::Str, end)
+        } else if c.is_alphabetic() || c == '_' {
+            let end = rest
+                .find(|c: char| !c.is_alphanumeric() && c != '_')
+                .unwrap_or(rest.len());
+            if KEYWORDS.contains(&&rest[..end]) {
+                (Token::Keyword, end)
+            } else {
+                (Token::Other, end)
+            }
+        } else {
+            (Token::Other, c.len_utf8())
+        };
+        let start = line.len() - rest.len();
+        match tokens.last_mut() {
+            Some((Token::Other, text)) if token == Token::Other => {
+                *text = &line[start - text.len()..start + len];
+            }
+            _ => tokens.push((token, &rest[..len])),
+        }
+        rest = &rest[len..];
+    }
+    tokens
+}
+
+// Comments are dimmed, keywords bold and strings cyan.
+fn highlight(line: &str) -> String {
+    tokens(line)
+        .into_iter()
+        .map(|(token, text)| match token {
+            Token::Comment => style(text).dim().to_string(),
+            Token::Keyword => style(text).bold().to_string(),
+            Token::Str => style(text).cyan().to_string(),
+            Token::Other => text.to_string(),
+        })
+        .collect()
+}
+
+pub fn list_ui(exercises: &[Exercise], hint_gate: HintGate) -> Result<()> {
+    let term = Term::stdout();
+    if !term.is_term() || !io::stdin().is_terminal() {
+        bail!("The interactive list needs a terminal");
+    }
+    if exercises.is_empty() {
+        bail!("There are no exercises to list");
+    }
+
+    let _screen = ui::AlternateScreen::enter();
+    let mut list = ListUi::new(exercises, hint_gate);
+    loop {
+        let (height, width) = term.size();
+        let (height, width) = (usize::from(height), usize::from(width));
+        let lines = list.render(height, width);
+        term.move_cursor_to(0, 0)?;
+        term.clear_screen()?;
+        term.write_str(&lines.join("\r\n"))?;
+        let key = term.read_key()?;
+        if !list.handle(key, height.saturating_sub(3).max(1)) {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+
+    fn exercises(num: usize) -> Vec<Exercise> {
+        (0..num)
+            .map(|ind| {
+                Exercise::for_test(
+                    &format!("ex{ind}"),
+                    &format!("exercises/ex{ind}.rs"),
+                    Mode::Compile,
+                )
+            })
+            .collect()
+    }
+
+    fn list(exercises: &[Exercise]) -> ListUi<'_> {
+        let mut list = ListUi::with_filter(exercises, HintGate::default(), ListFilter::default());
+        list.preview = false;
+        list.apply_filter();
+        list
+    }
+
+    #[test]
+    fn test_select() {
+        let exercises = exercises(6);
+        let mut list = list(&exercises);
+        assert_eq!(list.targets(), [0]);
+
+        list.handle(Key::Char('j'), 1);
+        list.handle(Key::Char(' '), 1);
+        list.handle(Key::Char('j'), 1);
+        list.handle(Key::Char('j'), 1);
+        list.handle(Key::Char('v'), 1);
+        list.handle(Key::End, 1);
+        assert_eq!(list.targets(), [1, 3, 4, 5]);
+        list.handle(Key::Char(' '), 1);
+        assert_eq!(list.range_start, None);
+        assert_eq!(list.targets(), [1, 3, 4, 5]);
+
+        list.handle(Key::Char('f'), 1);
+        assert_eq!(list.cursor, 1);
+        list.handle(Key::Char(' '), 1);
+        assert_eq!(list.targets(), [3, 4, 5]);
+
+        list.handle(Key::Escape, 1);
+        assert_eq!(list.targets(), [1]);
+    }
+
+    #[test]
+    fn test_reset_needs_confirmation() {
+        let exercises = exercises(3);
+        let mut list = list(&exercises);
+        list.handle(Key::Char(' '), 1);
+        list.handle(Key::Char('j'), 1);
+        list.handle(Key::Char(' '), 1);
+        list.handle(Key::Char('r'), 1);
+        assert_eq!(list.reset_prompt.as_deref(), Some(&[0, 1][..]));
+        assert!(list.render(10, 80).join("\n").contains("Reset 2 exercises"));
+
+        list.handle(Key::Char('n'), 1);
+        assert_eq!(list.reset_prompt, None);
+        assert_eq!(list.message, "Nothing was reset");
+        assert_eq!(list.targets(), [0, 1]);
+    }
+
+    #[test]
+    fn test_goto() {
+        let exercises = exercises(20);
+        let mut list = list(&exercises);
+        for key in [Key::Char(':'), Key::Char('1'), Key::Char('2'), Key::Enter] {
+            list.handle(key, 1);
+        }
+        assert_eq!(list.cursor, 11);
+
+        for c in ":ex3".chars() {
+            list.handle(Key::Char(c), 1);
+        }
+        list.handle(Key::Enter, 1);
+        assert_eq!(list.cursor, 3);
+
+        for key in [Key::Char(':'), Key::Char('2'), Key::Char('1'), Key::Enter] {
+            list.handle(key, 1);
+        }
+        assert_eq!(list.cursor, 3);
+        assert_eq!(list.message, "There is no exercise 21");
+
+        for key in [Key::Char(':'), Key::Char('9'), Key::Escape, Key::Char('j')] {
+            list.handle(key, 1);
+        }
+        assert_eq!(list.cursor, 4);
+    }
+
+    #[test]
+    fn test_filter() {
+        let mut exercises = exercises(6);
+        for (ind, exercise) in exercises.iter_mut().enumerate() {
+            let topic = if ind < 3 { "vecs" } else { "traits" };
+            exercise.path = format!("exercises/{topic}/ex{ind}.rs").into();
+        }
+        exercises[4].tags = vec![String::from("generics")];
+        let mut list = list(&exercises);
+        list.statuses = vec!["Done", "Done", "Pending", "Pending", "Pending", "Done"];
+        list.filter = ListFilter {
+            status: Some(StatusFilter::Pending),
+            ..Default::default()
+        };
+        list.apply_filter();
+        assert_eq!(list.visible, [2, 3, 4]);
+
+        list.filter.status = Some(StatusFilter::Current);
+        list.apply_filter();
+        assert_eq!(list.visible, [0, 1, 2]);
+
+        list.filter = ListFilter {
+            status: Some(StatusFilter::Done),
+            topic: Some(String::from("traits")),
+            tag: None,
+        };
+        list.apply_filter();
+        assert_eq!(list.visible, [5]);
+        assert!(list.render(5, 80)[0].ends_with("[done | topic traits]"));
+
+        list.filter = ListFilter {
+            tag: Some(String::from("generics")),
+            ..Default::default()
+        };
+        list.apply_filter();
+        assert_eq!(list.visible, [4]);
+        assert_eq!(list.targets(), [4]);
+
+        list.bookmarked[1] = true;
+        list.filter = ListFilter {
+            status: Some(StatusFilter::Bookmarked),
+            ..Default::default()
+        };
+        list.apply_filter();
+        assert_eq!(list.visible, [1]);
+        let lines = list.render(10, 80);
+        assert!(lines[0].ends_with("[bookmarked]"));
+        assert!(lines[1].contains(ui::glyph("★", "b")));
+    }
+
+    #[test]
+    fn test_next_of() {
+        let topics = [String::from("vecs"), String::from("traits")];
+        assert_eq!(next_of(&topics, None).as_deref(), Some("vecs"));
+        assert_eq!(
+            next_of(&topics, Some(&topics[0])).as_deref(),
+            Some("traits")
+        );
+        assert_eq!(next_of(&topics, Some(&topics[1])), None);
+    }
+
+    #[test]
+    fn test_render_scrolls_to_the_cursor() {
+        let exercises = exercises(10);
+        let mut list = list(&exercises);
+        list.handle(Key::End, 1);
+        let lines = list.render(5, 300);
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].contains("ex7"));
+        assert!(lines[3].contains("ex9"));
+    }
+
+    #[test]
+    fn test_render_with_preview() {
+        let exercises = exercises(2);
+        let mut list = list(&exercises);
+        list.preview = true;
+        let lines = list.render(4, 120);
+        assert!(lines[0].contains("Name"));
+        assert!(lines[0].ends_with("exercises/ex0.rs"));
+        assert!(lines[1].contains("Failed to read the exercise"));
+        assert!(lines
+            .iter()
+            .all(|line| console::measure_text_width(line) <= 120));
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(
+            wrap("Look at the types\nof the\n\nfunction", 8),
+            ["Look at", "the", "types", "of the", "", "function"]
+        );
+        assert_eq!(wrap("a_very_long_word", 6), ["a_very"]);
+    }
+
+    #[test]
+    fn test_render_with_hint() {
+        let exercises = exercises(10);
+        let mut list = list(&exercises);
+        list.hint = Some((0, String::from("Look at the types")));
+        let lines = list.render(10, 300);
+        assert_eq!(lines.len(), 10);
+        assert!(lines[6].starts_with("---") || lines[6].starts_with("───"));
+        assert!(lines[7].contains("Hint of ex0"));
+        assert_eq!(lines[8], "Look at the types");
+    }
+
+    #[test]
+    fn test_tokens() {
+        assert_eq!(
+            tokens(r#"    let s = "a \"b\""; // TODO"#),
+            [
+                (Token::Other, "    "),
+                (Token::Keyword, "let"),
+                (Token::Other, " s = "),
+                (Token::Str, r#""a \"b\"""#),
+                (Token::Other, "; "),
+                (Token::Comment, "// TODO"),
+            ]
+        );
+        assert_eq!(
+            tokens("fn letter("),
+            [(Token::Keyword, "fn"), (Token::Other, " letter(")]
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use anyhow::{bail, Context, Result};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::trace;
+
+use crate::state_file;
+
+// Watch mode and `verify` record the progress while they run. If two of them run in the
+// same Rustlings directory, e.g. in two terminals or as an editor task, they would overwrite
+// each other's progress. The lock file next to the state file contains the ID of the process
+// that holds the lock. Locks of processes that don't run anymore are taken over.
+pub struct Lock;
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        release();
+    }
+}
+
+// Also called when Rustlings is stopped by a signal, since the `Lock` isn't dropped then.
+pub fn release() {
+    let path = path();
+    // Don't remove the lock of a process that took it over with `--force`.
+    if read_pid(&path) == Some(process::id()) {
+        let _ = fs::remove_file(&path);
+    }
+}
+
+// `.rustlings-state.lock` next to `.rustlings-state.json`
+pub fn path() -> PathBuf {
+    state_file::path().with_extension("lock")
+}
+
+fn read_pid(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
+
+fn is_running(pid: u32) -> bool {
+    if cfg!(target_os = "linux") {
+        Path::new(&format!("/proc/{pid}")).exists()
+    } else if cfg!(unix) {
+        Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    } else {
+        Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {pid}"), "/NH"])
+            .output()
+            .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+    }
+}
+
+// Take the lock until the returned `Lock` is dropped.
+// With `force`, the lock is taken even if another Rustlings process holds it.
+pub fn acquire(force: bool) -> Result<Lock> {
+    let path = path();
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&path);
+    let mut file = match file {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            let pid = read_pid(&path);
+            trace!("{} is held by the process {pid:?}", path.display());
+            if let Some(pid) = pid {
+                if !force && pid != process::id() && is_running(pid) {
+                    bail!(
+                        "Another rustlings (process {pid}) is running in this directory.\n\
+                         Close it first, or pass `--force` if you are sure that it doesn't run anymore."
+                    );
+                }
+            }
+            fs::File::create(&path)
+                .with_context(|| format!("Failed to take over the lock {}", path.display()))?
+        }
+        Err(e) => return Err(e).with_context(|| format!("Failed to create {}", path.display())),
+    };
+    file.write_all(process::id().to_string().as_bytes())
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    trace!("Took the lock {}", path.display());
+    Ok(Lock)
+}
+
+// Other commands, e.g. `rustlings hint` in a second terminal, can still record progress while
+// watch mode runs. Each update of the state file loads, changes and saves it while holding
+// this short lock, so that two processes don't overwrite each other's update.
+pub struct UpdateLock(PathBuf);
+
+impl Drop for UpdateLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+// `.rustlings-state.update.lock` next to `.rustlings-state.json`
+fn update_path() -> PathBuf {
+    state_file::path().with_extension("update.lock")
+}
+
+// Wait until no other process updates the state file. Updates are only held up for a moment,
+// so a lock that is held for longer than the timeout is left behind and taken over.
+pub fn acquire_update() -> Option<UpdateLock> {
+    const TIMEOUT: Duration = Duration::from_secs(2);
+
+    let path = update_path();
+    let start = Instant::now();
+    loop {
+        match fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&path)
+        {
+            Ok(mut file) => {
+                let _ = file.write_all(process::id().to_string().as_bytes());
+                return Some(UpdateLock(path));
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                // Only one thread of this process updates the state at a time.
+                let stale =
+                    read_pid(&path).is_some_and(|pid| pid == process::id() || !is_running(pid));
+                if stale || start.elapsed() > TIMEOUT {
+                    trace!("Taking over the lock {}", path.display());
+                    let _ = fs::remove_file(&path);
+                } else {
+                    thread::sleep(Duration::from_millis(10));
+                }
+            }
+            // E.g. a read-only directory, in which saving the state fails anyway
+            Err(e) => {
+                trace!("Failed to create {}: {e}", path.display());
+                return None;
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+use tracing::level_filters::LevelFilter;
+use tracing::{trace, Subscriber};
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::fmt::format::{DefaultFields, Format, Full};
+use tracing_subscriber::fmt::{self, time::Uptime};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::registry::LookupSpan;
+
+// Lines like `0.001234567s rustlings::state_file: Saved .rustlings-state.json`
+// with the seconds since the start
+fn layer<S>() -> fmt::Layer<S, DefaultFields, Format<Full, Uptime>>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    fmt::layer()
+        .with_ansi(false)
+        .with_level(false)
+        .with_timer(Uptime::default())
+}
+
+// Diagnostic messages for problems that only some users have, like missed file events
+// or failing state writes. They are logged with `tracing::trace!` and written to the file of
+// `--log-file` and, with `--verbose`, to stderr. Nothing is logged otherwise.
+pub fn init(verbose: bool, log_file: Option<&Path>) -> Result<()> {
+    let file = match log_file {
+        Some(path) => Some(
+            fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .with_context(|| format!("Failed to open the log file {}", path.display()))?,
+        ),
+        None => None,
+    };
+    if !verbose && file.is_none() {
+        return Ok(());
+    }
+
+    tracing_subscriber::registry()
+        .with(verbose.then(|| layer().with_writer(io::stderr)))
+        .with(file.map(|file| layer().with_writer(Mutex::new(file))))
+        // The messages of the dependencies aren't about Rustlings
+        .with(Targets::new().with_target(env!("CARGO_CRATE_NAME"), LevelFilter::TRACE))
+        .init();
+    trace!(
+        "rustlings {} started with {:?}",
+        env!("CARGO_PKG_VERSION"),
+        std::env::args().collect::<Vec<_>>()
+    );
+    Ok(())
+}
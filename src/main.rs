@@ -1,39 +1,182 @@
-use crate::exercise::{Exercise, ExerciseList};
-use crate::project::write_project_json;
-use crate::run::{reset, run};
-use crate::verify::verify;
-use anyhow::Result;
+use crate::achievements::unlock_new;
+use crate::bookmarks::{bookmark, bookmarks};
+use crate::challenge::challenge;
+use crate::compare::compare;
+use crate::config::{Config, TestRunner};
+use crate::exam::{check_report, grade, load_exam, DISABLED_HINT};
+use crate::exercise::{
+    check_topic, last_timings, set_build_options, set_not_done_marker, supported_limits,
+    BuildOptions, Exercise, ExerciseList, Mode, FULL_OUTPUT_PATH,
+};
+use crate::explain::{errors, explain};
+use crate::export::{export, ExportFormat};
+use crate::footer::Footer;
+use crate::glossary::{glossary, glossary_for_topic, GlossaryEntry};
+use crate::graph::{graph, GraphFormat};
+use crate::hint::{show_hint, unlock_hint, HintGate};
+use crate::history::history;
+use crate::list_ui::list_ui;
+use crate::project::{write_devcontainer, write_project_json, write_vscode_config};
+use crate::quiz::Quiz;
+use crate::recheck::recheck;
+use crate::recommend::print_recommendation;
+use crate::report::report;
+use crate::review::review;
+use crate::run::{preview_reset, reset, run, run_all, show_changes};
+use crate::sandbox::Sandbox;
+use crate::share::share;
+use crate::state_file::StateFile;
+use crate::stats::{stats, ActivityTracker};
+use crate::toolchain::{check_toolchain, Version};
+use crate::undo::undo;
+use crate::uninstall::{clean, uninstall};
+use crate::verify::{verify, verify_quietly};
+use crate::watcher::{glob_match, is_ignored, FileWatcher};
+use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
-use console::Emoji;
 use notify_debouncer_mini::notify::{self, RecursiveMode};
-use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
+use notify_debouncer_mini::DebouncedEventKind;
 use shlex::Shlex;
+use std::collections::BTreeMap;
+use std::env;
 use std::ffi::OsStr;
 use std::fs;
-use std::io::{self, prelude::*};
-use std::path::Path;
+use std::io::{self, prelude::*, IsTerminal};
+use std::path::{Component, Path, PathBuf};
 use std::process::Command;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::{channel, RecvTimeoutError};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::Duration;
+use tracing::trace;
 
 #[macro_use]
 mod ui;
 
+mod achievements;
+mod backup;
+mod bookmarks;
+mod challenge;
+mod compare;
+mod config;
+mod dev;
+mod diff;
+mod eval;
+mod exam;
 mod exercise;
+mod exit_code;
+mod explain;
+mod export;
+mod footer;
+mod fuzzy;
+mod glossary;
+mod graph;
+mod hint;
+mod history;
+mod json;
+mod list_ui;
+mod lock;
+mod logging;
+mod mutants;
+mod playlist;
+mod porcelain;
+mod predict;
+mod pristine;
 mod project;
+mod quiz;
+mod recheck;
+mod recommend;
+mod recovery;
+mod regions;
+mod report;
+mod review;
+mod rules;
 mod run;
+mod sandbox;
+mod share;
+mod shuffle;
+mod shutdown;
+mod state_file;
+mod stats;
+mod tamper;
+mod template;
+mod theme;
+mod tips;
+mod toolchain;
+mod undo;
+mod uninstall;
+mod variants;
 mod verify;
+mod watcher;
 
 /// Rustlings is a collection of small exercises to get you used to writing and reading Rust code
 #[derive(Parser)]
 #[command(version)]
 struct Args {
     /// Show outputs from the test exercises
-    #[arg(long)]
+    #[arg(long, overrides_with = "capture")]
     nocapture: bool,
+    /// Hide the outputs of the test exercises, even if the config files set `nocapture`
+    #[arg(long, overrides_with = "nocapture", hide_short_help = true)]
+    capture: bool,
+    /// Number of parallel jobs for the exercises that are built with Cargo
+    #[arg(long, short)]
+    jobs: Option<u32>,
+    /// Run Cargo without accessing the network
+    #[arg(long, overrides_with = "no_offline")]
+    offline: bool,
+    /// Let Cargo access the network, even if the config files set `offline`
+    #[arg(long, overrides_with = "offline", hide_short_help = true)]
+    no_offline: bool,
+    /// Require Cargo.lock to be up to date
+    #[arg(long, overrides_with = "no_locked")]
+    locked: bool,
+    /// Don't require Cargo.lock to be up to date, even if the config files set `locked`
+    #[arg(long, overrides_with = "locked", hide_short_help = true)]
+    no_locked: bool,
+    /// Extra flags for every compilation of an exercise, e.g. `--rustflags="-C opt-level=0"`
+    #[arg(long, value_name = "FLAGS", allow_hyphen_values = true)]
+    rustflags: Option<String>,
+    /// Fail the compilation of exercises that have warnings, unless the exercise allows them
+    #[arg(long, overrides_with = "no_deny_warnings")]
+    deny_warnings: bool,
+    /// Allow warnings, even if the config files set `deny_warnings`
+    #[arg(long, overrides_with = "deny_warnings", hide_short_help = true)]
+    no_deny_warnings: bool,
+    /// Check the exercises for errors before building them, which gives faster feedback on slow machines
+    #[arg(long, overrides_with = "no_fast_check")]
+    fast_check: bool,
+    /// Don't check the exercises before building them, even if the config files set `fast_check`
+    #[arg(long, overrides_with = "fast_check", hide_short_help = true)]
+    no_fast_check: bool,
+    /// Run the exercises without network access and with a read-only file system where the OS supports it
+    #[arg(long, overrides_with = "no_sandbox")]
+    sandbox: bool,
+    /// Run the exercises without the sandbox, even if the config files set `sandbox`
+    #[arg(long, overrides_with = "sandbox", hide_short_help = true)]
+    no_sandbox: bool,
+    /// Run even if another rustlings seems to be running in this directory
+    #[arg(long)]
+    force: bool,
+    /// Print diagnostic messages about file events, compilations and the progress file to stderr
+    #[arg(long)]
+    verbose: bool,
+    /// Append the diagnostic messages of `--verbose` to this file, e.g. to attach it to a bug report
+    #[arg(long, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+    /// Only print ASCII characters, for terminals that can't show symbols like ✓
+    #[arg(long, overrides_with = "no_ascii")]
+    ascii: bool,
+    /// Print symbols like ✓, even if the config files set `ascii`
+    #[arg(long, overrides_with = "ascii", hide_short_help = true)]
+    no_ascii: bool,
+    /// Print stable, tab-separated lines for scripts with `run`, `verify` and `list`
+    #[arg(long)]
+    porcelain: bool,
+    /// Print JSON with `run`, `verify`, `list`, `stats` and `hint`
+    #[arg(long, conflicts_with = "porcelain")]
+    json: bool,
     #[command(subcommand)]
     command: Option<Subcommands>,
 }
@@ -41,20 +184,82 @@ struct Args {
 #[derive(Subcommand)]
 enum Subcommands {
     /// Verify all exercises according to the recommended order
-    Verify,
+    Verify {
+        /// Only verify the exercises of this playlist
+        #[arg(long)]
+        playlist: Option<String>,
+        /// Start at this exercise instead of the first one
+        #[arg(long, value_name = "NAME")]
+        from: Option<String>,
+        /// Only verify the exercises of this topic, e.g. `vecs`
+        #[arg(long, value_name = "TOPIC")]
+        only: Option<String>,
+    },
     /// Rerun `verify` when files were edited
     Watch {
-        /// Show hints on success
+        /// Only watch the exercises of this playlist
         #[arg(long)]
+        playlist: Option<String>,
+        /// Show hints on success
+        #[arg(long, overrides_with = "no_success_hints")]
         success_hints: bool,
+        /// Don't show hints on success, even if the config files set `success_hints`
+        #[arg(long, overrides_with = "success_hints", hide_short_help = true)]
+        no_success_hints: bool,
+        /// Don't clear the screen between runs to avoid flickering on slow connections
+        #[arg(long, overrides_with = "no_minimal_redraw")]
+        minimal_redraw: bool,
+        /// Clear the screen between runs, even if the config files set `minimal_redraw`
+        #[arg(long, overrides_with = "minimal_redraw", hide_short_help = true)]
+        no_minimal_redraw: bool,
+        /// Check the done exercises that you modify in the background
+        #[arg(long, overrides_with = "no_background_check")]
+        background_check: bool,
+        /// Don't check the done exercises in the background, even if the config files set `background_check`
+        #[arg(long, overrides_with = "background_check", hide_short_help = true)]
+        no_background_check: bool,
+        /// Wait this many milliseconds for more file changes before verifying (default: 1000)
+        #[arg(long, value_name = "MS")]
+        debounce: Option<u64>,
+        /// Run Clippy on exercises that pass and offer its suggestions as style tips
+        #[arg(long, overrides_with = "no_style_tips")]
+        style_tips: bool,
+        /// Don't offer style tips, even if the config files set `style_tips`
+        #[arg(long, overrides_with = "style_tips", hide_short_help = true)]
+        no_style_tips: bool,
+        /// Check the files for changes every MS milliseconds (default: 1000) instead of relying on
+        /// file system events, which are missed on network file systems and in some containers
+        #[arg(long, value_name = "MS", num_args = 0..=1, default_missing_value = "1000")]
+        poll: Option<u64>,
+        /// Show the progress and the current exercise in the title of the terminal
+        #[arg(long, overrides_with = "no_title")]
+        title: bool,
+        /// Don't show the progress in the title, even if the config files set `title`
+        #[arg(long, overrides_with = "title", hide_short_help = true)]
+        no_title: bool,
+        /// Verify the pending exercises in a shuffled order. Pass a seed like `--shuffle=42` to get the same order again
+        #[arg(long, value_name = "SEED", num_args = 0..=1, require_equals = true, default_missing_value = "random")]
+        shuffle: Option<String>,
     },
-    /// Run/Test a single exercise
+    /// Run/Test exercises. Several exercises end with a summary of their results
     Run {
+        /// The names of the exercises or glob patterns like `vecs*`
+        #[arg(required = true)]
+        names: Vec<String>,
+        /// Run N exercises at the same time when running several
+        #[arg(short = 'j', long = "jobs", value_name = "N", value_parser = clap::value_parser!(u16).range(1..))]
+        jobs: Option<u16>,
+    },
+    /// Reset a single exercise to its pristine copy after showing the changes that are discarded
+    Reset {
         /// The name of the exercise
         name: String,
+        /// Only show the changes that would be discarded
+        #[arg(long)]
+        dry_run: bool,
     },
-    /// Reset a single exercise using "git stash -- <filename>"
-    Reset {
+    /// Show your changes of an exercise compared to its pristine copy
+    Diff {
         /// The name of the exercise
         name: String,
     },
@@ -63,6 +268,50 @@ enum Subcommands {
         /// The name of the exercise
         name: String,
     },
+    /// Compare your solution of an exercise with the official solution
+    Compare {
+        /// The name of the exercise
+        name: String,
+    },
+    /// Explain an error code, or the errors of the last failed compilation
+    Explain {
+        /// The error code, e.g. `E0382`
+        code: Option<String>,
+    },
+    /// Show which error codes you encountered most often and in which exercises
+    Errors,
+    /// Look up a term in the glossary of the course
+    Glossary {
+        /// The term to search for. Shows the whole glossary if it is missing
+        term: Option<String>,
+    },
+    /// Print the structure of the course as a graph with the done and pending exercises
+    Graph {
+        /// The format of the graph
+        #[arg(short, long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+    },
+    /// Export the course as a workbook with the description and the starter code of each exercise
+    Export {
+        /// The format of the workbook
+        #[arg(short, long, value_enum, default_value_t = ExportFormat::Markdown)]
+        format: ExportFormat,
+        /// Include the official solutions from `solutions/`
+        #[arg(long)]
+        solutions: bool,
+        /// Write the workbook to this file instead of printing it
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Show the recorded runs of the exercises
+    History {
+        /// Only show the runs of this exercise
+        #[arg(short, long)]
+        exercise: Option<String>,
+        /// The number of runs to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
     /// List the exercises available in Rustlings
     List {
         /// Show only the paths of the exercises
@@ -81,13 +330,222 @@ enum Subcommands {
         /// Display only exercises that have been solved
         #[arg(short, long)]
         solved: bool,
+        /// Display only bookmarked exercises
+        #[arg(short, long)]
+        bookmarked: bool,
+        /// Browse the exercises and reset or mark several of them as pending at once
+        #[arg(short, long)]
+        interactive: bool,
     },
+    /// Manage playlists, i.e. named subsets of the exercises
+    Playlist {
+        #[command(subcommand)]
+        command: PlaylistCommand,
+    },
+    /// Bookmark an exercise to revisit it later
+    Bookmark {
+        /// The name of the exercise
+        name: String,
+        /// Remove the bookmark instead
+        #[arg(short, long)]
+        remove: bool,
+    },
+    /// List the bookmarked exercises
+    Bookmarks,
     /// Enable rust-analyzer for exercises
-    Lsp,
+    Lsp {
+        /// Also generate VS Code settings and tasks for the exercises
+        #[arg(long)]
+        vscode: bool,
+        /// Also generate a dev container definition
+        #[arg(long)]
+        devcontainer: bool,
+    },
+    /// Solve the pending exercises of a topic against the clock
+    Challenge {
+        /// The topic of the exercises, e.g. `vecs`
+        #[arg(short, long)]
+        topic: String,
+        /// The time limit in minutes
+        #[arg(short, long, default_value_t = 30)]
+        minutes: u64,
+        /// Reset the completed exercises of the topic to solve them again
+        #[arg(long)]
+        reset: bool,
+    },
+    /// Grade the exercises listed in exam.toml and write a signed report
+    ///
+    /// Only run this on the machine of the instructor. Anyone who has the key can sign any
+    /// report, so the key must never be on the machines of the students, and the students
+    /// must not grade their own exercises.
+    Exam {
+        /// The key to sign the report with, known only to the instructor. Defaults to the RUSTLINGS_EXAM_KEY environment variable
+        #[arg(long)]
+        key: Option<String>,
+        /// Check the signature of the given report instead of grading
+        #[arg(long)]
+        check: Option<String>,
+    },
+    /// Answer the quizzes of the exercises that you completed
+    Quiz,
+    /// Practice with quick expression exercises that are evaluated as you type them
+    Eval {
+        /// The name of the expression exercise, even if it was solved already. Defaults to all unsolved ones
+        name: Option<String>,
+    },
+    /// Predict what programs print before they are run
+    Predict {
+        /// The name of the prediction exercise, even if it was predicted already. Defaults to all that weren't predicted correctly
+        name: Option<String>,
+    },
+    /// Verify done exercises again to find the ones that don't pass anymore
+    Recheck {
+        /// Check all done exercises instead of the ones that weren't checked for the longest time
+        #[arg(long)]
+        all: bool,
+        /// Add the "I AM NOT DONE" comment to the broken exercises again
+        #[arg(long)]
+        mark_pending: bool,
+    },
+    /// Suggest the next exercise or a topic to practice again
+    Recommend,
+    /// Reset a completed exercise that is due for review
+    Review {
+        /// Only list the exercises that are due for review
+        #[arg(short, long)]
+        list: bool,
+    },
+    /// Show the unlocked achievements and the ones left to unlock
+    Achievements,
+    /// Show statistics about your progress, like the time spent on the exercises
+    Stats,
+    /// Write a report of your progress per topic and exercise, e.g. to submit it with your homework
+    Report {
+        /// The format of the report
+        #[arg(short, long, value_enum, default_value_t = ExportFormat::Markdown)]
+        format: ExportFormat,
+        /// The file to write the report to (default: `rustlings-report.md` or `rustlings-report.html`)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Write your solution of an exercise, its task and its output to a Markdown file to ask for help or for a review
+    Share {
+        /// The name of the exercise
+        name: String,
+        /// The file to write to (default: `rustlings-share-<exercise>.md`)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Restore the read-only regions of an exercise that were changed, but keep your other changes
+    Revert {
+        /// The name of the exercise
+        name: String,
+    },
+    /// Undo the last reset of an exercise
+    Undo,
+    /// Restore the latest backup of an exercise from before it was reset
+    Restore {
+        /// The name of the exercise
+        name: String,
+    },
+    /// Remove files generated by Rustlings
+    Uninstall {
+        /// Also remove the exercises and info.toml
+        #[arg(long)]
+        all: bool,
+        /// Move solved exercises to `rustlings-solutions/` before removing the exercises
+        #[arg(long, requires = "all")]
+        keep_solutions: bool,
+        /// Don't ask for confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+    /// Remove the build artifacts and caches of the exercises and report the reclaimed disk space
+    Clean,
+    /// Commands for authors of a course
+    Dev {
+        #[command(subcommand)]
+        command: DevCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum DevCommand {
+    /// Verify the exercises when files change, reloading info.toml and picking up new exercises
+    Watch {
+        /// Wait this many milliseconds for more file changes before verifying (default: 1000)
+        #[arg(long, value_name = "MS")]
+        debounce: Option<u64>,
+    },
+    /// Check that the official solution of each exercise from `solutions/` passes
+    RunAllSolutions,
+    /// Check that no exercise passes before it's edited and that no template variables are left
+    Check,
+    /// Create an exercise from a template and add it to info.toml
+    New {
+        /// The path of the new exercise, e.g. `exercises/05_vecs/vecs3.rs`
+        path: PathBuf,
+        /// How the exercise is checked
+        #[arg(long, value_enum, default_value = "compile")]
+        mode: Mode,
+        /// A template file that can contain the variables {{course_name}}, {{exercise_name}}, {{topic}}
+        /// and numbered {{hint}} markers
+        #[arg(long)]
+        template: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PlaylistCommand {
+    /// Add exercises to a playlist. The playlist is created if it doesn't exist
+    Add {
+        /// The name of the playlist
+        playlist: String,
+        /// The names of the exercises
+        #[arg(required = true)]
+        exercises: Vec<String>,
+    },
+    /// Remove exercises from a playlist, or the whole playlist if no exercises are given
+    Remove {
+        /// The name of the playlist
+        playlist: String,
+        /// The names of the exercises
+        exercises: Vec<String>,
+    },
+    /// List the playlists, or the exercises of a playlist
+    List {
+        /// The name of the playlist
+        playlist: Option<String>,
+    },
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
+    let mut args = Args::parse();
+    if let Err(e) = logging::init(args.verbose, args.log_file.as_deref()) {
+        println!("{e:#}");
+        std::process::exit(1);
+    }
+    shutdown::install();
+    enter_course_dir(&mut args);
+    let config = Config::load().unwrap_or_else(|e| {
+        println!("{e:#}");
+        std::process::exit(exit_code::COURSE_ERROR);
+    });
+    config.apply_color();
+    theme::set(config.theme.unwrap_or_default());
+    if flag(args.ascii, args.no_ascii, config.ascii) {
+        ui::use_ascii();
+    }
+    if args.porcelain {
+        porcelain::enable();
+    }
+    if args.json {
+        json::enable();
+    }
+
+    // Checking for color support enables escape codes in Windows consoles that support them.
+    // Legacy consoles fall back to plain output.
+    console::colors_enabled();
 
     if args.command.is_none() {
         println!("\n{WELCOME}\n");
@@ -100,19 +558,125 @@ fn main() -> Result<()> {
         std::process::exit(1);
     }
 
-    let info_file = fs::read_to_string("info.toml").unwrap_or_else(|e| {
-        match e.kind() {
-            io::ErrorKind::NotFound => println!(
-                "The program must be run from the rustlings directory\nTry `cd rustlings/`!",
-            ),
-            _ => println!("Failed to read the info.toml file: {e}"),
+    let info_file = fs::read_to_string("info.toml").unwrap_or_else(|e| match e.kind() {
+        io::ErrorKind::NotFound => recovery::missing_info_toml(),
+        _ => {
+            println!("Failed to read the info.toml file: {e}");
+            std::process::exit(exit_code::COURSE_ERROR);
         }
-        std::process::exit(1);
     });
-    let exercises = toml_edit::de::from_str::<ExerciseList>(&info_file)
-        .unwrap()
-        .exercises;
-    let verbose = args.nocapture;
+    let exercise_list = toml_edit::de::from_str::<ExerciseList>(&info_file)
+        .or_else(|e| {
+            let info_file = recovery::broken_info_toml(&e.to_string());
+            toml_edit::de::from_str::<ExerciseList>(&info_file)
+        })
+        .unwrap_or_else(|e| {
+            println!("Failed to parse the info.toml file: {e}");
+            std::process::exit(exit_code::COURSE_ERROR);
+        });
+    if config.per_user_state == Some(true) {
+        if let Err(e) = state_file::use_per_user_state() {
+            println!("{e:#}");
+            std::process::exit(1);
+        }
+    }
+    if let Some(minimum) = config
+        .minimum_rust_version
+        .as_ref()
+        .or(exercise_list.minimum_rust_version.as_ref())
+    {
+        let Some(minimum) = Version::parse(minimum) else {
+            println!("Invalid `minimum_rust_version`: {minimum}");
+            std::process::exit(exit_code::COURSE_ERROR);
+        };
+        if let Err(e) = check_toolchain(minimum) {
+            println!("{e}");
+            std::process::exit(1);
+        }
+    }
+    let mut exercises = exercise_list.exercises;
+    // `rustlings dev` reports the missing files of the course it develops itself.
+    if !matches!(args.command, Some(Subcommands::Dev { .. })) {
+        recovery::restore_missing_exercises(&exercises);
+        variants::instantiate_all(&exercises);
+        state_file::rename_aliases(&exercises);
+    }
+    let exam = load_exam().unwrap_or_else(|e| {
+        println!("{e:#}");
+        std::process::exit(exit_code::COURSE_ERROR);
+    });
+    if exam.is_some() {
+        state_file::update(|state| {
+            state.exam_started_at.get_or_insert_with(state_file::now);
+        });
+    }
+    if exam.is_some()
+        || StateFile::load()
+            .unwrap_or_default()
+            .exam_started_at
+            .is_some()
+    {
+        for exercise in &mut exercises {
+            exercise.hint = String::from(DISABLED_HINT);
+        }
+    }
+    let quizzes = exercise_list.quizzes;
+    let expressions = exercise_list.expressions;
+    let predictions = exercise_list.predictions;
+    let explanations = exercise_list.explanations;
+    let glossary_entries = exercise_list.glossary;
+    let course_name = exercise_list.course_name;
+    let verbose = flag(args.nocapture, args.capture, config.nocapture);
+    set_not_done_marker(config.not_done_marker != Some(false));
+    let hint_gate = HintGate {
+        min_failures: config.hints.min_failures,
+        min_minutes: config.hints.min_minutes,
+    };
+    let rustflags = args
+        .rustflags
+        .or(config.build.rustflags)
+        .unwrap_or_default();
+    let Some(rustflags) = shlex::split(&rustflags) else {
+        println!("Invalid rustflags: {rustflags}");
+        std::process::exit(exit_code::COURSE_ERROR);
+    };
+    let sandbox = if flag(args.sandbox, args.no_sandbox, config.sandbox) {
+        let sandbox = Sandbox::detect();
+        if sandbox.is_none() {
+            println!("No sandbox is available on this system, so the exercises are run without one. On Linux, install bubblewrap.");
+        }
+        sandbox
+    } else {
+        None
+    };
+    let limits = supported_limits(config.limits.memory_mb, config.limits.cpu_seconds);
+    // cargo-nextest builds and runs the tests itself, so they would escape the sandbox and the limits.
+    let limited = sandbox.is_some() || limits.memory_mb.is_some() || limits.cpu_seconds.is_some();
+    let mut nextest = config.build.test_runner == Some(TestRunner::Nextest);
+    if nextest && limited {
+        println!("cargo-nextest can't run the tests in the sandbox or with resource limits, so they are run without it.");
+        nextest = false;
+    } else if nextest && which::which("cargo-nextest").is_err() {
+        println!("cargo-nextest wasn't found, so the tests are run without it. Install it with `cargo install cargo-nextest`.");
+        nextest = false;
+    }
+    set_build_options(BuildOptions {
+        sandbox,
+        memory_limit_mb: limits.memory_mb,
+        cpu_limit_seconds: limits.cpu_seconds,
+        max_output_kib: config.max_output_kib,
+        nextest,
+        deny_warnings: flag(
+            args.deny_warnings,
+            args.no_deny_warnings,
+            config.deny_warnings,
+        ),
+        jobs: args.jobs.or(config.build.jobs),
+        offline: flag(args.offline, args.no_offline, config.build.offline),
+        locked: flag(args.locked, args.no_locked, config.build.locked),
+        rustflags,
+        fast_check: flag(args.fast_check, args.no_fast_check, config.build.fast_check),
+    });
 
     let command = args.command.unwrap_or_else(|| {
         println!("{DEFAULT_OUT}\n");
@@ -126,11 +690,22 @@ fn main() -> Result<()> {
             filter,
             unsolved,
             solved,
+            bookmarked,
+            interactive,
         } => {
-            if !paths && !names {
-                println!("{:<17}\t{:<46}\t{:<7}", "Name", "Path", "Status");
+            if interactive {
+                if let Err(e) = list_ui(&exercises, hint_gate) {
+                    println!("{e:#}");
+                    std::process::exit(1);
+                }
+                return Ok(());
+            }
+            if !paths && !names && !porcelain::enabled() && !json::enabled() {
+                println!("{:<17}\t{:<46}\t{:<7}\tTime", "Name", "Path", "Status");
             }
             let mut exercises_done: u16 = 0;
+            let mut listed = Vec::new();
+            let state = StateFile::load().unwrap_or_default();
             let lowercase_filter = filter
                 .as_ref()
                 .map(|s| s.to_lowercase())
@@ -152,22 +727,37 @@ fn main() -> Result<()> {
                 let filter_cond = filters
                     .iter()
                     .any(|f| exercise.name.contains(f) || fname.contains(f));
-                let looks_done = exercise.looks_done();
-                let status = if looks_done {
+                let looks_done = exercise.looks_done(&state);
+                if looks_done && !exercise.optional {
                     exercises_done += 1;
-                    "Done"
-                } else {
-                    "Pending"
-                };
+                }
+                let status = list_ui::status(exercise, &state);
                 let solve_cond =
                     (looks_done && solved) || (!looks_done && unsolved) || (!solved && !unsolved);
-                if solve_cond && (filter_cond || filter.is_none()) {
-                    let line = if paths {
+                let bookmark_cond = !bookmarked || bookmarks::is_bookmarked(exercise, &state);
+                if solve_cond && bookmark_cond && (filter_cond || filter.is_none()) {
+                    if json::enabled() {
+                        listed.push(json::ListedExercise {
+                            name: &exercise.name,
+                            path: &exercise.path,
+                            topic: exercise.topic(),
+                            status: status.to_lowercase(),
+                            optional: exercise.optional,
+                        });
+                        continue;
+                    }
+                    let line = if porcelain::enabled() {
+                        format!("{}\t{}\t{fname}\n", exercise.name, status.to_lowercase())
+                    } else if paths {
                         format!("{fname}\n")
                     } else if names {
                         format!("{}\n", exercise.name)
                     } else {
-                        format!("{:<17}\t{fname:<46}\t{status:<7}\n", exercise.name)
+                        let time = stats::seconds_spent(exercise, &state)
+                            .map(stats::format_duration)
+                            .unwrap_or_default();
+                        let status = theme::list_status(status, status == "Done");
+                        format!("{:<17}\t{fname:<46}\t{status}\t{time}\n", exercise.name)
                     };
                     // Somehow using println! leads to the binary panicking
                     // when its output is piped.
@@ -185,78 +775,715 @@ fn main() -> Result<()> {
                 }
             }
 
-            let percentage_progress = exercises_done as f32 / exercises.len() as f32 * 100.0;
+            let required = exercises.iter().filter(|e| !e.optional).count();
+            if json::enabled() {
+                json::print(&json::List {
+                    done: exercises_done.into(),
+                    total: required,
+                    exercises: listed,
+                });
+            }
+            if porcelain::enabled() || json::enabled() {
+                std::process::exit(0);
+            }
+            let percentage_progress = exercises_done as f32 / required as f32 * 100.0;
             println!(
                 "Progress: You completed {} / {} exercises ({:.1} %).",
-                exercises_done,
-                exercises.len(),
-                percentage_progress
+                exercises_done, required, percentage_progress
             );
             std::process::exit(0);
         }
 
-        Subcommands::Run { name } => {
+        Subcommands::Run { names, jobs } => {
+            let selected = find_exercises(&names, &exercises);
+            match selected.as_slice() {
+                [exercise] if !is_glob(&names[0]) && !porcelain::enabled() && !json::enabled() => {
+                    run(exercise, verbose).unwrap_or_else(|_| exit_code::exit_for_last_exercise());
+                }
+                _ => run_all(&selected, jobs.map_or(1, usize::from))
+                    .unwrap_or_else(|failure| std::process::exit(failure.code())),
+            }
+        }
+
+        Subcommands::Bookmark { name, remove } => {
             let exercise = find_exercise(&name, &exercises);
 
-            run(exercise, verbose).unwrap_or_else(|_| std::process::exit(1));
+            bookmark(exercise, remove);
         }
 
-        Subcommands::Reset { name } => {
+        Subcommands::Bookmarks => bookmarks(&exercises),
+
+        Subcommands::Reset { name, dry_run } => {
             let exercise = find_exercise(&name, &exercises);
 
-            reset(exercise).unwrap_or_else(|_| std::process::exit(1));
+            match preview_reset(exercise) {
+                Ok(false) => println!("{exercise} doesn't have any changes to reset."),
+                Ok(true) if dry_run => (),
+                Ok(true) => {
+                    reset(exercise).unwrap_or_else(|_| std::process::exit(1));
+                    println!("{exercise} was reset. Run `rustlings restore {}` to bring back your changes.", exercise.name);
+                }
+                Err(e) => {
+                    println!("{e:#}");
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Subcommands::Diff { name } => {
+            let exercise = find_exercise(&name, &exercises);
+            if let Err(e) = show_changes(exercise) {
+                println!("{e:#}");
+                std::process::exit(1);
+            }
         }
 
         Subcommands::Hint { name } => {
             let exercise = find_exercise(&name, &exercises);
+            if json::enabled() {
+                let (hint, locked) = match unlock_hint(&exercise.name, &exercise.hint, hint_gate) {
+                    Ok(hint) => (Some(hint), None),
+                    Err(reason) => (None, Some(reason)),
+                };
+                json::print(&json::Hint {
+                    name: &exercise.name,
+                    hint,
+                    locked,
+                });
+            } else {
+                show_hint(&exercise.name, &exercise.hint, hint_gate);
+            }
+        }
+
+        Subcommands::Compare { name } => {
+            let exercise = find_exercise(&name, &exercises);
+            if let Err(e) = compare(exercise) {
+                println!("{e:#}");
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Explain { code } => {
+            if let Err(e) = explain(code.as_deref(), &explanations) {
+                println!("{e:#}");
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Errors => {
+            if let Err(e) = errors() {
+                println!("{e:#}");
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Glossary { term } => {
+            glossary(&glossary_entries, term.as_deref()).unwrap_or_else(|_| std::process::exit(1));
+        }
+
+        Subcommands::Graph { format } => print!("{}", graph(&exercises, &quizzes, format)),
 
-            println!("{}", exercise.hint);
+        Subcommands::Export {
+            format,
+            solutions,
+            output,
+        } => {
+            let title = course_name.as_deref().unwrap_or("Rustlings");
+            let result =
+                export(&exercises, title, format, solutions).and_then(|workbook| match &output {
+                    Some(path) => fs::write(path, workbook)
+                        .with_context(|| format!("Failed to write {}", path.display())),
+                    None => {
+                        print!("{workbook}");
+                        Ok(())
+                    }
+                });
+            if let Err(e) = result {
+                println!("{e:#}");
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::History { exercise, limit } => {
+            if let Err(e) = history(exercise.as_deref(), limit) {
+                println!("{e:#}");
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Playlist { command } => {
+            let result = match command {
+                PlaylistCommand::Add {
+                    playlist,
+                    exercises: names,
+                } => {
+                    let selected = names
+                        .iter()
+                        .map(|name| find_exercise(name, &exercises))
+                        .collect::<Vec<_>>();
+                    playlist::add(&playlist, &selected)
+                }
+                PlaylistCommand::Remove {
+                    playlist,
+                    exercises: names,
+                } => {
+                    let selected = names
+                        .iter()
+                        .map(|name| find_exercise(name, &exercises))
+                        .collect::<Vec<_>>();
+                    playlist::remove(&playlist, &selected)
+                }
+                PlaylistCommand::List { playlist } => playlist::list(playlist.as_deref()),
+            };
+            if let Err(e) = result {
+                println!("{e:#}");
+                std::process::exit(1);
+            }
         }
 
-        Subcommands::Verify => {
-            verify(&exercises, (0, exercises.len()), verbose, false)
-                .unwrap_or_else(|_| std::process::exit(1));
+        Subcommands::Verify {
+            playlist,
+            from,
+            only,
+        } => {
+            if let Some(playlist) = playlist {
+                restrict_to_playlist(&mut exercises, &playlist);
+            }
+            let lock = take_lock(args.force);
+            let required: Vec<&Exercise> = exercises.iter().filter(|e| !e.optional).collect();
+            let state = StateFile::load().unwrap_or_default();
+            let gate = quiz::next_gate(&quizzes, &required, &state);
+            let reachable = match gate {
+                Some((ind, _)) => &required[..ind],
+                None => &required[..],
+            };
+            let mut slice = reachable.to_vec();
+            if let Some(name) = from {
+                let exercise = find_exercise(&name, &exercises);
+                let Some(ind) = slice.iter().position(|e| e.name == exercise.name) else {
+                    println!(
+                        "{exercise} isn't one of the required exercises that can be verified now!"
+                    );
+                    std::process::exit(1);
+                };
+                slice.drain(..ind);
+            }
+            let progress = match only {
+                Some(topic) => {
+                    if let Err(message) = check_topic(&topic, &exercises) {
+                        println!("{message}");
+                        std::process::exit(1);
+                    }
+                    slice.retain(|e| e.topic() == topic);
+                    if slice.is_empty() {
+                        println!("No exercises found for the topic '{topic}'!");
+                        std::process::exit(1);
+                    }
+                    (0, slice.len())
+                }
+                // The skipped exercises count as done for the progress bar
+                None => (reachable.len() - slice.len(), required.len()),
+            };
+            let result = if porcelain::enabled() {
+                verify_quietly(slice, |verified| {
+                    porcelain::print(&[&verified.exercise.name, verified.result]);
+                })
+            } else if json::enabled() {
+                let mut verified = Vec::new();
+                let result = verify_quietly(slice, |v| verified.push(v));
+                json::print(&json::Results {
+                    passed: result.is_ok(),
+                    exercises: verified
+                        .into_iter()
+                        .map(|v| json::ExerciseResult {
+                            name: &v.exercise.name,
+                            path: &v.exercise.path,
+                            result: v.result,
+                            seconds: v.duration.as_secs_f64(),
+                            stdout: v.output.stdout,
+                            stderr: v.output.stderr,
+                        })
+                        .collect(),
+                });
+                result
+            } else {
+                verify(slice, progress, verbose, false)
+            };
+            unlock_new(&exercises);
+            // `exit` doesn't drop the lock.
+            drop(lock);
+            if result.is_err() {
+                exit_code::exit_for_last_exercise();
+            }
+            if let Some((_, quiz)) = gate {
+                quiz::announce(quiz);
+                std::process::exit(1);
+            }
         }
 
-        Subcommands::Lsp => {
+        Subcommands::Lsp {
+            vscode,
+            devcontainer,
+        } => {
             if let Err(e) = write_project_json(exercises) {
                 println!("Failed to write rust-project.json to disk for rust-analyzer: {e}");
             } else {
                 println!("Successfully generated rust-project.json");
                 println!("rust-analyzer will now parse exercises, restart your language server or editor");
             }
+
+            if vscode {
+                if let Err(e) = write_vscode_config() {
+                    println!("Failed to write the VS Code configuration: {e:#}");
+                }
+            }
+
+            if devcontainer {
+                if let Err(e) = write_devcontainer() {
+                    println!("Failed to write the dev container definition: {e:#}");
+                }
+            }
+        }
+
+        Subcommands::Challenge {
+            topic,
+            minutes,
+            reset,
+        } => {
+            if let Err(e) = challenge(&exercises, &topic, minutes, reset, verbose) {
+                println!("{e:#}");
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Exam { key, check } => {
+            let Some(key) = key.or_else(|| std::env::var("RUSTLINGS_EXAM_KEY").ok()) else {
+                println!("A key is required to sign the report. Pass it with --key or RUSTLINGS_EXAM_KEY.");
+                std::process::exit(1);
+            };
+
+            let result = match (check, &exam) {
+                (Some(path), _) => check_report(&path, &key).map(|report| {
+                    print!(
+                        "The report is valid. Score: {} / {} exercises passed",
+                        report.passed, report.total
+                    );
+                    if let (Some(points), Some(max_points)) = (report.points, report.max_points) {
+                        print!(", {points} / {max_points} points");
+                    }
+                    println!();
+                }),
+                (None, Some(exam)) => grade(exam, &exercises, &key),
+                (None, None) => {
+                    println!("There is no exam.toml file listing the exercises of the exam.");
+                    std::process::exit(1);
+                }
+            };
+            if let Err(e) = result {
+                println!("{e:#}");
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Quiz => {
+            let available = quiz::available(&quizzes, &exercises);
+            if available.is_empty() {
+                println!("There is no quiz to answer right now.");
+            }
+            let mut stdin = io::stdin().lock();
+            for quiz in available {
+                match quiz::ask(quiz, &mut stdin) {
+                    Ok(true) => (),
+                    Ok(false) => std::process::exit(1),
+                    Err(e) => {
+                        println!("Failed to read the answer: {e}");
+                        std::process::exit(1);
+                    }
+                }
+            }
         }
 
-        Subcommands::Watch { success_hints } => match watch(&exercises, verbose, success_hints) {
-            Err(e) => {
-                println!("Error: Could not watch your progress. Error message was {e:?}.");
-                println!("Most likely you've run out of disk space or your 'inotify limit' has been reached.");
+        Subcommands::Eval { name } => {
+            let selected = match name {
+                Some(name) => match expressions.iter().find(|e| e.name == name) {
+                    Some(expression) => vec![expression],
+                    None => {
+                        println!("No expression exercise found for '{name}'!");
+                        std::process::exit(1);
+                    }
+                },
+                None => eval::unsolved(&expressions),
+            };
+            if selected.is_empty() {
+                println!("There is no expression exercise to solve right now.");
+            }
+            let mut stdin = io::stdin().lock();
+            let mut solved = 0;
+            for expression in &selected {
+                match eval::ask(expression, &mut stdin) {
+                    Ok(true) => solved += 1,
+                    Ok(false) => (),
+                    Err(e) => {
+                        println!("Failed to read the code: {e}");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            if solved < selected.len() {
+                println!(
+                    "\nSolved {solved} of {} expression exercises.",
+                    selected.len()
+                );
                 std::process::exit(1);
             }
-            Ok(WatchStatus::Finished) => {
+        }
+
+        Subcommands::Predict { name } => {
+            let selected = match name {
+                Some(name) => match predictions.iter().find(|p| p.name == name) {
+                    Some(prediction) => vec![prediction],
+                    None => {
+                        println!("No prediction exercise found for '{name}'!");
+                        std::process::exit(1);
+                    }
+                },
+                None => predict::unpredicted(&predictions),
+            };
+            if selected.is_empty() {
+                println!("There is no program to predict right now.");
+            }
+            let mut stdin = io::stdin().lock();
+            let mut correct = 0;
+            for prediction in &selected {
+                match predict::ask(prediction, &mut stdin) {
+                    Ok(true) => correct += 1,
+                    Ok(false) => (),
+                    Err(e) => {
+                        println!("Failed to read {}: {e}", prediction.path.display());
+                        std::process::exit(1);
+                    }
+                }
+            }
+            if correct < selected.len() {
                 println!(
-                    "{emoji} All exercises completed! {emoji}",
-                    emoji = Emoji("🎉", "★")
+                    "\nPredicted {correct} of {} programs correctly.",
+                    selected.len()
                 );
-                println!("\n{FENISH_LINE}\n");
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Recheck { all, mark_pending } => {
+            let sample = (!all).then_some(recheck::SAMPLE_SIZE);
+            let (checked, broken) = recheck(&exercises, sample);
+            recheck::report(checked, &broken, mark_pending);
+            if !broken.is_empty() && !mark_pending {
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Recommend => print_recommendation(&exercises),
+
+        Subcommands::Review { list } => {
+            if let Err(e) = review(&exercises, list) {
+                println!("{e:#}");
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Achievements => achievements::list(&exercises),
+
+        Subcommands::Stats => stats(&exercises),
+
+        Subcommands::Revert { name } => {
+            let exercise = find_exercise(&name, &exercises);
+            if !revert_regions(exercise) {
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Share { name, output } => {
+            let exercise = find_exercise(&name, &exercises);
+            let output = output.unwrap_or_else(|| share::default_path(exercise));
+            if let Err(e) = share(exercise, &output) {
+                println!("{e:#}");
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Report { format, output } => {
+            let output = output.unwrap_or_else(|| match format {
+                ExportFormat::Markdown => PathBuf::from("rustlings-report.md"),
+                ExportFormat::Html => PathBuf::from("rustlings-report.html"),
+            });
+            let title = course_name.as_deref().unwrap_or("Rustlings");
+            if let Err(e) = fs::write(&output, report(&exercises, title, format)) {
+                println!("Failed to write {}: {e}", output.display());
+                std::process::exit(1);
+            }
+            println!("The report was written to {}", output.display());
+        }
+
+        Subcommands::Restore { name } => {
+            let exercise = find_exercise(&name, &exercises);
+            if let Err(e) = backup::restore(exercise) {
+                println!("{e:#}");
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Undo => {
+            if let Err(e) = undo() {
+                println!("{e:#}");
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Uninstall {
+            all,
+            keep_solutions,
+            yes,
+        } => {
+            if let Err(e) = uninstall(&exercises, all, keep_solutions, yes) {
+                println!("Failed to uninstall Rustlings: {e:#}");
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Clean => {
+            if let Err(e) = clean() {
+                println!("Failed to clean: {e:#}");
+                std::process::exit(1);
+            }
+        }
+
+        Subcommands::Dev { command } => match command {
+            DevCommand::Watch { debounce } => {
+                let debounce = debounce
+                    .or(config.debounce_ms)
+                    .unwrap_or(DEFAULT_DEBOUNCE_MS);
+                let ignore = ignore_patterns(&config.watch.ignore);
+                if let Err(e) = dev::watch(verbose, Duration::from_millis(debounce), &ignore) {
+                    println!("Error: Could not watch the course. Error message was {e:?}.");
+                    std::process::exit(1);
+                }
+            }
+            DevCommand::RunAllSolutions => {
+                if let Err(e) = dev::run_all_solutions(&exercises) {
+                    println!("{e:#}");
+                    std::process::exit(1);
+                }
+            }
+            DevCommand::Check => {
+                if let Err(e) = dev::check(&exercises) {
+                    println!("{e:#}");
+                    std::process::exit(1);
+                }
             }
-            Ok(WatchStatus::Unfinished) => {
-                println!("We hope you're enjoying learning about Rust!");
-                println!("If you want to continue working on the exercises at a later point, you can simply run `rustlings watch` again");
+            DevCommand::New {
+                path,
+                mode,
+                template,
+            } => {
+                if let Err(e) = dev::new(&path, mode, template.as_deref()) {
+                    println!("{e:#}");
+                    std::process::exit(1);
+                }
             }
         },
+
+        Subcommands::Watch {
+            playlist,
+            success_hints,
+            no_success_hints,
+            minimal_redraw,
+            no_minimal_redraw,
+            background_check,
+            no_background_check,
+            debounce,
+            style_tips,
+            no_style_tips,
+            poll,
+            title,
+            no_title,
+            shuffle,
+        } => {
+            let shuffle = shuffle.map(|seed| {
+                if seed == "random" {
+                    let seed = state_file::now();
+                    println!("Shuffled the exercises with `--shuffle={seed}`");
+                    seed
+                } else {
+                    seed.parse().unwrap_or_else(|_| {
+                        println!("The seed of `--shuffle` must be a number, not '{seed}'");
+                        std::process::exit(1);
+                    })
+                }
+            });
+            if let Some(playlist) = playlist {
+                restrict_to_playlist(&mut exercises, &playlist);
+            }
+            let _lock = take_lock(args.force);
+            shutdown::cancel_runs_on_interrupt();
+            if flag(
+                background_check,
+                no_background_check,
+                config.watch.background_check,
+            ) {
+                recheck::spawn_background_check(exercises.clone());
+            }
+            if flag(style_tips, no_style_tips, config.watch.style_tips) {
+                tips::enable();
+            }
+            footer::set_show_keys(config.watch.show_keys != Some(false));
+            let options = WatchOptions {
+                verbose,
+                success_hints: flag(success_hints, no_success_hints, config.watch.success_hints),
+                minimal_redraw: flag(
+                    minimal_redraw,
+                    no_minimal_redraw,
+                    config.watch.minimal_redraw,
+                ),
+                debounce: Duration::from_millis(
+                    debounce
+                        .or(config.debounce_ms)
+                        .unwrap_or(DEFAULT_DEBOUNCE_MS),
+                ),
+                bell: config.bell == Some(true),
+                poll: poll.or(config.watch.poll_ms).map(Duration::from_millis),
+                ignore: ignore_patterns(&config.watch.ignore),
+                title: flag(title, no_title, config.watch.title),
+                footer: config
+                    .watch
+                    .footer
+                    .clone()
+                    .unwrap_or_else(|| footer::DEFAULT_FOOTER.to_string()),
+                shuffle,
+            };
+            let shell = ShellContext {
+                explanations,
+                glossary_entries,
+                exercises: exercises.clone(),
+                aliases: config.aliases,
+                editor: config.editor,
+                hint_gate,
+            };
+            let status = watch(&exercises, &quizzes, shell, &options);
+            ui::reset_title();
+            match status {
+                Err(e) => {
+                    println!("Error: Could not watch your progress. Error message was {e:?}.");
+                    println!("Most likely you've run out of disk space or your 'inotify limit' has been reached.");
+                    println!("Try `rustlings watch --poll` to check for changes without the watcher of the OS.");
+                    std::process::exit(1);
+                }
+                Ok(WatchStatus::Finished) => {
+                    let emoji = if ui::no_emoji() {
+                        ui::glyph("★", "*")
+                    } else {
+                        "🎉"
+                    };
+                    println!("{emoji} All exercises completed! {emoji}");
+                    let fenish_line = ui::ascii_art(FENISH_LINE);
+                    println!("\n{}\n", ui::plain_if_unsupported(&fenish_line));
+                }
+                Ok(WatchStatus::Unfinished) => {
+                    stats::record_time_until_exit();
+                    println!("We hope you're enjoying learning about Rust!");
+                    println!("If you want to continue working on the exercises at a later point, you can simply run `rustlings watch` again");
+                }
+            }
+        }
     }
 
     Ok(())
 }
 
+// What the watch shell needs to know about the exercise that failed
+struct FailedExercise {
+    name: String,
+    hint: String,
+    topic: String,
+}
+
+impl From<&Exercise> for FailedExercise {
+    fn from(exercise: &Exercise) -> Self {
+        Self {
+            name: exercise.name.clone(),
+            hint: exercise.hint.clone(),
+            topic: exercise.topic().to_string(),
+        }
+    }
+}
+
+// What the watch shell needs besides the state shared with watch mode
+struct ShellContext {
+    explanations: BTreeMap<String, String>,
+    glossary_entries: Vec<GlossaryEntry>,
+    exercises: Vec<Exercise>,
+    // Custom names for the commands from the config
+    aliases: BTreeMap<String, String>,
+    // The editor command from the config
+    editor: Option<String>,
+    hint_gate: HintGate,
+}
+
+impl ShellContext {
+    // Open the exercise with the editor from the config, `$VISUAL` or `$EDITOR`.
+    fn edit(&self, exercise: &Exercise) {
+        let Some(editor) = self
+            .editor
+            .clone()
+            .or_else(|| env::var("VISUAL").ok())
+            .or_else(|| env::var("EDITOR").ok())
+        else {
+            println!("No editor is configured. Set `editor` in the config file or the EDITOR environment variable.");
+            return;
+        };
+
+        let mut parts = Shlex::new(&editor);
+        let Some(program) = parts.next() else {
+            println!("The editor command is empty");
+            return;
+        };
+        if let Err(e) = Command::new(program)
+            .args(parts)
+            .arg(&exercise.path)
+            .status()
+        {
+            println!("failed to execute the editor `{editor}`: {e}");
+        }
+    }
+}
+
+// The exercise that watch mode currently shows. The lock is released before returning, so that
+// commands that compile or open an editor don't block watch mode in the meantime.
+fn current_exercise<'a>(
+    failed_exercise: &Mutex<Option<FailedExercise>>,
+    exercises: &'a [Exercise],
+) -> Option<&'a Exercise> {
+    let current = failed_exercise.lock().unwrap();
+    let name = &current.as_ref()?.name;
+    exercises.iter().find(|e| &e.name == name)
+}
+
 fn spawn_watch_shell(
-    failed_exercise_hint: Arc<Mutex<Option<String>>>,
+    failed_exercise: Arc<Mutex<Option<FailedExercise>>>,
+    pending_quiz: Arc<Mutex<Option<Quiz>>>,
+    shell: ShellContext,
+    should_reverify: Arc<AtomicBool>,
     should_quit: Arc<AtomicBool>,
 ) {
     println!("Welcome to watch mode! You can type 'help' to get an overview of the commands you can use here.");
 
     thread::spawn(move || {
+        let ShellContext {
+            explanations,
+            glossary_entries,
+            exercises,
+            aliases,
+            ..
+        } = &shell;
         let mut input = String::with_capacity(32);
         let mut stdin = io::stdin().lock();
 
@@ -264,20 +1491,114 @@ fn spawn_watch_shell(
             // Recycle input buffer.
             input.clear();
 
-            if let Err(e) = stdin.read_line(&mut input) {
-                println!("error reading command: {e}");
+            match stdin.read_line(&mut input) {
+                // Stdin was closed, there won't be any more commands.
+                Ok(0) => break,
+                Ok(_) => (),
+                Err(e) => println!("error reading command: {e}"),
             }
 
             let input = input.trim();
+            let input = aliases.get(input).map_or(input, String::as_str);
             if input == "hint" {
-                if let Some(hint) = &*failed_exercise_hint.lock().unwrap() {
-                    println!("{hint}");
+                if let Some(exercise) = &*failed_exercise.lock().unwrap() {
+                    show_hint(&exercise.name, &exercise.hint, shell.hint_gate);
+                }
+            } else if let Some(name) = input.strip_prefix("hint ") {
+                match fuzzy::find(name.trim(), exercises) {
+                    Ok(exercise) => show_hint(&exercise.name, &exercise.hint, shell.hint_gate),
+                    Err(suggestions) => println!(
+                        "No exercise found for '{}'!{}",
+                        name.trim(),
+                        fuzzy::did_you_mean(&suggestions)
+                    ),
+                }
+            } else if input == "glossary" {
+                match &*failed_exercise.lock().unwrap() {
+                    Some(exercise) if !exercise.topic.is_empty() => {
+                        glossary_for_topic(glossary_entries, &exercise.topic);
+                    }
+                    _ => {
+                        let _ = glossary(glossary_entries, None);
+                    }
+                }
+            } else if input == "bookmark" {
+                match current_exercise(&failed_exercise, exercises) {
+                    Some(exercise) => bookmark(exercise, false),
+                    None => println!("There is no current exercise to bookmark."),
+                }
+            } else if input == "edit" {
+                match current_exercise(&failed_exercise, exercises) {
+                    Some(exercise) => shell.edit(exercise),
+                    None => println!("There is no current exercise to edit."),
+                }
+            } else if input == "compare" {
+                match current_exercise(&failed_exercise, exercises) {
+                    Some(exercise) => {
+                        if let Err(e) = compare(exercise) {
+                            println!("{e:#}");
+                        }
+                    }
+                    None => println!("There is no current exercise to compare."),
+                }
+            } else if input == "diff" {
+                match current_exercise(&failed_exercise, exercises) {
+                    Some(exercise) => {
+                        if let Err(e) = show_changes(exercise) {
+                            println!("{e:#}");
+                        }
+                    }
+                    None => println!("There is no current exercise to diff."),
+                }
+            } else if input == "revert" {
+                match current_exercise(&failed_exercise, exercises) {
+                    Some(exercise) => {
+                        revert_regions(exercise);
+                    }
+                    None => println!("There is no current exercise to revert."),
+                }
+            } else if input == "recheck" {
+                let (checked, broken) = recheck(exercises, Some(recheck::SAMPLE_SIZE));
+                recheck::report(checked, &broken, false);
+            } else if input == "undo" {
+                if let Err(e) = undo() {
+                    println!("{e:#}");
+                }
+            } else if input == "explain" {
+                if let Err(e) = explain(None, explanations) {
+                    println!("{e:#}");
+                }
+            } else if input == "quiz" {
+                let Some(quiz) = pending_quiz.lock().unwrap().clone() else {
+                    println!("There is no quiz to answer right now.");
+                    continue;
+                };
+                match quiz::ask(&quiz, &mut stdin) {
+                    Ok(true) => should_reverify.store(true, Ordering::SeqCst),
+                    Ok(false) => (),
+                    Err(e) => println!("error reading the answer: {e}"),
+                }
+            } else if input == "tips" {
+                match tips::last() {
+                    Some(tips) => ui::page(&tips),
+                    None => println!("There are no style tips for the last exercise that passed."),
+                }
+            } else if input == "output" {
+                match fs::read_to_string(FULL_OUTPUT_PATH) {
+                    Ok(output) => ui::page(&output),
+                    Err(_) => println!("No exercise was run yet."),
                 }
             } else if input == "clear" {
-                println!("\x1B[2J\x1B[1;1H");
+                ui::clear_screen();
             } else if input == "quit" {
                 should_quit.store(true, Ordering::SeqCst);
                 println!("Bye!");
+            } else if input == "keys" {
+                if footer::toggle_keys() {
+                    println!("The most used commands are shown below the output again.");
+                } else {
+                    println!("The commands are hidden. Type 'keys' to show them again or 'help' for all commands.");
+                }
             } else if input == "help" {
                 println!("{WATCH_MODE_HELP_MESSAGE}");
             } else if let Some(cmd) = input.strip_prefix('!') {
@@ -298,24 +1619,177 @@ fn spawn_watch_shell(
     });
 }
 
+// Returns whether the regions could be restored
+fn revert_regions(exercise: &Exercise) -> bool {
+    match regions::revert(exercise) {
+        Ok(0) => println!("The read-only regions of {exercise} weren't changed."),
+        Ok(1) => println!("Restored a read-only region of {exercise}."),
+        Ok(n) => println!("Restored {n} read-only regions of {exercise}."),
+        Err(e) => {
+            println!("{e:#}");
+            return false;
+        }
+    }
+    true
+}
+
+fn restrict_to_playlist(exercises: &mut Vec<Exercise>, playlist: &str) {
+    if let Err(e) = playlist::restrict(exercises, playlist) {
+        println!("{e:#}");
+        std::process::exit(1);
+    }
+}
+
+// The default patterns of ignored files and the ones of the config files
+fn ignore_patterns(configured: &[String]) -> Vec<String> {
+    watcher::DEFAULT_IGNORE
+        .iter()
+        .map(|pattern| pattern.to_string())
+        .chain(configured.iter().cloned())
+        .collect()
+}
+
+// Like Cargo finds Cargo.toml, commands work from any subdirectory of the course, e.g. when
+// the shell is in `exercises/07_structs/`. Paths in the arguments stay relative to the directory
+// that the command was run in, so they are made absolute before entering the course.
+fn enter_course_dir(args: &mut Args) {
+    let Ok(current_dir) = env::current_dir() else {
+        return;
+    };
+    let Some(course_dir) = current_dir
+        .ancestors()
+        .find(|dir| dir.join("info.toml").is_file())
+    else {
+        return;
+    };
+    if course_dir != current_dir {
+        trace!("Found the course in {}", course_dir.display());
+        for path in path_arguments(args) {
+            *path = absolute_path(&current_dir, path);
+        }
+        if let Err(e) = env::set_current_dir(course_dir) {
+            println!(
+                "Failed to enter the course in {}: {e}",
+                course_dir.display()
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+// The arguments that are paths of files
+fn path_arguments(args: &mut Args) -> Vec<&mut PathBuf> {
+    let mut paths = args.log_file.iter_mut().collect::<Vec<_>>();
+    match &mut args.command {
+        Some(
+            Subcommands::Export { output, .. }
+            | Subcommands::Report { output, .. }
+            | Subcommands::Share { output, .. },
+        ) => paths.extend(output),
+        Some(Subcommands::Dev {
+            command: DevCommand::New { path, template, .. },
+        }) => {
+            paths.push(path);
+            paths.extend(template);
+        }
+        _ => (),
+    }
+    paths
+}
+
+// The path relative to `dir` as an absolute path. `..` is resolved without following symlinks
+// since the files to write don't exist yet.
+fn absolute_path(dir: &Path, path: &Path) -> PathBuf {
+    let mut absolute = PathBuf::new();
+    for component in dir.join(path).components() {
+        match component {
+            Component::ParentDir => {
+                absolute.pop();
+            }
+            Component::CurDir => (),
+            component => absolute.push(component),
+        }
+    }
+    absolute
+}
+
+// A flag like `--sandbox` that `--no-sandbox` turns off, both overriding the config files.
+// Only the last one of them on the command line is set.
+fn flag(on: bool, off: bool, config: Option<bool>) -> bool {
+    on || (!off && config == Some(true))
+}
+
+fn take_lock(force: bool) -> lock::Lock {
+    lock::acquire(force).unwrap_or_else(|e| {
+        println!("{e:#}");
+        std::process::exit(1);
+    })
+}
+
+fn is_glob(name: &str) -> bool {
+    name.contains(['*', '?'])
+}
+
+// The exercises of the names and glob patterns in the given order, without duplicates
+fn find_exercises(names: &[String], exercises: &[Exercise]) -> Vec<Exercise> {
+    let mut found: Vec<Exercise> = Vec::new();
+    for name in names {
+        let matches = if is_glob(name) {
+            let matches: Vec<&Exercise> = exercises
+                .iter()
+                .filter(|e| glob_match(name, &e.name))
+                .collect();
+            if matches.is_empty() {
+                println!("No exercise matches '{name}'!");
+                std::process::exit(1);
+            }
+            matches
+        } else {
+            vec![find_exercise(name, exercises)]
+        };
+        for exercise in matches {
+            if !found.iter().any(|e| e.name == exercise.name) {
+                found.push(exercise.clone());
+            }
+        }
+    }
+    found
+}
+
 fn find_exercise<'a>(name: &str, exercises: &'a [Exercise]) -> &'a Exercise {
     if name == "next" {
+        let state = StateFile::load().unwrap_or_default();
         exercises
             .iter()
-            .find(|e| !e.looks_done())
+            .find(|e| !e.optional && !e.looks_done(&state))
             .unwrap_or_else(|| {
-                println!("🎉 Congratulations! You have done all the exercises!");
-                println!("🔚 There are no more exercises to do next!");
+                if ui::no_emoji() {
+                    println!("Congratulations! You have done all the exercises!");
+                    println!("There are no more exercises to do next!");
+                } else {
+                    println!("🎉 Congratulations! You have done all the exercises!");
+                    println!("🔚 There are no more exercises to do next!");
+                }
                 std::process::exit(1)
             })
     } else {
-        exercises
+        if let Some(exercise) = exercises.iter().find(|e| e.name == name) {
+            return exercise;
+        }
+        if let Some(exercise) = exercises
             .iter()
-            .find(|e| e.name == name)
-            .unwrap_or_else(|| {
-                println!("No exercise found for '{name}'!");
-                std::process::exit(1)
-            })
+            .find(|e| e.aliases.iter().any(|alias| alias == name))
+        {
+            println!("'{name}' has been renamed to '{}'.", exercise.name);
+            return exercise;
+        }
+        fuzzy::find(name, exercises).unwrap_or_else(|suggestions| {
+            println!(
+                "No exercise found for '{name}'!{}",
+                fuzzy::did_you_mean(&suggestions)
+            );
+            std::process::exit(1)
+        })
     }
 }
 
@@ -324,84 +1798,238 @@ enum WatchStatus {
     Unfinished,
 }
 
-fn watch(
-    exercises: &[Exercise],
+const DEFAULT_DEBOUNCE_MS: u64 = 1000;
+
+// The settings of watch mode from the command line and the config files
+struct WatchOptions {
     verbose: bool,
     success_hints: bool,
+    minimal_redraw: bool,
+    // How long to wait for more file changes before verifying
+    debounce: Duration,
+    // Ring the terminal bell after verifying
+    bell: bool,
+    // Poll for file changes in this interval instead of using the watcher of the OS
+    poll: Option<Duration>,
+    // Glob patterns of the files whose changes are ignored
+    ignore: Vec<String>,
+    // Show the progress in the title of the terminal
+    title: bool,
+    // The template of the line below the output of the current exercise
+    footer: String,
+    // The seed of the order in which the pending exercises are verified
+    shuffle: Option<u64>,
+}
+
+fn watch(
+    exercises: &[Exercise],
+    quizzes: &[Quiz],
+    shell: ShellContext,
+    options: &WatchOptions,
 ) -> notify::Result<WatchStatus> {
-    /* Clears the terminal with an ANSI escape code.
-    Works in UNIX and newer Windows terminals. */
-    fn clear_screen() {
-        println!("\x1Bc");
-    }
+    let WatchOptions {
+        verbose,
+        success_hints,
+        minimal_redraw,
+        ..
+    } = *options;
+
+    /* The output is kept linear if it isn't written to a terminal.
+    Resetting the whole terminal is slow over SSH or in tmux, so the new output
+    is only separated from the old one with minimal redrawing. */
+    let clear_screen = || {
+        if minimal_redraw {
+            println!("\n{}\n", console::style("-".repeat(60)).dim());
+        } else if io::stdout().is_terminal() {
+            ui::clear_screen();
+        }
+    };
 
     let (tx, rx) = channel();
     let should_quit = Arc::new(AtomicBool::new(false));
+    let should_reverify = Arc::new(AtomicBool::new(false));
+    let failed_exercise = Arc::new(Mutex::new(None));
+    let pending_quiz = Arc::new(Mutex::new(None));
 
-    let mut debouncer = new_debouncer(Duration::from_secs(1), tx)?;
+    let mut debouncer = FileWatcher::new(options.debounce, options.poll, tx)?;
     debouncer
         .watcher()
         .watch(Path::new("./exercises"), RecursiveMode::Recursive)?;
 
-    clear_screen();
+    // Optional exercises are only verified in watch mode when their file is edited.
+    let required: Vec<&Exercise> = exercises.iter().filter(|e| !e.optional).collect();
+    // Quizzes gate the exercises in the order of the course, so only the order in which
+    // the reachable exercises are verified is shuffled.
+    let mut order = required.clone();
+    if let Some(seed) = options.shuffle {
+        shuffle::shuffle(&mut order, seed);
+    }
 
-    let failed_exercise_hint = match verify(
-        exercises.iter(),
-        (0, exercises.len()),
-        verbose,
-        success_hints,
-    ) {
-        Ok(_) => return Ok(WatchStatus::Finished),
-        Err(exercise) => Arc::new(Mutex::new(Some(exercise.hint.clone()))),
+    // Verify the pending exercises, starting with the edited one if there is one.
+    // Returns `true` if all exercises are done.
+    let verify_pending_exercises = |edited: Option<&Path>| -> bool {
+        let is_edited = |e: &Exercise| edited.is_some_and(|path| path.ends_with(&e.path));
+
+        if let Some(bonus) = exercises.iter().find(|e| e.optional && is_edited(e)) {
+            if let Err(exercise) = verify([bonus], (0, 1), verbose, success_hints) {
+                *failed_exercise.lock().unwrap() = Some(FailedExercise::from(exercise));
+                return false;
+            }
+        }
+
+        // Exercises after a quiz that wasn't answered yet are not verified.
+        let state = StateFile::load().unwrap_or_default();
+        let gate = quiz::next_gate(quizzes, &required, &state);
+        let reachable = match gate {
+            Some((ind, _)) => &required[..ind],
+            None => &required[..],
+        };
+
+        let pending_exercises = required
+            .iter()
+            .copied()
+            .find(|e| is_edited(e))
+            .into_iter()
+            .chain(order.iter().copied().filter(|e| {
+                reachable.iter().any(|r| r.name == e.name) && !e.looks_done(&state) && !is_edited(e)
+            }));
+        let num_done = required
+            .iter()
+            .filter(|e| e.looks_done(&state) && !is_edited(e))
+            .count();
+        let result = verify(
+            pending_exercises,
+            (num_done, required.len()),
+            verbose,
+            success_hints,
+        );
+        unlock_new(exercises);
+        match result {
+            Ok(_) => match gate {
+                Some((_, quiz)) => {
+                    quiz::announce(quiz);
+                    *failed_exercise.lock().unwrap() = None;
+                    *pending_quiz.lock().unwrap() = Some(quiz.clone());
+                    false
+                }
+                None => true,
+            },
+            Err(exercise) => {
+                *failed_exercise.lock().unwrap() = Some(FailedExercise::from(exercise));
+                false
+            }
+        }
     };
-    spawn_watch_shell(Arc::clone(&failed_exercise_hint), Arc::clone(&should_quit));
+    let verify_pending = |edited: Option<&Path>| -> bool {
+        let finished = verify_pending_exercises(edited);
+        let state = StateFile::load().unwrap_or_default();
+        let num_done = required.iter().filter(|e| e.looks_done(&state)).count();
+        if options.title {
+            let current = failed_exercise
+                .lock()
+                .unwrap()
+                .as_ref()
+                .map(|e| e.name.clone());
+            ui::set_title(&progress_title(
+                num_done,
+                required.len(),
+                current.as_deref(),
+            ));
+        }
+        let timings = last_timings();
+        if timings.compile.is_some() {
+            println!("{}", console::style(timings).dim());
+        }
+        let current = failed_exercise
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|e| e.name.clone());
+        let current = current.and_then(|name| exercises.iter().find(|e| e.name == name));
+        if let (Some(exercise), false) = (current, options.footer.is_empty()) {
+            let footer = Footer {
+                num_done,
+                total: required.len(),
+                exercise,
+            };
+            println!(
+                "{}",
+                console::style(footer::render(&options.footer, &footer)).dim()
+            );
+        }
+        if options.bell {
+            print!("\x07");
+            let _ = io::stdout().flush();
+        }
+        finished
+    };
+
+    clear_screen();
+    if let Some(summary) = history::resume_summary(exercises) {
+        println!("{}\n", console::style(summary).bold());
+    }
+    if verify_pending(None) {
+        return Ok(WatchStatus::Finished);
+    }
+
+    // Reading commands from piped stdin (e.g. in Docker or CI) would never end,
+    // so only the file changes are watched in that case.
+    if io::stdin().is_terminal() {
+        spawn_watch_shell(
+            Arc::clone(&failed_exercise),
+            Arc::clone(&pending_quiz),
+            shell,
+            Arc::clone(&should_reverify),
+            Arc::clone(&should_quit),
+        );
+    } else {
+        println!("Watching for changes without reading commands since stdin is not a terminal. Press Ctrl+C to exit.");
+    }
+    let mut activity = ActivityTracker::default();
     loop {
         match rx.recv_timeout(Duration::from_secs(1)) {
             Ok(event) => match event {
                 Ok(events) => {
                     for event in events {
+                        trace!("File event {:?} for {}", event.kind, event.path.display());
                         let event_path = event.path;
                         if event.kind == DebouncedEventKind::Any
                             && event_path.extension() == Some(OsStr::new("rs"))
                             && event_path.exists()
+                            && !is_ignored(&event_path, &options.ignore)
                         {
+                            trace!("Verifying after the change of {}", event_path.display());
                             let filepath = event_path.as_path().canonicalize().unwrap();
-                            let pending_exercises =
-                                exercises
-                                    .iter()
-                                    .find(|e| filepath.ends_with(&e.path))
-                                    .into_iter()
-                                    .chain(exercises.iter().filter(|e| {
-                                        !e.looks_done() && !filepath.ends_with(&e.path)
-                                    }));
-                            let num_done = exercises
-                                .iter()
-                                .filter(|e| e.looks_done() && !filepath.ends_with(&e.path))
-                                .count();
+                            if let Some(exercise) =
+                                exercises.iter().find(|e| filepath.ends_with(&e.path))
+                            {
+                                activity.edited(exercise);
+                            }
                             clear_screen();
-                            match verify(
-                                pending_exercises,
-                                (num_done, exercises.len()),
-                                verbose,
-                                success_hints,
-                            ) {
-                                Ok(_) => return Ok(WatchStatus::Finished),
-                                Err(exercise) => {
-                                    let mut failed_exercise_hint =
-                                        failed_exercise_hint.lock().unwrap();
-                                    *failed_exercise_hint = Some(exercise.hint.clone());
-                                }
+                            if verify_pending(Some(&filepath)) {
+                                return Ok(WatchStatus::Finished);
                             }
                         }
                     }
                 }
-                Err(e) => println!("watch error: {e:?}"),
+                Err(e) => {
+                    trace!("The watcher failed: {e:?}");
+                    println!("watch error: {e:?}");
+                }
             },
             Err(RecvTimeoutError::Timeout) => {
-                // the timeout expired, just check the `should_quit` variable below then loop again
+                // the timeout expired, check the `should_reverify` and `should_quit` variables below then loop again
             }
             Err(e) => println!("watch error: {e:?}"),
         }
+        // A quiz was answered, so the next exercises can be verified
+        if should_reverify.swap(false, Ordering::SeqCst) {
+            *pending_quiz.lock().unwrap() = None;
+            clear_screen();
+            if verify_pending(None) {
+                return Ok(WatchStatus::Finished);
+            }
+        }
         // Check if we need to exit
         if should_quit.load(Ordering::SeqCst) {
             return Ok(WatchStatus::Unfinished);
@@ -409,6 +2037,15 @@ fn watch(
     }
 }
 
+// E.g. `rustlings — 42/96 — iterators3`
+fn progress_title(num_done: usize, total: usize, current: Option<&str>) -> String {
+    let dash = ui::glyph("—", "-");
+    match current {
+        Some(name) => format!("rustlings {dash} {num_done}/{total} {dash} {name}"),
+        None => format!("rustlings {dash} {num_done}/{total}"),
+    }
+}
+
 const DEFAULT_OUT: &str = "Thanks for installing Rustlings!
 
 Is this your first time? Don't worry, Rustlings was made for beginners! We are
@@ -473,11 +2110,24 @@ const WELCOME: &str = r"       welcome to...
                                |___/";
 
 const WATCH_MODE_HELP_MESSAGE: &str = "Commands available to you in watch mode:
-  hint   - prints the current exercise's hint
-  clear  - clears the screen
-  quit   - quits watch mode
-  !<cmd> - executes a command, like `!rustc --explain E0381`
-  help   - displays this help message
+  hint     - prints the current exercise's hint, or the one of another exercise with `hint <name>`
+  quiz     - answers the quiz that comes before the next exercise
+  bookmark - bookmarks the current exercise to revisit it later
+  edit     - opens the current exercise in your editor
+  compare  - compares your passing solution of the current exercise with the official one
+  diff     - shows your changes of the current exercise compared to the original
+  revert   - restores the read-only regions of the current exercise that you changed
+  recheck  - checks that some of the done exercises still pass
+  undo     - undoes the last reset of an exercise
+  explain  - explains the errors of the last failed compilation
+  output   - shows the full output of the last run in a pager
+  tips     - shows the style tips of Clippy for the last exercise that passed
+  glossary - shows the glossary entries for the topic of the current exercise
+  clear    - clears the screen
+  keys     - hides or shows the most used commands below the output
+  quit     - quits watch mode
+  !<cmd>   - executes a command, like `!rustc --explain E0381`
+  help     - displays this help message
 
 Watch mode automatically re-evaluates the current exercise
 when you edit a file's contents.";
@@ -0,0 +1,166 @@
+use regex::Regex;
+use std::env;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use std::process;
+use std::time::Duration;
+
+use crate::exercise::Exercise;
+
+// Replacements that change the behavior of the implementation in ways
+// that meaningful tests notice
+const OPERATORS: &[(&str, &str)] = &[
+    (r" \+ ", " - "),
+    (r" - ", " + "),
+    (r" \* ", " / "),
+    (r" / ", " * "),
+    (r" % ", " / "),
+    (r" == ", " != "),
+    (r" != ", " == "),
+    (r" < ", " <= "),
+    (r" <= ", " < "),
+    (r" > ", " >= "),
+    (r" >= ", " > "),
+    (r" && ", " || "),
+    (r" \|\| ", " && "),
+    (r"\btrue\b", "false"),
+    (r"\bfalse\b", "true"),
+];
+// Each mutant is compiled and tested, so their number is limited.
+const MAX_MUTANTS: usize = 30;
+// Mutants that run this many times longer than the exercise are stopped
+const TIMEOUT_FACTOR: u32 = 10;
+const MIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+// A change of a line of the implementation
+#[derive(PartialEq, Eq, Debug)]
+pub struct Mutation {
+    // The line number, starting at 1
+    pub line: usize,
+    pub original: String,
+    pub mutated: String,
+}
+
+impl Display for Mutation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Your tests still pass if line {} of the implementation is changed from\n    {}\nto\n    {}\nWrite a test that fails with this change.",
+            self.line,
+            self.original.trim(),
+            self.mutated.trim(),
+        )
+    }
+}
+
+// The mutations of the lines before the test module.
+// Comments, strings and items like `use` declarations are left alone.
+fn mutations(source: &str) -> Vec<Mutation> {
+    let operators = OPERATORS
+        .iter()
+        .map(|(pattern, replacement)| (Regex::new(pattern).unwrap(), *replacement))
+        .collect::<Vec<_>>();
+
+    let mut mutations = Vec::new();
+    for (ind, line) in source.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("#[cfg(test)]") {
+            break;
+        }
+        if trimmed.starts_with("//")
+            || trimmed.starts_with('#')
+            || trimmed.starts_with("use ")
+            || line.contains('"')
+        {
+            continue;
+        }
+
+        for (regex, replacement) in &operators {
+            for found in regex.find_iter(line) {
+                let mutated = format!(
+                    "{}{replacement}{}",
+                    &line[..found.start()],
+                    &line[found.end()..]
+                );
+                mutations.push(Mutation {
+                    line: ind + 1,
+                    original: line.to_string(),
+                    mutated,
+                });
+            }
+        }
+    }
+    mutations.truncate(MAX_MUTANTS);
+    mutations
+}
+
+// Test the mutants of the exercise's implementation and return the first one that its tests don't catch.
+// Mutants that don't compile are skipped. Mutants can loop forever, e.g. with `<` replaced by
+// `<=`, so they are stopped after a multiple of the run time of the exercise and count as caught.
+pub fn surviving(exercise: &Exercise, run_time: Duration) -> Option<Mutation> {
+    let timeout = (run_time * TIMEOUT_FACTOR).max(MIN_TIMEOUT);
+    let source = fs::read_to_string(&exercise.path).ok()?;
+    let mutant_path = env::temp_dir().join(format!(
+        "rustlings_mutant_{}_{}.rs",
+        process::id(),
+        exercise.name
+    ));
+    let mut mutant = exercise.clone();
+    mutant.path = mutant_path.clone();
+    mutant.mutants = false;
+
+    let mut survivor = None;
+    for mutation in mutations(&source) {
+        let mutated_source = source
+            .lines()
+            .enumerate()
+            .map(|(ind, line)| {
+                if ind + 1 == mutation.line {
+                    format!("{}\n", mutation.mutated)
+                } else {
+                    format!("{line}\n")
+                }
+            })
+            .collect::<String>();
+        if fs::write(&mutant_path, mutated_source).is_err() {
+            break;
+        }
+        let survived = mutant
+            .compile()
+            .ok()
+            .is_some_and(|compiled| compiled.run_with_timeout(timeout).is_ok());
+        if survived {
+            survivor = Some(mutation);
+            break;
+        }
+    }
+    let _ = fs::remove_file(&mutant_path);
+    survivor
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_mutations() {
+        let source = "// a + b
+fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[cfg(test)]
+mod tests {
+    fn check() { assert!(1 + 1 == 2); }
+}
+";
+        assert_eq!(
+            mutations(source),
+            [Mutation {
+                line: 3,
+                original: String::from("    a + b"),
+                mutated: String::from("    a - b"),
+            }]
+        );
+    }
+}
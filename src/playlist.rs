@@ -0,0 +1,108 @@
+use anyhow::{bail, Context, Result};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+
+use crate::exercise::Exercise;
+
+// Named subsets of the exercises, e.g. `"week 3" = ["vecs1", "vecs2"]`.
+// The file can be edited by hand or with `rustlings playlist`.
+pub const PLAYLISTS_PATH: &str = "playlists.toml";
+
+pub type Playlists = BTreeMap<String, Vec<String>>;
+
+pub fn load() -> Result<Playlists> {
+    match fs::read_to_string(PLAYLISTS_PATH) {
+        Ok(content) => toml_edit::de::from_str(&content)
+            .with_context(|| format!("Failed to parse {PLAYLISTS_PATH}")),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Playlists::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {PLAYLISTS_PATH}")),
+    }
+}
+
+// Comments in the file are lost when saving.
+fn save(playlists: &Playlists) -> Result<()> {
+    let mut content = String::new();
+    for (name, exercises) in playlists {
+        // JSON strings are valid TOML basic strings.
+        content.push_str(&format!("{} = [\n", serde_json::to_string(name)?));
+        for exercise in exercises {
+            content.push_str(&format!("    {},\n", serde_json::to_string(exercise)?));
+        }
+        content.push_str("]\n");
+    }
+    fs::write(PLAYLISTS_PATH, content).with_context(|| format!("Failed to write {PLAYLISTS_PATH}"))
+}
+
+pub fn add(playlist: &str, exercises: &[&Exercise]) -> Result<()> {
+    let mut playlists = load()?;
+    let names = playlists.entry(playlist.to_string()).or_default();
+    for exercise in exercises {
+        if !names.contains(&exercise.name) {
+            names.push(exercise.name.clone());
+        }
+    }
+    let count = names.len();
+    save(&playlists)?;
+
+    println!("The playlist '{playlist}' has {count} exercises.");
+    Ok(())
+}
+
+// Remove the exercises from the playlist, or the whole playlist if no exercises are given.
+pub fn remove(playlist: &str, exercises: &[&Exercise]) -> Result<()> {
+    let mut playlists = load()?;
+    let Some(names) = playlists.get_mut(playlist) else {
+        bail!("There is no playlist named '{playlist}'");
+    };
+
+    if exercises.is_empty() {
+        playlists.remove(playlist);
+        println!("Removed the playlist '{playlist}'.");
+    } else {
+        names.retain(|name| exercises.iter().all(|e| &e.name != name));
+        println!("The playlist '{playlist}' has {} exercises.", names.len());
+    }
+    save(&playlists)
+}
+
+// List the playlists, or the exercises of one playlist.
+pub fn list(playlist: Option<&str>) -> Result<()> {
+    let playlists = load()?;
+    match playlist {
+        Some(playlist) => {
+            let Some(names) = playlists.get(playlist) else {
+                bail!("There is no playlist named '{playlist}'");
+            };
+            for name in names {
+                println!("{name}");
+            }
+        }
+        None if playlists.is_empty() => {
+            println!("There are no playlists yet. Create one with `rustlings playlist add <playlist> <exercises>...`.");
+        }
+        None => {
+            for (name, names) in &playlists {
+                println!("{name:<30}\t{} exercises", names.len());
+            }
+        }
+    }
+    Ok(())
+}
+
+// Keep only the exercises of the playlist, in the order of the course.
+pub fn restrict(exercises: &mut Vec<Exercise>, playlist: &str) -> Result<()> {
+    let playlists = load()?;
+    let Some(names) = playlists.get(playlist) else {
+        bail!("There is no playlist named '{playlist}'");
+    };
+    if let Some(unknown) = names
+        .iter()
+        .find(|name| exercises.iter().all(|e| &e.name != *name))
+    {
+        bail!("The playlist '{playlist}' contains the unknown exercise '{unknown}'");
+    }
+
+    exercises.retain(|e| names.contains(&e.name));
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::exit_code::Failure;
+
+// `--porcelain` prints a line per exercise with fields separated by tabs, for scripts.
+// Unlike the normal output, the format is documented in the README and doesn't change.
+// New fields are only ever added at the end of the lines.
+static PORCELAIN: AtomicBool = AtomicBool::new(false);
+
+pub fn enable() {
+    PORCELAIN.store(true, Ordering::Relaxed);
+    console::set_colors_enabled(false);
+}
+
+pub fn enabled() -> bool {
+    PORCELAIN.load(Ordering::Relaxed)
+}
+
+// The result of running or verifying an exercise
+pub fn result(failure: Option<Failure>) -> &'static str {
+    match failure {
+        None => "passed",
+        Some(Failure::Compilation) => "compilation-failed",
+        Some(Failure::Tests) => "tests-failed",
+        Some(Failure::Timeout) => "timeout",
+    }
+}
+
+pub fn print(fields: &[&str]) {
+    println!("{}", fields.join("\t"));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_result() {
+        assert_eq!(result(None), "passed");
+        assert_eq!(result(Some(Failure::Compilation)), "compilation-failed");
+        assert_eq!(result(Some(Failure::Timeout)), "timeout");
+    }
+}
@@ -0,0 +1,127 @@
+use console::style;
+use serde::Deserialize;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+
+use crate::exercise::Exercise;
+use crate::state_file::{self, StateFile};
+
+// What learners type to predict that a program panics or exits with an error
+const PANIC_ANSWER: &str = "panic";
+// What learners type to predict that a program doesn't compile, e.g. since it uses a moved value
+const COMPILE_ERROR_ANSWER: &str = "error";
+
+// A program whose output the learner predicts before it is run, e.g. to learn about
+// evaluation order, integer overflow or moves.
+// This is deserialized from the `predictions` array in the info.toml file.
+#[derive(Deserialize, Clone, Debug)]
+pub struct Prediction {
+    // Name of the prediction exercise
+    pub name: String,
+    // The path to the program
+    pub path: PathBuf,
+    // Explains the output after a wrong prediction
+    #[serde(default)]
+    pub hint: String,
+}
+
+impl Prediction {
+    pub fn is_predicted(&self, state: &StateFile) -> bool {
+        state.predictions.get(&self.name) == Some(&true)
+    }
+
+    // What the program printed, or the answer for a program that doesn't compile or run,
+    // followed by the output of the compiler or the panic
+    fn output(&self) -> (String, String) {
+        let program = Exercise::program(&self.name, self.path.clone());
+        let compiled = match program.compile() {
+            Ok(compiled) => compiled,
+            Err(output) => return (String::from(COMPILE_ERROR_ANSWER), output.stderr),
+        };
+        match compiled.run() {
+            Ok(output) => (output.stdout, String::new()),
+            Err(output) => (String::from(PANIC_ANSWER), output.stderr),
+        }
+    }
+}
+
+// Trailing whitespace and empty lines at the end don't count.
+fn normalize(output: &str) -> Vec<&str> {
+    let mut lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+    lines
+}
+
+// The prediction exercises that weren't predicted correctly yet
+pub fn unpredicted(predictions: &[Prediction]) -> Vec<&Prediction> {
+    let state = StateFile::load().unwrap_or_default();
+    predictions
+        .iter()
+        .filter(|prediction| !prediction.is_predicted(&state))
+        .collect()
+}
+
+// Show the program, read the predicted output until an empty line and compare it with the
+// output of the program. The result is recorded. Returns whether the prediction was correct.
+pub fn ask(prediction: &Prediction, input: &mut impl BufRead) -> io::Result<bool> {
+    let source = fs::read_to_string(&prediction.path)?;
+    println!(
+        "\n{} ({})\n",
+        style(&prediction.name).bold(),
+        prediction.path.display()
+    );
+    println!("{}", source.trim_end());
+    println!(
+        "\nWhat does this program print? Finish with an empty line.\nType `{PANIC_ANSWER}` if it panics or `{COMPILE_ERROR_ANSWER}` if it doesn't compile."
+    );
+
+    let mut predicted = String::new();
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        predicted.push_str(&line);
+    }
+    if predicted.is_empty() {
+        println!("Skipped {}.", prediction.name);
+        return Ok(false);
+    }
+
+    let (output, details) = prediction.output();
+    let correct = normalize(&predicted) == normalize(&output);
+    state_file::update(|state| {
+        state.predictions.insert(prediction.name.clone(), correct);
+    });
+
+    if correct {
+        success!("{} was predicted correctly!", prediction.name);
+    } else {
+        warn!("Not quite. The answer for {} is:", prediction.name);
+        println!("{}", output.trim_end());
+        if !details.is_empty() {
+            println!("\n{}", details.trim());
+        }
+        if !prediction.hint.is_empty() {
+            println!("\n{}", prediction.hint);
+        }
+    }
+    Ok(correct)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("1 \n2\n\n"), ["1", "2"]);
+        assert_eq!(normalize("a\n\nb"), ["a", "", "b"]);
+        assert!(normalize("\n").is_empty());
+    }
+}
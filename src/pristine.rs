@@ -0,0 +1,69 @@
+use anyhow::{bail, Result};
+use std::fs;
+use std::path::{Component, Path};
+use std::process::Command;
+
+// The exercises of the course as they were when Rustlings was built, by path
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/pristine.rs"));
+}
+
+pub use embedded::INFO_TOML;
+
+// The paths of the embedded exercises
+pub fn embedded_paths() -> impl Iterator<Item = &'static str> {
+    embedded::EXERCISES.iter().map(|(path, _)| *path)
+}
+
+// The path with `/` as separator and without `./`, like the embedded paths
+fn normalize(path: &Path) -> String {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+// The unmodified content of an exercise, from the copy embedded in Rustlings
+// or from the last commit if the course isn't the embedded one, e.g. another course
+// or a newer version of the exercises with the same paths.
+pub fn pristine(path: &Path) -> Result<String> {
+    let normalized = normalize(path);
+    let embedded_course = fs::read_to_string("info.toml").is_ok_and(|info| info == INFO_TOML);
+    if let Some((_, content)) = embedded::EXERCISES
+        .iter()
+        .find(|(embedded, _)| embedded_course && *embedded == normalized)
+    {
+        return Ok(content.to_string());
+    }
+
+    let output = Command::new("git")
+        .arg("show")
+        .arg(format!("HEAD:./{normalized}"))
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+        }
+        _ => bail!("There is no pristine copy of {normalized}"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_embedded() {
+        let intro1 = pristine(Path::new("./exercises/00_intro/intro1.rs")).unwrap();
+        assert!(intro1.starts_with("// intro1.rs"));
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize(Path::new("./exercises/vecs1.rs")),
+            "exercises/vecs1.rs"
+        );
+    }
+}
@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
 use serde::Serialize;
+use serde_json::json;
 use std::env;
-use std::path::PathBuf;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
 use crate::exercise::Exercise;
@@ -81,3 +84,80 @@ pub fn write_project_json(exercises: Vec<Exercise>) -> Result<()> {
 
     Ok(())
 }
+
+/// Write `.vscode/settings.json` and `.vscode/tasks.json` to disk.
+/// rust-analyzer is pointed to the `rust-project.json` file written by `write_project_json`.
+pub fn write_vscode_config() -> Result<()> {
+    let settings = json!({
+        "rust-analyzer.linkedProjects": ["rust-project.json"],
+        "rust-analyzer.check.command": "clippy",
+        "files.exclude": { "**/target": true },
+    });
+    let tasks = json!({
+        "version": "2.0.0",
+        "tasks": [
+            {
+                "label": "rustlings watch",
+                "type": "shell",
+                "command": "rustlings watch",
+                "isBackground": true,
+                "problemMatcher": [],
+            },
+            {
+                "label": "rustlings verify",
+                "type": "shell",
+                "command": "rustlings verify",
+                "group": "test",
+                "problemMatcher": ["$rustc"],
+            },
+        ],
+    });
+
+    write_new_json(Path::new(".vscode/settings.json"), &settings)?;
+    write_new_json(Path::new(".vscode/tasks.json"), &tasks)
+}
+
+/// Write `.devcontainer/devcontainer.json` to disk.
+/// The container installs the version of Rustlings that wrote it, since the exercises and
+/// the state file belong to that version.
+pub fn write_devcontainer() -> Result<()> {
+    let devcontainer = json!({
+        "image": "mcr.microsoft.com/devcontainers/rust:1",
+        "postCreateCommand": [
+            "cargo",
+            "install",
+            "--locked",
+            "--version",
+            env!("CARGO_PKG_VERSION"),
+            "rustlings",
+        ],
+        "postAttachCommand": ["rustlings", "watch"],
+        "customizations": {
+            "vscode": { "extensions": ["rust-lang.rust-analyzer"] },
+        },
+    });
+
+    write_new_json(Path::new(".devcontainer/devcontainer.json"), &devcontainer)
+}
+
+// Existing files are left untouched since they might have been customized.
+fn write_new_json(path: &Path, content: &serde_json::Value) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = match OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            println!("Skipping {} because it already exists", path.display());
+            return Ok(());
+        }
+        Err(e) => return Err(e).with_context(|| format!("Failed to create {}", path.display())),
+    };
+
+    serde_json::to_writer_pretty(&mut file, content)?;
+    file.write_all(b"\n")?;
+    println!("Successfully generated {}", path.display());
+
+    Ok(())
+}
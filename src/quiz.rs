@@ -0,0 +1,105 @@
+use console::style;
+use serde::Deserialize;
+use std::io::{self, BufRead, Write};
+
+use crate::exercise::Exercise;
+use crate::state_file::{self, StateFile};
+
+// A multiple-choice question that is asked after completing an exercise.
+// This is deserialized from the `quizzes` array in the info.toml file.
+#[derive(Deserialize, Clone, Debug)]
+pub struct Quiz {
+    // Name of the quiz
+    pub name: String,
+    // The name of the exercise after which the quiz is asked
+    pub after: String,
+    // The question to answer
+    pub question: String,
+    // The possible answers
+    pub choices: Vec<String>,
+    // The number of the correct choice, starting at 1
+    pub answer: usize,
+    // Optional quizzes don't need to be answered to continue with the exercises
+    #[serde(default)]
+    pub optional: bool,
+}
+
+impl Quiz {
+    pub fn is_answered(&self, state: &StateFile) -> bool {
+        state.quiz_answers.get(&self.name) == Some(&self.answer)
+    }
+}
+
+// Find the first required quiz that wasn't answered yet.
+// Returns the quiz and the number of exercises before it.
+pub fn next_gate<'a>(
+    quizzes: &'a [Quiz],
+    exercises: &[&Exercise],
+    state: &StateFile,
+) -> Option<(usize, &'a Quiz)> {
+    quizzes
+        .iter()
+        .filter(|quiz| !quiz.optional && !quiz.is_answered(state))
+        .filter_map(|quiz| {
+            let ind = exercises.iter().position(|e| e.name == quiz.after)?;
+            Some((ind + 1, quiz))
+        })
+        .min_by_key(|(ind, _)| *ind)
+}
+
+// All quizzes that weren't answered yet and whose exercise is done.
+pub fn available<'a>(quizzes: &'a [Quiz], exercises: &[Exercise]) -> Vec<&'a Quiz> {
+    let state = StateFile::load().unwrap_or_default();
+    quizzes
+        .iter()
+        .filter(|quiz| !quiz.is_answered(&state))
+        .filter(|quiz| {
+            exercises
+                .iter()
+                .any(|e| e.name == quiz.after && e.looks_done(&state))
+        })
+        .collect()
+}
+
+pub fn announce(quiz: &Quiz) {
+    println!(
+        "Before you continue, answer the quiz {} with `rustlings quiz` or by typing 'quiz' in watch mode.",
+        style(&quiz.name).bold(),
+    );
+}
+
+// Ask the question of the quiz and record the answer.
+// Returns whether the answer was correct.
+pub fn ask(quiz: &Quiz, input: &mut impl BufRead) -> io::Result<bool> {
+    println!("\n{}\n", style(&quiz.question).bold());
+    for (ind, choice) in quiz.choices.iter().enumerate() {
+        println!("  {}) {choice}", ind + 1);
+    }
+    print!("\nYour answer: ");
+    io::stdout().flush()?;
+
+    let mut line = String::new();
+    input.read_line(&mut line)?;
+    let answer = match line.trim().parse::<usize>() {
+        Ok(answer) if (1..=quiz.choices.len()).contains(&answer) => answer,
+        _ => {
+            println!(
+                "Please answer with a number between 1 and {}.",
+                quiz.choices.len()
+            );
+            return Ok(false);
+        }
+    };
+
+    state_file::update(|state| {
+        state.quiz_answers.insert(quiz.name.clone(), answer);
+    });
+
+    if answer == quiz.answer {
+        success!("{} is the correct answer!", answer);
+        Ok(true)
+    } else {
+        warn!("{} is not the correct answer. Try again!", answer);
+        Ok(false)
+    }
+}
@@ -0,0 +1,179 @@
+use anyhow::Result;
+use std::fs;
+use std::thread;
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::exercise::{uses_not_done_marker, Exercise, Mode};
+use crate::state_file::{self, StateFile};
+use crate::undo;
+
+// The number of done exercises that are checked again at once by default
+pub const SAMPLE_SIZE: usize = 5;
+// How often the background check in watch mode looks for modified done exercises
+const BACKGROUND_INTERVAL: Duration = Duration::from_secs(30);
+
+// Clippy exercises share one Cargo project with the exercise being verified, so they are skipped.
+fn can_recheck(exercise: &Exercise, state: &StateFile) -> bool {
+    !matches!(exercise.mode, Mode::Clippy) && exercise.looks_done(state)
+}
+
+// Quietly verify done exercises again to find the ones that were broken after completing them,
+// e.g. while experimenting. The exercises that weren't checked for the longest time are checked first.
+// Returns the number of checked exercises and the broken ones.
+pub fn recheck(exercises: &[Exercise], sample: Option<usize>) -> (usize, Vec<&Exercise>) {
+    let state = StateFile::load().unwrap_or_default();
+    let mut done = exercises
+        .iter()
+        .filter(|e| can_recheck(e, &state))
+        .map(|e| {
+            let checked_at = state.exercises.get(&e.name).and_then(|r| r.checked_at);
+            (checked_at, e)
+        })
+        .collect::<Vec<_>>();
+    done.sort_by_key(|(checked_at, _)| *checked_at);
+
+    let sample = done
+        .into_iter()
+        .take(sample.unwrap_or(usize::MAX))
+        .map(|(_, e)| e)
+        .collect::<Vec<_>>();
+    let broken = check(&sample);
+    (sample.len(), broken)
+}
+
+// Verify the given exercises and record the results.
+// Returns the broken exercises.
+fn check<'a>(exercises: &[&'a Exercise]) -> Vec<&'a Exercise> {
+    let checked = exercises
+        .iter()
+        .map(|&e| {
+            let passes = e.compile().is_ok_and(|compiled| compiled.run().is_ok());
+            (e, passes)
+        })
+        .collect::<Vec<_>>();
+
+    state_file::update(|state| {
+        let now = state_file::now();
+        for (exercise, passes) in &checked {
+            let record = state.record(&exercise.name);
+            record.checked_at = Some(now);
+            record.regressed = !passes;
+        }
+    });
+
+    checked
+        .into_iter()
+        .filter(|(_, passes)| !passes)
+        .map(|(e, _)| e)
+        .collect()
+}
+
+// Done exercises whose file was modified after they were completed or checked the last time
+fn modified_since_check<'a>(exercises: &'a [Exercise], state: &StateFile) -> Vec<&'a Exercise> {
+    exercises
+        .iter()
+        .filter(|e| can_recheck(e, state))
+        .filter(|e| {
+            let record = state.exercises.get(&e.name);
+            let Some(last_check) = record.and_then(|r| r.checked_at.or(r.completed_at)) else {
+                return false;
+            };
+            fs::metadata(&e.path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+                .is_some_and(|modified| modified.as_secs() > last_check)
+        })
+        .collect()
+}
+
+// Periodically check the done exercises that were modified in the background
+// so that broken exercises are noticed while working on the current one.
+// Nothing is printed unless an exercise is broken.
+pub fn spawn_background_check(exercises: Vec<Exercise>) {
+    thread::spawn(move || loop {
+        thread::sleep(BACKGROUND_INTERVAL);
+        let state = StateFile::load().unwrap_or_default();
+        let modified = modified_since_check(&exercises, &state);
+        for exercise in check(&modified) {
+            warn!(
+                "{} was done but doesn't pass anymore. Type 'recheck' for details.",
+                exercise
+            );
+        }
+    });
+}
+
+// Whether a recheck found that the done exercise is broken
+pub fn is_regressed(exercise: &Exercise, state: &StateFile) -> bool {
+    state
+        .exercises
+        .get(&exercise.name)
+        .is_some_and(|record| record.regressed)
+        && exercise.looks_done(state)
+}
+
+// Add the "I AM NOT DONE" comment to the exercise again so that it is verified as pending.
+pub fn mark_pending(exercise: &Exercise) -> Result<()> {
+    undo::push(&format!("mark {exercise} as pending"), &[exercise])?;
+    // Without the comment, an exercise is done once it was verified, so that is forgotten.
+    if !uses_not_done_marker() {
+        state_file::update(|state| {
+            let record = state.record(&exercise.name);
+            record.completed_at = None;
+            record.regressed = false;
+        });
+        return Ok(());
+    }
+
+    let content = fs::read_to_string(&exercise.path)?;
+    fs::write(&exercise.path, format!("// I AM NOT DONE\n\n{content}"))?;
+    state_file::update(|state| state.record(&exercise.name).regressed = false);
+    Ok(())
+}
+
+pub fn report(checked: usize, broken: &[&Exercise], mark: bool) {
+    if broken.is_empty() {
+        success!("All {} checked exercises still pass.", checked);
+        return;
+    }
+
+    for exercise in broken {
+        if !mark {
+            warn!("{} was done but doesn't pass anymore.", exercise);
+        } else if let Err(e) = mark_pending(exercise) {
+            println!("Failed to mark {exercise} as pending: {e:#}");
+        } else {
+            warn!(
+                "{} doesn't pass anymore and was marked as pending.",
+                exercise
+            );
+        }
+    }
+    if !mark {
+        println!("Run `rustlings recheck --mark-pending` to solve them again.");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_modified_since_check() {
+        let exercises = [Exercise::for_test(
+            "finished",
+            "tests/fixture/state/finished_exercise.rs",
+            Mode::Compile,
+        )];
+
+        let mut state = StateFile::default();
+        assert!(modified_since_check(&exercises, &state).is_empty());
+
+        state.record("finished").completed_at = Some(0);
+        assert_eq!(modified_since_check(&exercises, &state).len(), 1);
+
+        state.record("finished").checked_at = Some(u64::MAX);
+        assert!(modified_since_check(&exercises, &state).is_empty());
+    }
+}
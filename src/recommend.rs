@@ -0,0 +1,162 @@
+use console::style;
+
+use crate::exercise::Exercise;
+use crate::state_file::{self, StateFile};
+
+// Topics with at least this many failed verifications per completed exercise
+// are recommended for review.
+const STRUGGLE_THRESHOLD: f64 = 3.0;
+
+#[derive(Debug)]
+pub enum Recommendation<'a> {
+    // Revisit a topic that the learner struggled with
+    Review {
+        topic: &'a str,
+        failures_per_exercise: f64,
+    },
+    // Continue with the next pending exercise
+    Next(&'a Exercise),
+    // All exercises are done and no topic needs a review
+    Done,
+}
+
+// Suggest what to do next.
+// A topic is only recommended for review once one of its exercises is due for review
+// so that the learner isn't sent back right after completing it.
+pub fn recommend<'a>(exercises: &'a [Exercise], state: &StateFile, now: u64) -> Recommendation<'a> {
+    let required = || exercises.iter().filter(|e| !e.optional);
+
+    let mut struggled: Option<(&str, f64)> = None;
+    for exercise in required() {
+        let topic = exercise.topic();
+        if topic.is_empty() || struggled.is_some_and(|(t, _)| t == topic) {
+            continue;
+        }
+
+        let mut completed = 0;
+        let mut failures = 0;
+        let mut due = false;
+        for record in required()
+            .filter(|e| e.topic() == topic)
+            .filter_map(|e| state.exercises.get(&e.name))
+            .filter(|record| record.completed_at.is_some())
+        {
+            completed += 1;
+            failures += record.failures;
+            due |= record
+                .review
+                .as_ref()
+                .is_some_and(|review| review.started_with_failures.is_none() && review.due <= now);
+        }
+        if completed == 0 || !due {
+            continue;
+        }
+
+        let failures_per_exercise = f64::from(failures) / f64::from(completed);
+        if failures_per_exercise >= STRUGGLE_THRESHOLD
+            && struggled.map_or(true, |(_, worst)| failures_per_exercise > worst)
+        {
+            struggled = Some((topic, failures_per_exercise));
+        }
+    }
+
+    if let Some((topic, failures_per_exercise)) = struggled {
+        return Recommendation::Review {
+            topic,
+            failures_per_exercise,
+        };
+    }
+
+    match required().find(|e| !e.looks_done(state)) {
+        Some(exercise) => Recommendation::Next(exercise),
+        None => Recommendation::Done,
+    }
+}
+
+pub fn print_recommendation(exercises: &[Exercise]) {
+    let state = StateFile::load().unwrap_or_default();
+    match recommend(exercises, &state, state_file::now()) {
+        Recommendation::Review {
+            topic,
+            failures_per_exercise,
+        } => {
+            println!(
+                "You had {failures_per_exercise:.1} failed attempts per exercise of the topic {} in the past.",
+                style(topic).bold()
+            );
+            println!("Practice it again with `rustlings challenge --topic {topic} --reset`.");
+        }
+        Recommendation::Next(exercise) => {
+            println!(
+                "Continue with the next exercise {}.",
+                style(exercise).bold()
+            );
+            println!(
+                "Solve it with `rustlings watch` or `rustlings run {}`.",
+                exercise.name
+            );
+        }
+        Recommendation::Done => {
+            println!("You completed all exercises and there is nothing to review right now.");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+    use crate::review::Review;
+
+    fn exercise(name: &str, path: &str) -> Exercise {
+        Exercise::for_test(name, path, Mode::Compile)
+    }
+
+    fn completed(state: &mut StateFile, name: &str, failures: u32, due: u64) {
+        let record = state.record(name);
+        record.completed_at = Some(0);
+        record.failures = failures;
+        record.review = Some(Review {
+            due,
+            ..Review::new(0)
+        });
+    }
+
+    #[test]
+    fn test_recommend_next() {
+        let exercises = [exercise(
+            "pending",
+            "tests/fixture/state/pending_exercise.rs",
+        )];
+        assert!(matches!(
+            recommend(&exercises, &StateFile::default(), 0),
+            Recommendation::Next(e) if e.name == "pending"
+        ));
+    }
+
+    #[test]
+    fn test_recommend_review() {
+        let exercises = [
+            exercise("vecs1", "exercises/05_vecs/vecs1.rs"),
+            exercise("vecs2", "exercises/05_vecs/vecs2.rs"),
+            exercise("enums1", "exercises/08_enums/enums1.rs"),
+        ];
+        let mut state = StateFile::default();
+        completed(&mut state, "vecs1", 5, 100);
+        completed(&mut state, "vecs2", 1, 200);
+        completed(&mut state, "enums1", 1, 100);
+
+        // The review isn't due yet.
+        assert!(matches!(
+            recommend(&exercises, &state, 50),
+            Recommendation::Next(e) if e.name == "vecs1"
+        ));
+        assert!(matches!(
+            recommend(&exercises, &state, 150),
+            Recommendation::Review {
+                topic: "vecs",
+                failures_per_exercise,
+            } if failures_per_exercise == 3.0
+        ));
+    }
+}
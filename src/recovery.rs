@@ -0,0 +1,151 @@
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use crate::exercise::Exercise;
+use crate::exit_code;
+use crate::pristine::{self, pristine};
+use crate::ui;
+
+// Guided fixes for a Rustlings directory that can't be used as it is, e.g. since Rustlings
+// was started in the wrong directory or files of the course were deleted by accident.
+
+const CLONE_COMMAND: &str = concat!(
+    "git clone -b ",
+    env!("CARGO_PKG_VERSION"),
+    " --depth 1 https://github.com/rust-lang/rustlings"
+);
+
+// Without a terminal, nobody might see the question, so the answer is no.
+// The question would also break the output of `--porcelain` and `--json`.
+fn confirm(question: &str) -> bool {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() || ui::machine_readable() {
+        return false;
+    }
+
+    print!("{question} [y/N] ");
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).is_ok() && matches!(answer.trim(), "y" | "Y" | "yes")
+}
+
+// The subdirectories of the current directory that contain a course, e.g. `rustlings/`
+// right after cloning it
+fn courses_in_subdirectories() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(".") else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join("info.toml").is_file())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+// The exercises of the course that is built into Rustlings are here, so its info.toml fits them.
+fn restore_embedded_info_toml() -> Option<String> {
+    if !pristine::embedded_paths().any(|path| Path::new(path).exists()) {
+        return None;
+    }
+    if !confirm("Restore info.toml from the copy built into Rustlings?") {
+        notice!("Restore it with `git checkout info.toml`, or run Rustlings in a terminal to restore the copy built into Rustlings.");
+        return None;
+    }
+    // A broken info.toml might contain changes that the user wants to keep.
+    if Path::new("info.toml").exists() {
+        if let Err(e) = fs::copy("info.toml", "info.toml.bak") {
+            notice!("Failed to back up info.toml to info.toml.bak: {e}");
+            return None;
+        }
+        notice!("Your info.toml was saved to info.toml.bak.");
+    }
+    match fs::write("info.toml", pristine::INFO_TOML) {
+        Ok(()) => {
+            notice!("Restored info.toml.\n");
+            Some(pristine::INFO_TOML.to_string())
+        }
+        Err(e) => {
+            notice!("Failed to write info.toml: {e}");
+            None
+        }
+    }
+}
+
+// info.toml isn't in the current directory. Returns its content if it could be restored.
+pub fn missing_info_toml() -> String {
+    if let Some(info_file) = restore_embedded_info_toml() {
+        return info_file;
+    }
+
+    match courses_in_subdirectories().as_slice() {
+        [] => {
+            notice!(
+                "The program must be run from the rustlings directory, which contains info.toml."
+            );
+            notice!("If you don't have the exercises yet, get them with:\n\n    {CLONE_COMMAND}\n    cd rustlings\n");
+        }
+        [dir] => notice!(
+            "The program must be run from the rustlings directory\nTry `cd {}/`!",
+            dir.display()
+        ),
+        dirs => {
+            notice!("The program must be run from the directory of a course. These directories contain one:");
+            for dir in dirs {
+                notice!("    {}", dir.display());
+            }
+        }
+    }
+    std::process::exit(exit_code::COURSE_ERROR);
+}
+
+// info.toml can't be parsed. Returns the restored content if the user wants to restore it.
+pub fn broken_info_toml(error: &str) -> String {
+    notice!("Failed to parse the info.toml file: {error}");
+    notice!("If you didn't edit it on purpose, it might have been changed by accident.");
+    restore_embedded_info_toml().unwrap_or_else(|| std::process::exit(exit_code::COURSE_ERROR))
+}
+
+// Offers to restore the files of exercises that were deleted, up to the whole `exercises` directory.
+pub fn restore_missing_exercises(exercises: &[Exercise]) {
+    let missing: Vec<&Exercise> = exercises.iter().filter(|e| !e.path.exists()).collect();
+    let Some(first) = missing.first() else {
+        return;
+    };
+    match missing.len() {
+        n if n == exercises.len() => notice!("The files of all exercises are missing."),
+        1 => notice!("The exercise file {first} is missing."),
+        n => notice!("{first} and {} other exercise files are missing.", n - 1),
+    }
+
+    let restorable: Vec<(&Exercise, String)> = missing
+        .iter()
+        .filter_map(|exercise| Some((*exercise, pristine(&exercise.path).ok()?)))
+        .collect();
+    if restorable.is_empty() {
+        notice!("There are no pristine copies of them to restore.\n");
+        return;
+    }
+    if !confirm(&format!(
+        "Restore {} of them from their pristine copies?",
+        restorable.len()
+    )) {
+        notice!("Restore them with `git checkout exercises`, or run Rustlings in a terminal to restore their pristine copies.\n");
+        return;
+    }
+
+    let mut restored = 0;
+    for (exercise, content) in restorable {
+        let result = match exercise.path.parent() {
+            Some(dir) => fs::create_dir_all(dir),
+            None => Ok(()),
+        }
+        .and_then(|()| fs::write(&exercise.path, content));
+        match result {
+            Ok(()) => restored += 1,
+            Err(e) => notice!("Failed to restore {exercise}: {e}"),
+        }
+    }
+    notice!("Restored {restored} exercises.\n");
+}
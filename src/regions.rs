@@ -0,0 +1,251 @@
+use anyhow::{bail, Context, Result};
+use quote::ToTokens;
+use std::collections::BTreeSet;
+use std::fs;
+use std::ops::Range;
+use syn::spanned::Spanned;
+use syn::{Attribute, Item};
+
+use crate::exercise::Exercise;
+use crate::pristine::pristine;
+use crate::undo;
+use crate::variants;
+
+// Parts of an exercise that learners must not change, e.g. the provided tests, are between
+// these comments. The comments are part of the region.
+const BEGIN_MARKER: &str = "// READ-ONLY";
+const END_MARKER: &str = "// END READ-ONLY";
+
+// The lines of the read-only regions. A region without an end reaches the end of the file.
+fn regions(lines: &[&str]) -> Vec<Range<usize>> {
+    let mut regions = Vec::new();
+    let mut start = None;
+    for (ind, line) in lines.iter().enumerate() {
+        match (line.trim(), start) {
+            (BEGIN_MARKER, None) => start = Some(ind),
+            (END_MARKER, Some(begin)) => {
+                regions.push(begin..ind + 1);
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some(begin) = start {
+        regions.push(begin..lines.len());
+    }
+    regions
+}
+
+// The `cfg` attributes of a file or a module, which apply to all items in it
+fn cfgs(attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg") || attr.path().is_ident("cfg_attr"))
+        .map(|attr| format!("{} ", attr.to_token_stream()))
+        .collect()
+}
+
+// The lines of the items in a file, starting at 0, and the items as tokens together with the
+// `cfg` attributes of the file and the modules around them. Code outside of the read-only
+// regions can change the items in them, e.g. `#[cfg(any())]` in front of the region leaves out
+// the module in it. None if the file doesn't parse, which the compiler reports anyway.
+fn items(source: &str) -> Option<Vec<(Range<usize>, String)>> {
+    fn collect(items: &[Item], context: &str, out: &mut Vec<(Range<usize>, String)>) {
+        for item in items {
+            let span = item.span();
+            let lines = span.start().line - 1..span.end().line;
+            out.push((lines, format!("{context}{}", item.to_token_stream())));
+            if let Item::Mod(module) = item {
+                if let Some((_, content)) = &module.content {
+                    collect(content, &format!("{context}{}", cfgs(&module.attrs)), out);
+                }
+            }
+        }
+    }
+
+    let file = syn::parse_file(source).ok()?;
+    let mut out = Vec::new();
+    collect(&file.items, &cfgs(&file.attrs), &mut out);
+    Some(out)
+}
+
+// The read-only regions of the pristine exercise and of the learner's version
+struct Comparison {
+    pristine: String,
+    current: String,
+}
+
+impl Comparison {
+    fn load(exercise: &Exercise) -> Option<Self> {
+        let pristine = pristine(&exercise.path).ok()?;
+        if !pristine.contains(BEGIN_MARKER) {
+            return None;
+        }
+        Some(Self {
+            pristine: variants::instantiate(exercise, &pristine),
+            current: fs::read_to_string(&exercise.path).ok()?,
+        })
+    }
+
+    // The line numbers of the modified regions in the learner's version, starting at 1.
+    // Regions whose comments were removed are at the line of the pristine exercise.
+    // Besides the text, the items in each region have to be compiled like in the pristine
+    // exercise, so that e.g. a copy of a region in a comment doesn't count.
+    fn modified(&self) -> Vec<usize> {
+        let pristine: Vec<&str> = self.pristine.lines().collect();
+        let current: Vec<&str> = self.current.lines().collect();
+        let current_regions = regions(&current);
+        let pristine_items = items(&self.pristine).unwrap_or_default();
+        let current_items: BTreeSet<String> = items(&self.current)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(_, item)| item)
+            .collect();
+        let items_changed = |region: &Range<usize>| {
+            pristine_items.iter().any(|(lines, item)| {
+                region.start <= lines.start
+                    && lines.end <= region.end
+                    && !current_items.contains(item)
+            })
+        };
+        regions(&pristine)
+            .into_iter()
+            .enumerate()
+            .filter_map(|(ind, region)| match current_regions.get(ind) {
+                Some(current_region)
+                    if current[current_region.clone()] == pristine[region.clone()]
+                        && !items_changed(&region) =>
+                {
+                    None
+                }
+                Some(current_region) => Some(current_region.start + 1),
+                None => Some(region.start + 1),
+            })
+            .collect()
+    }
+}
+
+// A message for each read-only region of the exercise that was modified
+pub fn check(exercise: &Exercise) -> Option<String> {
+    let modified = Comparison::load(exercise)?.modified();
+    let lines: Vec<String> = modified.iter().map(usize::to_string).collect();
+    let regions = match lines.as_slice() {
+        [] => return None,
+        [line] => format!("The read-only region at line {line}"),
+        [rest @ .., last] => format!(
+            "The read-only regions at lines {} and {last}",
+            rest.join(", ")
+        ),
+    };
+    Some(format!(
+        "{regions} of {exercise} must not be changed.\nType 'revert' in watch mode or run `rustlings revert {}` to restore {}.\n",
+        exercise.name,
+        if modified.len() == 1 { "it" } else { "them" },
+    ))
+}
+
+// Restore the modified read-only regions and keep the rest of the learner's changes.
+// Returns the number of restored regions.
+pub fn revert(exercise: &Exercise) -> Result<usize> {
+    let Some(comparison) = Comparison::load(exercise) else {
+        bail!("{exercise} doesn't have read-only regions");
+    };
+    let modified = comparison.modified();
+    if modified.is_empty() {
+        return Ok(0);
+    }
+
+    let pristine: Vec<&str> = comparison.pristine.lines().collect();
+    let current: Vec<&str> = comparison.current.lines().collect();
+    let (pristine_regions, current_regions) = (regions(&pristine), regions(&current));
+    if current_regions.len() < pristine_regions.len() {
+        bail!("The comments around the read-only regions of {exercise} were removed, so they can't be found. Reset the exercise with `rustlings reset {}` instead", exercise.name);
+    }
+
+    let mut restored: Vec<&str> = Vec::with_capacity(current.len());
+    let mut end = 0;
+    for (region, current_region) in pristine_regions.iter().zip(&current_regions) {
+        restored.extend(&current[end..current_region.start]);
+        restored.extend(&pristine[region.clone()]);
+        end = current_region.end;
+    }
+    restored.extend(&current[end..]);
+
+    let mut content = restored.join("\n");
+    content.push('\n');
+    let reverted = Comparison {
+        pristine: comparison.pristine.clone(),
+        current: content.clone(),
+    };
+    if !reverted.modified().is_empty() {
+        bail!("The code around the read-only regions of {exercise} changes them, e.g. with a `#[cfg(...)]` attribute, so restoring them isn't enough. Reset the exercise with `rustlings reset {}` instead", exercise.name);
+    }
+
+    undo::push(
+        &format!("revert the read-only regions of {exercise}"),
+        &[exercise],
+    )
+    .context("Not reverting because the current version couldn't be saved for undo")?;
+    fs::write(&exercise.path, content).with_context(|| format!("Failed to write {exercise}"))?;
+    Ok(modified.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const PRISTINE: &str = "fn add(a: i32, b: i32) -> i32 {
+    todo!()
+}
+
+// READ-ONLY
+#[test]
+fn adds() {
+    assert_eq!(add(1, 2), 3);
+}
+// END READ-ONLY
+";
+
+    #[test]
+    fn test_regions() {
+        let lines: Vec<&str> = PRISTINE.lines().collect();
+        assert_eq!(regions(&lines), vec![4..10]);
+        assert_eq!(regions(&lines[..6]), vec![4..6]);
+        assert!(regions(&lines[..3]).is_empty());
+    }
+
+    #[test]
+    fn test_modified() {
+        let comparison = |current: &str| Comparison {
+            pristine: PRISTINE.to_string(),
+            current: current.to_string(),
+        };
+        let solved = PRISTINE.replace("todo!()", "a + b");
+        assert!(comparison(&solved).modified().is_empty());
+        let moved = solved.replace("a + b", "// Add them\n    a + b");
+        assert!(comparison(&moved).modified().is_empty());
+
+        let tampered = solved.replace("3);", "4);");
+        assert_eq!(comparison(&tampered).modified(), [5]);
+        let removed = &solved[..solved.find("// READ-ONLY").unwrap()];
+        assert_eq!(comparison(removed).modified(), [5]);
+    }
+
+    #[test]
+    fn test_modified_items() {
+        let comparison = |current: &str| Comparison {
+            pristine: PRISTINE.to_string(),
+            current: current.to_string(),
+        };
+        let solved = PRISTINE.replace("todo!()", "a + b");
+        // The region is unchanged, but the test in it isn't compiled anymore.
+        let left_out = solved.replace("// READ-ONLY", "#[cfg(any())]\n// READ-ONLY");
+        assert_eq!(comparison(&left_out).modified(), [6]);
+        let region = &solved[solved.find("// READ-ONLY").unwrap()..];
+        let commented = format!(
+            "{}/*\n{region}*/\n",
+            &solved[..solved.find("// READ-ONLY").unwrap()]
+        );
+        assert_eq!(comparison(&commented).modified(), [6]);
+    }
+}
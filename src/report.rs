@@ -0,0 +1,210 @@
+use std::fmt::Write;
+
+use crate::exercise::Exercise;
+use crate::export::{escape, ExportFormat};
+use crate::graph::topics;
+use crate::history::format_timestamp;
+use crate::state_file::{self, StateFile};
+use crate::stats::format_duration;
+
+// A table of the report with a header row
+struct Table {
+    header: Vec<&'static str>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    fn markdown(&self) -> String {
+        let mut out = format!("| {} |\n", self.header.join(" | "));
+        let _ = writeln!(out, "|{}", " --- |".repeat(self.header.len()));
+        for row in &self.rows {
+            let _ = writeln!(out, "| {} |", row.join(" | "));
+        }
+        out
+    }
+
+    fn html(&self) -> String {
+        let mut out = String::from("<table>\n<tr>");
+        for cell in &self.header {
+            let _ = write!(out, "<th>{}</th>", escape(cell));
+        }
+        out.push_str("</tr>\n");
+        for row in &self.rows {
+            out.push_str("<tr>");
+            for cell in row {
+                let _ = write!(out, "<td>{}</td>", escape(cell));
+            }
+            out.push_str("</tr>\n");
+        }
+        out.push_str("</table>\n");
+        out
+    }
+}
+
+fn percentage(done: usize, total: usize) -> String {
+    if total == 0 {
+        return String::from("-");
+    }
+    format!("{:.0} %", done as f64 * 100.0 / total as f64)
+}
+
+fn duration(seconds: u64) -> String {
+    if seconds == 0 {
+        return String::from("-");
+    }
+    format_duration(seconds)
+}
+
+// The progress per topic and per exercise
+fn tables(exercises: &[Exercise], state: &StateFile) -> (Vec<String>, Table, Table) {
+    let record = |exercise: &Exercise| state.exercises.get(&exercise.name).cloned();
+    let required = exercises.iter().filter(|e| !e.optional).collect::<Vec<_>>();
+    let done = required.iter().filter(|e| e.looks_done(state)).count();
+    let records = exercises.iter().filter_map(record).collect::<Vec<_>>();
+    let summary = vec![
+        format!(
+            "Exercises done: {done} / {} ({})",
+            required.len(),
+            percentage(done, required.len())
+        ),
+        format!(
+            "Time spent: {}",
+            format_duration(records.iter().map(|r| r.seconds_spent).sum())
+        ),
+        format!(
+            "Failed verifications: {}",
+            records.iter().map(|r| r.failures).sum::<u32>()
+        ),
+        format!(
+            "Hints viewed: {}",
+            records.iter().map(|r| r.hints_viewed).sum::<u32>()
+        ),
+    ];
+
+    let mut topic_table = Table {
+        header: vec!["Topic", "Done", "Completion", "Time spent", "Hints viewed"],
+        rows: Vec::new(),
+    };
+    for (topic, topic_exercises) in topics(exercises) {
+        let required = topic_exercises
+            .iter()
+            .filter(|e| !e.optional)
+            .collect::<Vec<_>>();
+        let done = required.iter().filter(|e| e.looks_done(state)).count();
+        let records = topic_exercises
+            .iter()
+            .filter_map(|e| record(e))
+            .collect::<Vec<_>>();
+        topic_table.rows.push(vec![
+            String::from(if topic.is_empty() { "other" } else { topic }),
+            format!("{done} / {}", required.len()),
+            percentage(done, required.len()),
+            duration(records.iter().map(|r| r.seconds_spent).sum()),
+            records
+                .iter()
+                .map(|r| r.hints_viewed)
+                .sum::<u32>()
+                .to_string(),
+        ]);
+    }
+
+    let mut exercise_table = Table {
+        header: vec![
+            "Exercise",
+            "Status",
+            "Time spent",
+            "Failures",
+            "Hints viewed",
+            "Completed at",
+        ],
+        rows: Vec::new(),
+    };
+    for exercise in exercises {
+        let record = record(exercise).unwrap_or_default();
+        let status = match (exercise.looks_done(state), exercise.optional) {
+            (true, _) => "done",
+            (false, true) => "optional",
+            (false, false) => "pending",
+        };
+        exercise_table.rows.push(vec![
+            exercise.name.clone(),
+            String::from(status),
+            duration(record.seconds_spent),
+            record.failures.to_string(),
+            record.hints_viewed.to_string(),
+            record
+                .completed_at
+                .map_or_else(|| String::from("-"), format_timestamp),
+        ]);
+    }
+
+    (summary, topic_table, exercise_table)
+}
+
+// Render the progress of the learner, e.g. to submit it with homework.
+pub fn report(exercises: &[Exercise], title: &str, format: ExportFormat) -> String {
+    let state = StateFile::load().unwrap_or_default();
+    let (summary, topic_table, exercise_table) = tables(exercises, &state);
+    let generated = format!("Generated at {} UTC.", format_timestamp(state_file::now()));
+
+    match format {
+        ExportFormat::Markdown => {
+            let mut out = format!("# Progress report: {title}\n\n{generated}\n\n");
+            for line in summary {
+                let _ = writeln!(out, "- {line}");
+            }
+            let _ = write!(
+                out,
+                "\n## Topics\n\n{}\n## Exercises\n\n{}",
+                topic_table.markdown(),
+                exercise_table.markdown()
+            );
+            out
+        }
+        ExportFormat::Html => {
+            let title = escape(&format!("Progress report: {title}"));
+            let mut out = format!(
+                "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+                <style>table {{ border-collapse: collapse; }} th, td {{ border: 1px solid #ccc; padding: 0.3em 0.6em; }}</style>\n\
+                </head>\n<body>\n<h1>{title}</h1>\n<p>{generated}</p>\n<ul>\n"
+            );
+            for line in summary {
+                let _ = writeln!(out, "<li>{}</li>", escape(&line));
+            }
+            let _ = write!(
+                out,
+                "</ul>\n<h2>Topics</h2>\n{}<h2>Exercises</h2>\n{}</body>\n</html>\n",
+                topic_table.html(),
+                exercise_table.html()
+            );
+            out
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_table() {
+        let table = Table {
+            header: vec!["Topic", "Done"],
+            rows: vec![vec![String::from("vecs"), String::from("1 / 2")]],
+        };
+        assert_eq!(
+            table.markdown(),
+            "| Topic | Done |\n| --- | --- |\n| vecs | 1 / 2 |\n"
+        );
+        assert_eq!(
+            table.html(),
+            "<table>\n<tr><th>Topic</th><th>Done</th></tr>\n<tr><td>vecs</td><td>1 / 2</td></tr>\n</table>\n"
+        );
+    }
+
+    #[test]
+    fn test_percentage() {
+        assert_eq!(percentage(1, 3), "33 %");
+        assert_eq!(percentage(0, 0), "-");
+    }
+}
@@ -0,0 +1,157 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::exercise::Exercise;
+use crate::run::reset;
+use crate::state_file::{self, StateFile};
+
+const DAY: u64 = 24 * 60 * 60;
+
+// A simplified SM-2 schedule for reviewing a completed exercise.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Review {
+    // Unix timestamp of when the next review is due
+    pub due: u64,
+    // The number of days between the last review and the due date
+    pub interval_days: f64,
+    // How easy the exercise was in past reviews. Higher values mean longer intervals.
+    pub ease: f64,
+    // The number of successful reviews in a row
+    pub repetitions: u32,
+    // The failure count of the exercise when the current review started.
+    // `None` if the exercise isn't being reviewed right now.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_with_failures: Option<u32>,
+}
+
+impl Review {
+    // The first review is due one day after completing the exercise.
+    pub fn new(now: u64) -> Self {
+        Self {
+            due: now + DAY,
+            interval_days: 1.0,
+            ease: 2.5,
+            repetitions: 0,
+            started_with_failures: None,
+        }
+    }
+
+    // Grade a finished review by the number of failed verifications during it
+    // and schedule the next one.
+    pub fn finish(&mut self, failures: u32, now: u64) {
+        let Some(start) = self.started_with_failures.take() else {
+            return;
+        };
+
+        let quality: u32 = match failures.saturating_sub(start) {
+            0 => 5,
+            1 => 4,
+            2 => 3,
+            _ => 2,
+        };
+        let penalty = f64::from(5 - quality);
+        self.ease = (self.ease + 0.1 - penalty * (0.08 + penalty * 0.02)).max(1.3);
+
+        if quality < 3 {
+            self.repetitions = 0;
+            self.interval_days = 1.0;
+        } else {
+            self.repetitions += 1;
+            self.interval_days = if self.repetitions == 1 {
+                6.0
+            } else {
+                (self.interval_days * self.ease).round()
+            };
+        }
+        self.due = now + (self.interval_days * DAY as f64) as u64;
+    }
+}
+
+// Reset the completed exercise that is the most overdue for review.
+// If `list` is set, the exercises due for review are only listed.
+pub fn review(exercises: &[Exercise], list: bool) -> Result<()> {
+    let state = StateFile::load()?;
+    let now = state_file::now();
+
+    let mut due = exercises
+        .iter()
+        .filter_map(|exercise| {
+            let review = state.exercises.get(&exercise.name)?.review.as_ref()?;
+            (review.started_with_failures.is_none() && review.due <= now)
+                .then_some((review.due, exercise))
+        })
+        .collect::<Vec<_>>();
+    due.sort_by_key(|(due, _)| *due);
+
+    let Some((_, next)) = due.first() else {
+        println!("There are no exercises due for review. Come back later!");
+        return Ok(());
+    };
+
+    if list {
+        for (due, exercise) in &due {
+            println!("{:<17}\tdue for {} days", exercise.name, (now - due) / DAY);
+        }
+        return Ok(());
+    }
+
+    if reset(next).is_err() {
+        anyhow::bail!("Failed to reset {next} for the review");
+    }
+    state_file::update(|state| {
+        let record = state.record(&next.name);
+        let failures = record.failures;
+        if let Some(review) = &mut record.review {
+            review.started_with_failures = Some(failures);
+        }
+    });
+
+    println!("{next} was reset for the review. Solve it again with `rustlings watch` or `rustlings run {}`.", next.name);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn started_review() -> Review {
+        Review {
+            started_with_failures: Some(3),
+            ..Review::new(0)
+        }
+    }
+
+    #[test]
+    fn test_review_intervals_grow() {
+        let mut review = started_review();
+        review.finish(3, 0);
+        assert_eq!(review.repetitions, 1);
+        assert_eq!(review.interval_days, 6.0);
+        assert_eq!(review.due, 6 * DAY);
+        assert_eq!(review.started_with_failures, None);
+
+        review.started_with_failures = Some(3);
+        review.finish(3, 0);
+        assert_eq!(review.repetitions, 2);
+        assert!(review.interval_days > 6.0);
+    }
+
+    #[test]
+    fn test_review_with_many_failures_starts_over() {
+        let mut review = started_review();
+        review.finish(3, 0);
+        review.started_with_failures = Some(3);
+        review.finish(7, 0);
+        assert_eq!(review.repetitions, 0);
+        assert_eq!(review.interval_days, 1.0);
+        assert!(review.ease < 2.5);
+    }
+
+    #[test]
+    fn test_finish_without_started_review() {
+        let mut review = Review::new(0);
+        review.finish(10, 100);
+        assert_eq!(review, Review::new(0));
+    }
+}
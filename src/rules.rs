@@ -0,0 +1,489 @@
+use proc_macro2::Span;
+use serde::Deserialize;
+use std::fmt::{self, Display, Formatter};
+use std::fs;
+use syn::punctuated::Punctuated;
+use syn::visit::{self, Visit};
+use syn::{Attribute, Expr, Ident, Token};
+
+use crate::exercise::Exercise;
+
+// Constructs that the code of an exercise must or must not use, so that exercises can practice
+// a construct instead of only checking the behavior. This is deserialized from the `rules`
+// table of an exercise, e.g. `rules = { require = ["match", "trait-impl"], forbid = ["unwrap"] }`.
+#[derive(Deserialize, Clone, Default, PartialEq, Eq, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Rules {
+    #[serde(default)]
+    pub require: Vec<Construct>,
+    #[serde(default)]
+    pub forbid: Vec<Construct>,
+}
+
+impl Rules {
+    pub fn is_empty(&self) -> bool {
+        self.require.is_empty() && self.forbid.is_empty()
+    }
+}
+
+// A construct of a rule by its name in info.toml. Other names are calls of functions or methods
+// with that name, e.g. `unwrap`, and names ending with `!` are macros, e.g. `println!`.
+#[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(try_from = "String")]
+pub enum Construct {
+    Match,
+    IfLet,
+    WhileLet,
+    ForLoop,
+    Loop,
+    Closure,
+    QuestionMark,
+    Return,
+    Unsafe,
+    Impl,
+    TraitImpl,
+    Trait,
+    Struct,
+    Enum,
+    Macro(String),
+    Call(String),
+}
+
+const NAMES: &[(&str, Construct)] = &[
+    ("match", Construct::Match),
+    ("if-let", Construct::IfLet),
+    ("while-let", Construct::WhileLet),
+    ("for", Construct::ForLoop),
+    ("loop", Construct::Loop),
+    ("closure", Construct::Closure),
+    ("?", Construct::QuestionMark),
+    ("return", Construct::Return),
+    ("unsafe", Construct::Unsafe),
+    ("impl", Construct::Impl),
+    ("trait-impl", Construct::TraitImpl),
+    ("trait", Construct::Trait),
+    ("struct", Construct::Struct),
+    ("enum", Construct::Enum),
+];
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+impl TryFrom<String> for Construct {
+    type Error = String;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        if let Some((_, construct)) = NAMES.iter().find(|(known, _)| *known == name) {
+            return Ok(construct.clone());
+        }
+        match name.strip_suffix('!') {
+            Some(name) if is_identifier(name) => Ok(Self::Macro(name.to_string())),
+            None if is_identifier(&name) => Ok(Self::Call(name)),
+            _ => {
+                let known: Vec<&str> = NAMES.iter().map(|(known, _)| *known).collect();
+                Err(format!(
+                    "Unknown construct `{name}`. Use one of {}, the name of a function or method, or the name of a macro followed by `!`",
+                    known.join(", ")
+                ))
+            }
+        }
+    }
+}
+
+impl Construct {
+    fn verb(&self) -> &'static str {
+        match self {
+            Self::Call(_) => "call",
+            Self::Impl | Self::TraitImpl | Self::Trait | Self::Struct | Self::Enum => "define",
+            _ => "use",
+        }
+    }
+}
+
+impl Display for Construct {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Match => write!(f, "a `match` expression"),
+            Self::IfLet => write!(f, "`if let`"),
+            Self::WhileLet => write!(f, "`while let`"),
+            Self::ForLoop => write!(f, "a `for` loop"),
+            Self::Loop => write!(f, "`loop`"),
+            Self::Closure => write!(f, "a closure"),
+            Self::QuestionMark => write!(f, "the `?` operator"),
+            Self::Return => write!(f, "`return`"),
+            Self::Unsafe => write!(f, "an `unsafe` block"),
+            Self::Impl => write!(f, "an `impl` block"),
+            Self::TraitImpl => write!(f, "a trait implementation"),
+            Self::Trait => write!(f, "a trait"),
+            Self::Struct => write!(f, "a struct"),
+            Self::Enum => write!(f, "an enum"),
+            Self::Macro(name) => write!(f, "`{name}!`"),
+            Self::Call(name) => write!(f, "`{name}`"),
+        }
+    }
+}
+
+// `#[test]` functions and `#[cfg(test)]` modules, which the rules don't apply to
+fn is_test(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path().is_ident("test")
+            || (attr.path().is_ident("cfg")
+                && attr.parse_args::<Ident>().is_ok_and(|arg| arg == "test"))
+    })
+}
+
+// The constructs in the parsed code and their lines
+#[derive(Default)]
+struct Finder {
+    found: Vec<(Construct, usize)>,
+}
+
+impl Finder {
+    fn add(&mut self, construct: Construct, span: Span) {
+        self.found.push((construct, span.start().line));
+    }
+
+    // A path with one segment that is called or passed to a call, e.g. `area` in
+    // `area(&r)` or `.map(area)`. Longer paths are found by `visit_expr_path`.
+    fn add_function(&mut self, expr: &Expr) {
+        if let Expr::Path(node) = expr {
+            if let Some(ident) = node.path.get_ident() {
+                self.add(Construct::Call(ident.to_string()), ident.span());
+            }
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for Finder {
+    fn visit_item_mod(&mut self, node: &'ast syn::ItemMod) {
+        if !is_test(&node.attrs) {
+            visit::visit_item_mod(self, node);
+        }
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        if !is_test(&node.attrs) {
+            visit::visit_item_fn(self, node);
+        }
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast syn::ItemImpl) {
+        self.add(Construct::Impl, node.impl_token.span);
+        if node.trait_.is_some() {
+            self.add(Construct::TraitImpl, node.impl_token.span);
+        }
+        visit::visit_item_impl(self, node);
+    }
+
+    fn visit_item_trait(&mut self, node: &'ast syn::ItemTrait) {
+        self.add(Construct::Trait, node.trait_token.span);
+        visit::visit_item_trait(self, node);
+    }
+
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        self.add(Construct::Struct, node.struct_token.span);
+        visit::visit_item_struct(self, node);
+    }
+
+    fn visit_item_enum(&mut self, node: &'ast syn::ItemEnum) {
+        self.add(Construct::Enum, node.enum_token.span);
+        visit::visit_item_enum(self, node);
+    }
+
+    fn visit_expr_match(&mut self, node: &'ast syn::ExprMatch) {
+        self.add(Construct::Match, node.match_token.span);
+        visit::visit_expr_match(self, node);
+    }
+
+    fn visit_expr_if(&mut self, node: &'ast syn::ExprIf) {
+        if matches!(*node.cond, Expr::Let(_)) {
+            self.add(Construct::IfLet, node.if_token.span);
+        }
+        visit::visit_expr_if(self, node);
+    }
+
+    fn visit_expr_while(&mut self, node: &'ast syn::ExprWhile) {
+        if matches!(*node.cond, Expr::Let(_)) {
+            self.add(Construct::WhileLet, node.while_token.span);
+        }
+        visit::visit_expr_while(self, node);
+    }
+
+    fn visit_expr_for_loop(&mut self, node: &'ast syn::ExprForLoop) {
+        self.add(Construct::ForLoop, node.for_token.span);
+        visit::visit_expr_for_loop(self, node);
+    }
+
+    fn visit_expr_loop(&mut self, node: &'ast syn::ExprLoop) {
+        self.add(Construct::Loop, node.loop_token.span);
+        visit::visit_expr_loop(self, node);
+    }
+
+    fn visit_expr_closure(&mut self, node: &'ast syn::ExprClosure) {
+        self.add(Construct::Closure, node.or1_token.spans[0]);
+        visit::visit_expr_closure(self, node);
+    }
+
+    fn visit_expr_try(&mut self, node: &'ast syn::ExprTry) {
+        self.add(Construct::QuestionMark, node.question_token.spans[0]);
+        visit::visit_expr_try(self, node);
+    }
+
+    fn visit_expr_return(&mut self, node: &'ast syn::ExprReturn) {
+        self.add(Construct::Return, node.return_token.span);
+        visit::visit_expr_return(self, node);
+    }
+
+    fn visit_expr_unsafe(&mut self, node: &'ast syn::ExprUnsafe) {
+        self.add(Construct::Unsafe, node.unsafe_token.span);
+        visit::visit_expr_unsafe(self, node);
+    }
+
+    fn visit_expr_method_call(&mut self, node: &'ast syn::ExprMethodCall) {
+        self.add(Construct::Call(node.method.to_string()), node.method.span());
+        for arg in &node.args {
+            self.add_function(arg);
+        }
+        visit::visit_expr_method_call(self, node);
+    }
+
+    // Functions are also found when they are passed without calling them, e.g. `.map(area)`.
+    fn visit_expr_call(&mut self, node: &'ast syn::ExprCall) {
+        self.add_function(&node.func);
+        for arg in &node.args {
+            self.add_function(arg);
+        }
+        visit::visit_expr_call(self, node);
+    }
+
+    // A path like `Option::unwrap` names a function wherever it is used. A single identifier
+    // elsewhere may be a variable with the name of a function, like `len` in `len + 1`.
+    fn visit_expr_path(&mut self, node: &'ast syn::ExprPath) {
+        if node.path.segments.len() > 1 {
+            if let Some(segment) = node.path.segments.last() {
+                self.add(
+                    Construct::Call(segment.ident.to_string()),
+                    segment.ident.span(),
+                );
+            }
+        }
+        visit::visit_expr_path(self, node);
+    }
+
+    // The body of a `macro_rules!` definition is only code once the macro is used, so it is
+    // skipped. The constructs in it aren't found, see CONTRIBUTING.md.
+    fn visit_item_macro(&mut self, node: &'ast syn::ItemMacro) {
+        if node.mac.path.is_ident("macro_rules") {
+            self.add(
+                Construct::Macro(String::from("macro_rules")),
+                node.mac.bang_token.span,
+            );
+            return;
+        }
+        visit::visit_item_macro(self, node);
+    }
+
+    // The arguments of macros like `println!` and `assert_eq!` are expressions, so the
+    // constructs in them count as well.
+    fn visit_macro(&mut self, node: &'ast syn::Macro) {
+        if let Some(segment) = node.path.segments.last() {
+            self.add(
+                Construct::Macro(segment.ident.to_string()),
+                segment.ident.span(),
+            );
+        }
+        if let Ok(args) = node.parse_body_with(Punctuated::<Expr, Token![,]>::parse_terminated) {
+            for arg in &args {
+                self.visit_expr(arg);
+            }
+        }
+        visit::visit_macro(self, node);
+    }
+}
+
+// The broken rules with the lines of the forbidden constructs
+fn broken_rules(rules: &Rules, file: &syn::File) -> Vec<String> {
+    let mut finder = Finder::default();
+    finder.visit_file(file);
+
+    let mut broken = Vec::new();
+    for construct in &rules.require {
+        if !finder.found.iter().any(|(found, _)| found == construct) {
+            broken.push(format!("It must {} {construct}.", construct.verb()));
+        }
+    }
+    let mut forbidden: Vec<&(Construct, usize)> = finder
+        .found
+        .iter()
+        .filter(|(found, _)| rules.forbid.contains(found))
+        .collect();
+    forbidden.sort_by_key(|(_, line)| *line);
+    forbidden.dedup();
+    for (construct, line) in forbidden {
+        broken.push(format!(
+            "line {line}: It must not {} {construct}.",
+            construct.verb()
+        ));
+    }
+    broken
+}
+
+// A message for the rules of the exercise that its code breaks. Exercises that don't parse are
+// left to the compiler.
+pub fn check(exercise: &Exercise) -> Option<String> {
+    if exercise.rules.is_empty() {
+        return None;
+    }
+    let source = fs::read_to_string(&exercise.path).ok()?;
+    let file = syn::parse_file(&source).ok()?;
+    let broken = broken_rules(&exercise.rules, &file);
+    if broken.is_empty() {
+        return None;
+    }
+
+    let mut message =
+        format!("{exercise} compiles, but it doesn't follow the rules of the exercise:\n");
+    for rule in broken {
+        message.push_str(&format!("  {rule}\n"));
+    }
+    Some(message)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SOURCE: &str = "enum Shape {
+    Circle(f64),
+    Square(f64),
+}
+
+fn area(shape: &Shape) -> f64 {
+    match shape {
+        Shape::Circle(r) => 3.14 * r * r,
+        Shape::Square(side) => side * side,
+    }
+}
+
+fn main() {
+    let input = \"2.5\".parse::<f64>().unwrap();
+    println!(\"{}\", area(&Shape::Square(input.abs())));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn squares() {
+        assert_eq!(area(&Shape::Square(2.0)), 4.0);
+        let _ = \"1\".parse::<i32>().unwrap();
+    }
+}
+";
+
+    fn rules(require: &[&str], forbid: &[&str]) -> Rules {
+        let constructs = |names: &[&str]| {
+            names
+                .iter()
+                .map(|name| Construct::try_from(name.to_string()).unwrap())
+                .collect()
+        };
+        Rules {
+            require: constructs(require),
+            forbid: constructs(forbid),
+        }
+    }
+
+    #[test]
+    fn test_construct_names() {
+        let construct = |name: &str| Construct::try_from(name.to_string());
+        assert_eq!(construct("if-let"), Ok(Construct::IfLet));
+        assert_eq!(
+            construct("unwrap"),
+            Ok(Construct::Call(String::from("unwrap")))
+        );
+        assert_eq!(construct("vec!"), Ok(Construct::Macro(String::from("vec"))));
+        assert!(construct("if let").is_err());
+        assert!(construct("!").is_err());
+    }
+
+    #[test]
+    fn test_broken_rules() {
+        let file = syn::parse_file(SOURCE).unwrap();
+        assert!(broken_rules(&rules(&["match", "enum", "abs"], &["for"]), &file).is_empty());
+        assert_eq!(
+            broken_rules(&rules(&["trait-impl"], &["unwrap", "println!"]), &file),
+            [
+                "It must define a trait implementation.",
+                "line 14: It must not call `unwrap`.",
+                "line 15: It must not use `println!`."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_functions_as_values_and_macro_rules() {
+        let file = syn::parse_file(
+            "macro_rules! first {
+    ($v:expr) => {
+        $v.first().copied().unwrap()
+    };
+}
+
+fn main() {
+    let numbers = vec![Some(1), Some(2)];
+    let numbers: Vec<i32> = numbers.into_iter().map(Option::unwrap).collect();
+    println!(\"{}\", first!(numbers));
+}
+",
+        )
+        .unwrap();
+        assert_eq!(
+            broken_rules(&rules(&[], &["unwrap", "macro_rules!"]), &file),
+            [
+                "line 1: It must not use `macro_rules!`.",
+                "line 9: It must not call `unwrap`."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_variables_named_like_functions() {
+        let file = syn::parse_file(
+            "fn total(v: &[i32]) -> usize {
+    let len = v.len();
+    len + 1
+}
+
+fn main() {
+    let words = vec![\"a\"];
+    let lens: Vec<usize> = words.iter().map(str::len).collect();
+    println!(\"{} {:?}\", total(&[1]), lens);
+}
+",
+        )
+        .unwrap();
+        assert_eq!(
+            broken_rules(&rules(&[], &["len"]), &file),
+            [
+                "line 2: It must not call `len`.",
+                "line 8: It must not call `len`."
+            ]
+        );
+        assert!(broken_rules(&rules(&[], &["lens"]), &file).is_empty());
+    }
+
+    #[test]
+    fn test_macro_arguments_and_tests() {
+        let file = syn::parse_file(SOURCE).unwrap();
+        // `area` is only called in `println!` in `main` outside of the tests.
+        assert_eq!(
+            broken_rules(&rules(&[], &["area"]), &file),
+            ["line 15: It must not call `area`."]
+        );
+    }
+}
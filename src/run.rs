@@ -1,42 +1,240 @@
-use std::process::Command;
-use std::time::Duration;
+use anyhow::{Context, Result};
+use std::fs;
+use std::time::{Duration, Instant};
 
-use crate::exercise::{Exercise, Mode};
+use crate::backup::backup;
+use crate::diff;
+use crate::exercise::{for_each_in_parallel, Exercise, ExerciseOutput, Mode};
+use crate::exit_code::{self, Failure};
+use crate::explain::record_error_codes;
+use crate::history::{self, Outcome};
+use crate::json;
+use crate::porcelain;
+use crate::pristine::pristine;
+use crate::theme;
+use crate::ui;
+use crate::undo;
+use crate::variants;
 use crate::verify::test;
-use indicatif::ProgressBar;
+
+// The unchanged lines shown around the changes of an exercise
+const DIFF_CONTEXT: usize = 3;
 
 // Invoke the rust compiler on the path of the given exercise,
 // and run the ensuing binary.
 // The verbose argument helps determine whether or not to show
 // the output from the test harnesses (if the mode of the exercise is test)
 pub fn run(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
-    match exercise.mode {
-        Mode::Test => test(exercise, verbose)?,
-        Mode::Compile => compile_and_run(exercise)?,
-        Mode::Clippy => compile_and_run(exercise)?,
+    let start = Instant::now();
+    let result = match exercise.mode {
+        Mode::Test => test(exercise, verbose),
+        Mode::Compile => compile_and_run(exercise),
+        Mode::Clippy => compile_and_run(exercise),
+    };
+    let outcome = if result.is_ok() {
+        Outcome::Passed
+    } else {
+        Outcome::Failed
+    };
+    history::record(&exercise.name, "run", outcome, start);
+    result
+}
+
+// The result of an exercise in the summary of `rustlings run` with several exercises
+enum RunResult {
+    Passed(ExerciseOutput),
+    CompilationFailed(ExerciseOutput),
+    Failed(ExerciseOutput),
+    TimedOut(ExerciseOutput),
+}
+
+impl RunResult {
+    fn failure(&self) -> Option<Failure> {
+        match self {
+            Self::Passed(_) => None,
+            Self::CompilationFailed(_) => Some(Failure::Compilation),
+            Self::Failed(_) => Some(Failure::Tests),
+            Self::TimedOut(_) => Some(Failure::Timeout),
+        }
+    }
+
+    fn output(&self) -> &ExerciseOutput {
+        match self {
+            Self::Passed(output)
+            | Self::CompilationFailed(output)
+            | Self::Failed(output)
+            | Self::TimedOut(output) => output,
+        }
+    }
+}
+
+// Compile and run the exercise without printing anything
+fn run_quietly(exercise: &Exercise) -> (RunResult, Duration) {
+    let start = Instant::now();
+    let result = match exercise.compile() {
+        Ok(compiled) => match compiled.run() {
+            Ok(output) => RunResult::Passed(output),
+            Err(output) if exit_code::last_failure() == Some(Failure::Timeout) => {
+                RunResult::TimedOut(output)
+            }
+            Err(output) => RunResult::Failed(output),
+        },
+        Err(output) => RunResult::CompilationFailed(output),
+    };
+    let outcome = match result {
+        RunResult::Passed(_) => Outcome::Passed,
+        _ => Outcome::Failed,
+    };
+    history::record(&exercise.name, "run", outcome, start);
+    (result, start.elapsed())
+}
+
+// Run several exercises, `jobs` of them at the same time. The outputs of the failed ones
+// are printed in the order of the exercises, followed by a table of all results.
+// Returns the failure of the first exercise that failed.
+pub fn run_all(exercises: &[Exercise], jobs: usize) -> Result<(), Failure> {
+    let progress_bar = ui::spinner();
+    progress_bar.set_message(format!("Running {} exercises...", exercises.len()));
+    progress_bar.enable_steady_tick(Duration::from_millis(100));
+    let results = for_each_in_parallel(exercises, jobs, run_quietly);
+    progress_bar.finish_and_clear();
+    let failures: Vec<Failure> = results
+        .iter()
+        .filter_map(|(result, _)| result.failure())
+        .collect();
+
+    if porcelain::enabled() {
+        for (exercise, (result, _)) in exercises.iter().zip(&results) {
+            porcelain::print(&[&exercise.name, porcelain::result(result.failure())]);
+        }
+        return failures.first().map_or(Ok(()), |&failure| Err(failure));
     }
+    if json::enabled() {
+        json::print(&json::Results {
+            passed: failures.is_empty(),
+            exercises: exercises
+                .iter()
+                .zip(&results)
+                .map(|(exercise, (result, duration))| json::ExerciseResult {
+                    name: &exercise.name,
+                    path: &exercise.path,
+                    result: porcelain::result(result.failure()),
+                    seconds: duration.as_secs_f64(),
+                    stdout: result.output().stdout.clone(),
+                    stderr: result.output().stderr.clone(),
+                })
+                .collect(),
+        });
+        return failures.first().map_or(Ok(()), |&failure| Err(failure));
+    }
+
+    for (exercise, (result, _)) in exercises.iter().zip(&results) {
+        match result {
+            RunResult::Passed(_) => (),
+            RunResult::CompilationFailed(output) => {
+                warn!(
+                    "Compilation of {} failed!, Compiler error message:\n",
+                    exercise
+                );
+                println!("{}", output.stderr.trim_end());
+                record_error_codes(&exercise.name, &output.stderr);
+                println!();
+            }
+            RunResult::Failed(output) | RunResult::TimedOut(output) => {
+                warn!("Ran {} with errors", exercise);
+                println!("{}", output.stdout.trim_end());
+                println!("{}\n", output.stderr.trim_end());
+            }
+        }
+    }
+
+    let width = exercises.iter().map(|e| e.name.len()).max().unwrap_or(0);
+    for (exercise, (result, duration)) in exercises.iter().zip(&results) {
+        let result = match result {
+            RunResult::Passed(_) => theme::good(format!("{:<18}", "Passed")),
+            RunResult::CompilationFailed(_) => theme::bad(format!("{:<18}", "Compilation failed")),
+            RunResult::Failed(_) => theme::bad(format!("{:<18}", "Failed")),
+            RunResult::TimedOut(_) => theme::bad(format!("{:<18}", "Timed out")),
+        };
+        println!(
+            "{:<width$}  {result}  {:>6.2}s",
+            exercise.name,
+            duration.as_secs_f64()
+        );
+    }
+    println!(
+        "\n{} / {} exercises passed",
+        exercises.len() - failures.len(),
+        exercises.len()
+    );
+
+    failures.first().map_or(Ok(()), |&failure| Err(failure))
+}
+
+// Show the changes that resetting the exercise would discard.
+// Returns whether there are any.
+pub fn preview_reset(exercise: &Exercise) -> Result<bool> {
+    let pristine = variants::instantiate(exercise, &pristine(&exercise.path)?);
+    let current =
+        fs::read_to_string(&exercise.path).with_context(|| format!("Failed to read {exercise}"))?;
+    let lines = diff::diff(&current, &pristine);
+    if !diff::has_changes(&lines) {
+        return Ok(false);
+    }
+
+    println!("Resetting {exercise} discards these changes:\n");
+    println!("{}", diff::format_changes(&lines, DIFF_CONTEXT));
+    Ok(true)
+}
+
+// Show what the learner changed compared to the pristine exercise.
+pub fn show_changes(exercise: &Exercise) -> Result<()> {
+    let pristine = variants::instantiate(exercise, &pristine(&exercise.path)?);
+    let current =
+        fs::read_to_string(&exercise.path).with_context(|| format!("Failed to read {exercise}"))?;
+    let lines = diff::diff(&pristine, &current);
+    if !diff::has_changes(&lines) {
+        println!("You didn't change {exercise} yet.");
+        return Ok(());
+    }
+
+    println!("Your changes of {exercise} (- original, + yours):\n");
+    println!("{}", diff::format_changes(&lines, DIFF_CONTEXT));
     Ok(())
 }
 
-// Resets the exercise by stashing the changes.
+// Resets the exercise to its pristine copy.
+// The changes can be restored with `rustlings undo` or `rustlings restore`.
 pub fn reset(exercise: &Exercise) -> Result<(), ()> {
-    let command = Command::new("git")
-        .arg("stash")
-        .arg("--")
-        .arg(&exercise.path)
-        .spawn();
-
-    match command {
-        Ok(_) => Ok(()),
-        Err(_) => Err(()),
+    let pristine = match pristine(&exercise.path) {
+        Ok(pristine) => variants::instantiate(exercise, &pristine),
+        Err(e) => {
+            println!("{e:#}");
+            return Err(());
+        }
+    };
+    if let Err(e) = undo::push(&format!("reset {exercise}"), &[exercise]) {
+        println!("Not resetting {exercise} because it couldn't be saved for undo: {e:#}");
+        return Err(());
     }
+    let changed = fs::read_to_string(&exercise.path).is_ok_and(|current| current != pristine);
+    if changed {
+        if let Err(e) = backup(exercise) {
+            println!("Not resetting {exercise} because it couldn't be backed up: {e:#}");
+            return Err(());
+        }
+    }
+
+    fs::write(&exercise.path, pristine).map_err(|e| {
+        println!("Failed to reset {exercise}: {e}");
+    })
 }
 
 // Invoke the rust compiler on the path of the given exercise
 // and run the ensuing binary.
 // This is strictly for non-test binaries, so output is displayed
 fn compile_and_run(exercise: &Exercise) -> Result<(), ()> {
-    let progress_bar = ProgressBar::new_spinner();
+    let progress_bar = ui::spinner();
     progress_bar.set_message(format!("Compiling {exercise}..."));
     progress_bar.enable_steady_tick(Duration::from_millis(100));
 
@@ -50,6 +248,7 @@ fn compile_and_run(exercise: &Exercise) -> Result<(), ()> {
                 exercise
             );
             println!("{}", output.stderr);
+            record_error_codes(&exercise.name, &output.stderr);
             return Err(());
         }
     };
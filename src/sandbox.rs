@@ -0,0 +1,71 @@
+use std::env;
+use std::process::{Command, Stdio};
+
+// Learners' code is untrusted when grading the submissions of a class.
+// The sandbox runs the built exercises in a separate process without network access
+// and, where the OS allows it, with a read-only file system.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Sandbox {
+    // bubblewrap: read-only file system, private `/tmp`, no network
+    Bubblewrap,
+    // A new user and network namespace: no network
+    Unshare,
+    // The deprecated, but still available sandbox of macOS: no network, no writes outside of `/tmp`
+    MacOs,
+}
+
+const SANDBOX_EXEC_PROFILE: &str = r#"(version 1)
+(allow default)
+(deny network*)
+(deny file-write*)
+(allow file-write* (subpath "/private/tmp") (subpath "/private/var/folders") (literal "/dev/null"))"#;
+
+impl Sandbox {
+    // The most restrictive sandbox that is available on this system
+    pub fn detect() -> Option<Self> {
+        if cfg!(target_os = "linux") {
+            if which::which("bwrap").is_ok() {
+                return Some(Self::Bubblewrap);
+            }
+            // Unprivileged user namespaces can be disabled.
+            let can_unshare = Command::new("unshare")
+                .args(["--map-root-user", "--net", "true"])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()
+                .is_ok_and(|status| status.success());
+            if can_unshare {
+                return Some(Self::Unshare);
+            }
+        } else if cfg!(target_os = "macos") && which::which("sandbox-exec").is_ok() {
+            return Some(Self::MacOs);
+        }
+
+        None
+    }
+
+    pub fn command(self, program: &str) -> Command {
+        let mut cmd;
+        match self {
+            Self::Bubblewrap => {
+                let current_dir = env::current_dir().unwrap_or_default();
+                cmd = Command::new("bwrap");
+                cmd.args(["--ro-bind", "/", "/", "--dev", "/dev", "--proc", "/proc"])
+                    .args(["--tmpfs", "/tmp", "--unshare-all", "--die-with-parent"])
+                    .arg("--chdir")
+                    .arg(current_dir);
+            }
+            Self::Unshare => {
+                cmd = Command::new("unshare");
+                cmd.args(["--map-root-user", "--net"]);
+            }
+            Self::MacOs => {
+                cmd = Command::new("sandbox-exec");
+                cmd.args(["-p", SANDBOX_EXEC_PROFILE]);
+            }
+        }
+        cmd.arg(program);
+        cmd
+    }
+}
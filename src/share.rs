@@ -0,0 +1,117 @@
+use anyhow::{Context, Result};
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::exercise::{Exercise, ExerciseOutput, Mode};
+use crate::exit_code::{self, Failure};
+use crate::pristine::pristine;
+
+// The file that `rustlings share` writes by default, e.g. `rustlings-share-vecs1.md`
+pub fn default_path(exercise: &Exercise) -> PathBuf {
+    PathBuf::from(format!("rustlings-share-{}.md", exercise.name))
+}
+
+// The description at the top of an exercise, without its file name and the hint instructions
+fn description(source: &str) -> String {
+    let lines: Vec<&str> = source
+        .lines()
+        .map_while(|line| line.strip_prefix("//"))
+        .map(str::trim)
+        .filter(|line| !line.ends_with(".rs") && !line.starts_with("Execute `rustlings hint"))
+        .collect();
+    lines.join("\n").trim().to_string()
+}
+
+// A fenced code block whose fence is longer than any backticks in the text
+fn code_block(text: &str, language: &str) -> String {
+    let mut fence = String::from("```");
+    while text.contains(fence.as_str()) {
+        fence.push('`');
+    }
+    format!("{fence}{language}\n{}\n{fence}\n", text.trim_end())
+}
+
+// Compile and run the exercise. Returns a summary and the output.
+fn run(exercise: &Exercise) -> (&'static str, String) {
+    let result = exercise.compile().and_then(|compiled| match exercise.mode {
+        Mode::Clippy => Ok(ExerciseOutput::default()),
+        Mode::Compile | Mode::Test => compiled.run(),
+    });
+    let summary = match exit_code::last_failure() {
+        None => "It compiles and runs successfully.",
+        Some(Failure::Compilation) => "It doesn't compile.",
+        Some(Failure::Tests) => "It compiles, but it fails.",
+        Some(Failure::Timeout) => "It compiles, but it takes too long to run.",
+    };
+    let output = match result {
+        Ok(output) | Err(output) => format!("{}{}", output.stdout, output.stderr),
+    };
+    (summary, output)
+}
+
+// A Markdown document with the task, the solution and the output of the exercise, e.g. to
+// ask for help in a forum, paste it into a gist or submit it for peer review.
+fn bundle(exercise: &Exercise, source: &str, pristine_source: &str) -> String {
+    let (summary, output) = run(exercise);
+    let mut out = format!("# {exercise}\n\n");
+    let description = description(pristine_source);
+    if !description.is_empty() {
+        let _ = write!(out, "## Task\n\n{description}\n\n");
+    }
+    let _ = write!(
+        out,
+        "## My solution\n\n{}\n## Output\n\n{summary}\n",
+        code_block(source, "rust")
+    );
+    if !output.trim().is_empty() {
+        let _ = write!(out, "\n{}", code_block(&output, "text"));
+    }
+    out
+}
+
+pub fn share(exercise: &Exercise, output: &Path) -> Result<()> {
+    let source =
+        fs::read_to_string(&exercise.path).with_context(|| format!("Failed to read {exercise}"))?;
+    // Learners might have removed the description while solving the exercise.
+    let pristine_source = pristine(&exercise.path).unwrap_or_else(|_| source.clone());
+    fs::write(output, bundle(exercise, &source, &pristine_source))
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+    println!(
+        "Your solution of {exercise} was written to {}. Paste it into a gist or a forum post to share it.",
+        output.display()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_description() {
+        let source = "// vecs1.rs
+//
+// Your task is to create a `Vec`.
+//
+// Execute `rustlings hint vecs1` or use the `hint` watch subcommand for a hint.
+
+// I AM NOT DONE
+fn main() {}
+";
+        assert_eq!(description(source), "Your task is to create a `Vec`.");
+        assert_eq!(description("fn main() {}"), "");
+    }
+
+    #[test]
+    fn test_code_block() {
+        assert_eq!(
+            code_block("let x = 1;\n", "rust"),
+            "```rust\nlet x = 1;\n```\n"
+        );
+        assert_eq!(
+            code_block("/// ```\n/// x\n/// ```", ""),
+            "````\n/// ```\n/// x\n/// ```\n````\n"
+        );
+    }
+}
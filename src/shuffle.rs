@@ -0,0 +1,44 @@
+// The SplitMix64 generator. Its numbers only depend on the seed.
+pub fn generator(seed: u64) -> impl FnMut() -> u64 {
+    let mut state = seed;
+    move || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+// Shuffles the items in an order that only depends on the seed,
+// using the Fisher-Yates shuffle.
+pub fn shuffle<T>(items: &mut [T], seed: u64) {
+    let mut next = generator(seed);
+
+    for ind in (1..items.len()).rev() {
+        let other = (next() % (ind as u64 + 1)) as usize;
+        items.swap(ind, other);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_shuffle_is_deterministic() {
+        let mut first: Vec<u32> = (0..20).collect();
+        let mut second = first.clone();
+        shuffle(&mut first, 42);
+        shuffle(&mut second, 42);
+        assert_eq!(first, second);
+        assert_ne!(first, (0..20).collect::<Vec<_>>());
+
+        let mut other_seed: Vec<u32> = (0..20).collect();
+        shuffle(&mut other_seed, 43);
+        assert_ne!(first, other_seed);
+
+        first.sort_unstable();
+        assert_eq!(first, (0..20).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,427 @@
+use std::io::{self, IsTerminal, Write};
+use std::panic;
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::trace;
+
+use crate::ui;
+
+#[cfg(unix)]
+use crate::{lock, state_file, stats};
+
+// Rustlings can be stopped while a spinner is drawn or the progress is written, e.g. by
+// Ctrl+C, a panic, closing the terminal or `kill`. The terminal is restored, the progress
+// is written and the running exercise is stopped before exiting, so that learners don't
+// end up with a hidden cursor, lost progress or an exercise that keeps running.
+pub fn install() {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        default_hook(info);
+    }));
+
+    #[cfg(unix)]
+    unix::handle_signals(&[libc::SIGINT, libc::SIGTERM, libc::SIGHUP]);
+}
+
+// Clear the line of a spinner, reset the colors, show the cursor and restore the title again
+pub fn restore_terminal() {
+    ui::reset_title();
+    ui::leave_alternate_screen();
+    let mut stdout = io::stdout();
+    if stdout.is_terminal() {
+        let _ = stdout.write_all(b"\r\x1b[2K\x1b[0m\x1b[?25h");
+        let _ = stdout.flush();
+    }
+}
+
+// Exit like the shell reports processes that were stopped by a signal
+#[cfg(unix)]
+fn exit_after_signal(signal: i32) -> ! {
+    trace!("Stopping after the signal {signal}");
+    stop_children();
+    restore_terminal();
+    stats::record_time_until_exit();
+    state_file::finish_writes();
+    lock::release();
+    std::process::exit(128 + signal);
+}
+
+// The processes that Rustlings waits for, e.g. the compiler or the running exercise.
+// Tests of exercises can start processes as well, which must not outlive a stopped run
+// since they would keep running or keep files open. On Unix, every child process gets its
+// own process group that is stopped as a whole. On Windows, the child processes are added
+// to a job object that stops them and the processes they started when Rustlings exits.
+static CHILDREN: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+// Removes the process from `CHILDREN` after it exited
+struct ChildGuard(u32);
+
+impl ChildGuard {
+    fn new(child: &Child) -> Self {
+        #[cfg(windows)]
+        windows::add_to_job(child);
+        CHILDREN.lock().unwrap().push(child.id());
+        Self(child.id())
+    }
+
+    // Processes that the child started can outlive it, e.g. servers that a test started or
+    // processes of a child that was killed by Ctrl+C or the CPU time limit. They are in the
+    // process group of the child, which is stopped once the child exited.
+    fn exited(self, status: io::Result<ExitStatus>) -> io::Result<ExitStatus> {
+        #[cfg(unix)]
+        unsafe {
+            libc::kill(-(self.0 as libc::pid_t), libc::SIGKILL);
+        }
+        status
+    }
+}
+
+impl Drop for ChildGuard {
+    fn drop(&mut self) {
+        CHILDREN.lock().unwrap().retain(|&id| id != self.0);
+    }
+}
+
+// Returns `false` if no child process was running.
+#[cfg(unix)]
+fn stop_children() -> bool {
+    let children = CHILDREN.lock().unwrap_or_else(|e| e.into_inner());
+    for &id in children.iter() {
+        trace!("Stopping the child process {id} and the processes it started");
+        unsafe {
+            libc::kill(-(id as libc::pid_t), libc::SIGTERM);
+        }
+    }
+    !children.is_empty()
+}
+
+// In watch mode, Ctrl+C stops a long compilation or a run that doesn't end and returns to
+// watch mode. It only exits Rustlings if nothing is running.
+static CANCEL_ON_INTERRUPT: AtomicBool = AtomicBool::new(false);
+static CANCELLED: AtomicBool = AtomicBool::new(false);
+
+pub fn cancel_runs_on_interrupt() {
+    CANCEL_ON_INTERRUPT.store(true, Ordering::Relaxed);
+}
+
+// Stop the running child processes on Ctrl+C in watch mode.
+// Returns `false` if Rustlings should exit instead.
+#[cfg(unix)]
+fn cancel_run() -> bool {
+    if !CANCEL_ON_INTERRUPT.load(Ordering::Relaxed) || !stop_children() {
+        return false;
+    }
+    trace!("Cancelled the running exercise");
+    CANCELLED.store(true, Ordering::Relaxed);
+    true
+}
+
+// Whether the current run was cancelled, so that its errors aren't shown
+pub fn cancelled() -> bool {
+    CANCELLED.load(Ordering::Relaxed)
+}
+
+// Whether a run was cancelled since the last call
+pub fn take_cancelled() -> bool {
+    CANCELLED.swap(false, Ordering::Relaxed)
+}
+
+// Resource limits of a process. Windows applies the memory and the CPU time limits with a
+// job object, while `ulimit` applies them on Unix. The process is killed after the timeout.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Limits {
+    pub memory_mb: Option<u64>,
+    pub cpu_seconds: Option<u64>,
+    pub timeout: Option<Duration>,
+}
+
+fn wait(child: &mut Child, timeout: Option<Duration>) -> io::Result<ExitStatus> {
+    let Some(timeout) = timeout else {
+        return child.wait();
+    };
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if Instant::now() >= deadline {
+            trace!(
+                "Stopping the child process {} after {timeout:?}",
+                child.id()
+            );
+            let _ = child.kill();
+            return child.wait();
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+}
+
+// `output` and `status` of processes that are stopped together with Rustlings
+pub trait TrackedCommand {
+    fn tracked_output(&mut self) -> io::Result<Output>;
+    fn tracked_status(&mut self) -> io::Result<ExitStatus>;
+    // Like `tracked_status`, but the process runs with the limits, and stdout and stderr are
+    // passed to the writers while it runs
+    fn tracked_limited_capture(
+        &mut self,
+        limits: Limits,
+        stdout: &mut (dyn Write + Send),
+        stderr: &mut (dyn Write + Send),
+    ) -> io::Result<ExitStatus>;
+}
+
+impl TrackedCommand for Command {
+    // Like `output`, stdin is closed and stdout and stderr are captured.
+    fn tracked_output(&mut self) -> io::Result<Output> {
+        let child = spawn(
+            self.stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped()),
+        )?;
+        let guard = ChildGuard::new(&child);
+        resume(&child);
+        let output = child.wait_with_output()?;
+        let status = guard.exited(Ok(output.status))?;
+        Ok(Output { status, ..output })
+    }
+
+    fn tracked_status(&mut self) -> io::Result<ExitStatus> {
+        let mut child = spawn(self)?;
+        let guard = ChildGuard::new(&child);
+        resume(&child);
+        guard.exited(child.wait())
+    }
+
+    fn tracked_limited_capture(
+        &mut self,
+        limits: Limits,
+        stdout: &mut (dyn Write + Send),
+        stderr: &mut (dyn Write + Send),
+    ) -> io::Result<ExitStatus> {
+        let mut child = spawn(self.stdout(Stdio::piped()).stderr(Stdio::piped()))?;
+        let guard = ChildGuard::new(&child);
+        let (Some(mut child_stdout), Some(mut child_stderr)) =
+            (child.stdout.take(), child.stderr.take())
+        else {
+            unreachable!("stdout and stderr are piped");
+        };
+        thread::scope(|scope| {
+            let readers = [
+                scope.spawn(move || io::copy(&mut child_stdout, stdout)),
+                scope.spawn(move || io::copy(&mut child_stderr, stderr)),
+            ];
+            let status = {
+                // Closing the job after the process exited stops the processes it started.
+                #[cfg(windows)]
+                let _job = windows::limit(&child, limits);
+                resume(&child);
+                guard.exited(wait(&mut child, limits.timeout))
+            };
+            // The processes that could keep the pipes open are stopped by now.
+            for reader in readers {
+                reader.join().unwrap_or_else(|e| panic::resume_unwind(e))?;
+            }
+            status
+        })
+    }
+}
+
+// On Windows, the process starts suspended, so that it can't start processes before it is
+// added to the job objects. `resume` starts it afterwards.
+fn spawn(cmd: &mut Command) -> io::Result<Child> {
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(cmd, 0);
+    #[cfg(windows)]
+    std::os::windows::process::CommandExt::creation_flags(cmd, windows::CREATE_SUSPENDED);
+    cmd.spawn()
+}
+
+fn resume(_child: &Child) {
+    #[cfg(windows)]
+    windows::resume(_child);
+}
+
+// The job object with the child processes, see `CHILDREN`
+#[cfg(windows)]
+mod windows {
+    use std::ffi::c_void;
+    use std::os::windows::io::AsRawHandle;
+    use std::process::Child;
+    use std::ptr;
+    use std::sync::OnceLock;
+
+    type Handle = *mut c_void;
+
+    // `JOBOBJECT_EXTENDED_LIMIT_INFORMATION`
+    #[repr(C)]
+    #[derive(Default)]
+    struct ExtendedLimitInformation {
+        per_process_user_time_limit: i64,
+        per_job_user_time_limit: i64,
+        limit_flags: u32,
+        minimum_working_set_size: usize,
+        maximum_working_set_size: usize,
+        active_process_limit: u32,
+        affinity: usize,
+        priority_class: u32,
+        scheduling_class: u32,
+        io_counters: [u64; 6],
+        process_memory_limit: usize,
+        job_memory_limit: usize,
+        peak_process_memory_used: usize,
+        peak_job_memory_used: usize,
+    }
+
+    const JOB_OBJECT_EXTENDED_LIMIT_INFORMATION: i32 = 9;
+    const JOB_OBJECT_LIMIT_PROCESS_TIME: u32 = 0x2;
+    const JOB_OBJECT_LIMIT_PROCESS_MEMORY: u32 = 0x100;
+    const JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE: u32 = 0x2000;
+
+    pub const CREATE_SUSPENDED: u32 = 0x4;
+
+    #[link(name = "ntdll")]
+    extern "system" {
+        // Resumes all threads of a process, since `Child` doesn't have the handle of its thread
+        fn NtResumeProcess(process: Handle) -> i32;
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn CreateJobObjectW(attributes: *mut c_void, name: *const u16) -> Handle;
+        fn SetInformationJobObject(job: Handle, class: i32, info: *mut c_void, len: u32) -> i32;
+        fn AssignProcessToJobObject(job: Handle, process: Handle) -> i32;
+        fn CloseHandle(handle: Handle) -> i32;
+    }
+
+    // The handle is closed by Windows when Rustlings exits, which stops all processes of the job.
+    struct Job(Handle);
+
+    unsafe impl Send for Job {}
+    unsafe impl Sync for Job {}
+
+    fn create_job(mut info: ExtendedLimitInformation) -> Option<Job> {
+        unsafe {
+            let job = CreateJobObjectW(ptr::null_mut(), ptr::null());
+            if job.is_null() {
+                return None;
+            }
+            info.limit_flags |= JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+            let set = SetInformationJobObject(
+                job,
+                JOB_OBJECT_EXTENDED_LIMIT_INFORMATION,
+                (&mut info as *mut ExtendedLimitInformation).cast(),
+                std::mem::size_of::<ExtendedLimitInformation>() as u32,
+            );
+            if set == 0 {
+                CloseHandle(job);
+                return None;
+            }
+            Some(Job(job))
+        }
+    }
+
+    fn job() -> Option<&'static Job> {
+        static JOB: OnceLock<Option<Job>> = OnceLock::new();
+        JOB.get_or_init(|| create_job(ExtendedLimitInformation::default()))
+            .as_ref()
+    }
+
+    // A job of its own for the process with the resource limits. Jobs can be nested since
+    // Windows 8, so the process stays in the job of all child processes as well.
+    // Its processes are stopped when the returned job is dropped.
+    pub fn limit(child: &Child, limits: super::Limits) -> Option<LimitJob> {
+        let mut info = ExtendedLimitInformation::default();
+        if let Some(memory_mb) = limits.memory_mb {
+            info.limit_flags |= JOB_OBJECT_LIMIT_PROCESS_MEMORY;
+            info.process_memory_limit = usize::try_from(memory_mb * 1024 * 1024).ok()?;
+        }
+        if let Some(cpu_seconds) = limits.cpu_seconds {
+            // In units of 100 nanoseconds
+            info.limit_flags |= JOB_OBJECT_LIMIT_PROCESS_TIME;
+            info.per_process_user_time_limit = i64::try_from(cpu_seconds * 10_000_000).ok()?;
+        }
+        if info.limit_flags == 0 {
+            return None;
+        }
+        let job = create_job(info)?;
+        unsafe {
+            AssignProcessToJobObject(job.0, child.as_raw_handle());
+        }
+        Some(LimitJob(job))
+    }
+
+    pub struct LimitJob(Job);
+
+    impl Drop for LimitJob {
+        fn drop(&mut self) {
+            unsafe {
+                CloseHandle((self.0).0);
+            }
+        }
+    }
+
+    pub fn resume(child: &Child) {
+        unsafe {
+            NtResumeProcess(child.as_raw_handle());
+        }
+    }
+
+    pub fn add_to_job(child: &Child) {
+        if let Some(job) = job() {
+            unsafe {
+                AssignProcessToJobObject(job.0, child.as_raw_handle());
+            }
+        }
+    }
+}
+
+// Only a few functions may be called in a signal handler. It writes the signal to a pipe
+// and a thread does the actual work after reading it.
+#[cfg(unix)]
+mod unix {
+    use std::fs::File;
+    use std::io::Read;
+    use std::os::fd::FromRawFd;
+    use std::sync::atomic::{AtomicI32, Ordering};
+    use std::thread;
+
+    static PIPE: AtomicI32 = AtomicI32::new(-1);
+
+    extern "C" fn handler(signal: libc::c_int) {
+        let byte = signal as u8;
+        unsafe {
+            libc::write(PIPE.load(Ordering::Relaxed), (&byte as *const u8).cast(), 1);
+        }
+    }
+
+    pub fn handle_signals(signals: &[libc::c_int]) {
+        let mut fds = [0; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return;
+        }
+        // The exercises and tools that Rustlings starts must not inherit the pipe.
+        for fd in fds {
+            unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) };
+        }
+        PIPE.store(fds[1], Ordering::Relaxed);
+        for &signal in signals {
+            unsafe { libc::signal(signal, handler as *const () as libc::sighandler_t) };
+        }
+
+        let mut reader = unsafe { File::from_raw_fd(fds[0]) };
+        thread::spawn(move || {
+            let mut signal = [0];
+            while reader.read_exact(&mut signal).is_ok() {
+                let signal = i32::from(signal[0]);
+                if signal == libc::SIGINT && super::cancel_run() {
+                    continue;
+                }
+                super::exit_after_signal(signal);
+            }
+        });
+    }
+}
@@ -0,0 +1,389 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tracing::trace;
+
+use crate::backup::BACKUP_DIR;
+use crate::challenge::ChallengeResult;
+use crate::exercise::Exercise;
+use crate::history::{self, HISTORY_PATH};
+use crate::list_ui::ListFilter;
+use crate::lock;
+use crate::review::Review;
+use crate::undo::UNDO_PATH;
+
+// Whether an exercise is done is determined by its file.
+// The state file stores the progress that can't be derived from the files.
+pub const STATE_FILE_PATH: &str = ".rustlings-state.json";
+
+// Set if the progress is stored per OS user, see `use_per_user_state`
+static PER_USER_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+// The path of a file of the progress like the state file or the history. They are stored in
+// the Rustlings directory, or without the `.rustlings-` prefix in the directory of the user.
+pub fn progress_path(path: &str) -> PathBuf {
+    match PER_USER_DIR.get() {
+        Some(dir) => dir.join(path.strip_prefix(".rustlings-").unwrap_or(path)),
+        None => PathBuf::from(path),
+    }
+}
+
+// The path of the state file that is used
+pub fn path() -> &'static Path {
+    static PATH: OnceLock<PathBuf> = OnceLock::new();
+    match PER_USER_DIR.get() {
+        Some(_) => PATH.get_or_init(|| progress_path(STATE_FILE_PATH)),
+        None => Path::new(STATE_FILE_PATH),
+    }
+}
+
+// The data directory of the OS user, e.g. `~/.local/share/rustlings`
+fn user_data_dir() -> Option<PathBuf> {
+    let data_dir = if cfg!(windows) {
+        PathBuf::from(env::var_os("LOCALAPPDATA")?)
+    } else {
+        match env::var_os("XDG_DATA_HOME") {
+            Some(dir) => PathBuf::from(dir),
+            None => PathBuf::from(env::var_os("HOME")?)
+                .join(".local")
+                .join("share"),
+        }
+    };
+    Some(data_dir.join("rustlings"))
+}
+
+// A directory name that identifies the Rustlings directory, e.g. `rustlings-1f0c9a6b2d4e8f73`
+// for `/home/lab/rustlings`, so that the states of different checkouts stay apart.
+fn workspace_key(workspace: &Path) -> String {
+    let hash = Sha256::digest(workspace.as_os_str().as_encoded_bytes());
+    let hex: String = hash[..8].iter().map(|byte| format!("{byte:02x}")).collect();
+    match workspace.file_name() {
+        Some(name) => format!("{}-{hex}", name.to_string_lossy()),
+        None => hex,
+    }
+}
+
+// Rename the file or the directory, or copy it if it is moved to another file system.
+fn move_path(from: &Path, to: &Path) -> io::Result<()> {
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+        for entry in fs::read_dir(from)? {
+            let entry = entry?;
+            move_path(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        fs::remove_dir(from)
+    } else {
+        fs::copy(from, to)?;
+        fs::remove_file(from)
+    }
+}
+
+// On machines where several students share a Rustlings directory, the progress is stored
+// in the data directory of each OS user instead. This includes the history, the undo entries
+// and the backups of reset exercises. Existing files of the Rustlings directory are moved
+// there, so they belong to the first user who runs Rustlings.
+pub fn use_per_user_state() -> Result<()> {
+    let workspace = env::current_dir()
+        .and_then(fs::canonicalize)
+        .context("Failed to get the current directory")?;
+    let Some(data_dir) = user_data_dir() else {
+        anyhow::bail!("The data directory of the user couldn't be determined");
+    };
+    let dir = data_dir.join(workspace_key(&workspace));
+    fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let _ = PER_USER_DIR.set(dir);
+
+    let mut moved = false;
+    for file in [STATE_FILE_PATH, HISTORY_PATH, UNDO_PATH, BACKUP_DIR] {
+        let path = progress_path(file);
+        if !path.exists() && Path::new(file).exists() {
+            move_path(Path::new(file), &path)
+                .with_context(|| format!("Failed to move {file} to {}", path.display()))?;
+            moved = true;
+        }
+    }
+    if moved {
+        notice!(
+            "Your progress was moved to {}",
+            path().parent().unwrap_or(path()).display()
+        );
+    }
+    trace!("Storing the progress in {}", path().display());
+    Ok(())
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+pub struct StateFile {
+    // Records by exercise name
+    #[serde(default)]
+    pub exercises: BTreeMap<String, ExerciseRecord>,
+    // The last answer given to each quiz
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub quiz_answers: BTreeMap<String, usize>,
+    // The names of the solved expression exercises
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub expressions_solved: BTreeSet<String>,
+    // Whether the last prediction of each prediction exercise was correct
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub predictions: BTreeMap<String, bool>,
+    // The seed of the variants of the exercises with parameters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub variant_seed: Option<u64>,
+    // The results of all finished challenges
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub challenges: Vec<ChallengeResult>,
+    // Unix timestamps of when the achievements were unlocked, by achievement
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub achievements: BTreeMap<String, u64>,
+    // The names of the bookmarked exercises
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub bookmarks: BTreeSet<String>,
+    // The error codes reported by the last failed compilation
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub last_error_codes: Vec<String>,
+    // The exercises that the interactive list showed the last time
+    #[serde(default, skip_serializing_if = "ListFilter::is_empty")]
+    pub list_filter: ListFilter,
+    // Unix timestamp of when Rustlings first found exam.toml. Hints stay disabled until
+    // the exam is graded, even if the file is removed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exam_started_at: Option<u64>,
+}
+
+// What is known about the past attempts of an exercise
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+pub struct ExerciseRecord {
+    // Unix timestamp of the first time the exercise was verified as done
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<u64>,
+    // The number of failed verifications
+    #[serde(default)]
+    pub failures: u32,
+    // Unix timestamp of the first failed verification
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub first_failed_at: Option<u64>,
+    // The active time spent on the exercise in watch mode until it was completed
+    #[serde(default)]
+    pub seconds_spent: u64,
+    // How often the hint of the exercise was shown
+    #[serde(default)]
+    pub hints_viewed: u32,
+    // Unix timestamp of the last time `rustlings recheck` verified the done exercise
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checked_at: Option<u64>,
+    // Whether the last recheck found that the done exercise doesn't pass anymore
+    #[serde(default)]
+    pub regressed: bool,
+    // The schedule for reviewing the exercise after it was completed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review: Option<Review>,
+    // How many failed compilations reported each error code
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub error_codes: BTreeMap<String, u32>,
+    // The parts of a multi-part exercise whose tests passed at least once
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub parts_done: BTreeSet<String>,
+}
+
+impl StateFile {
+    // A missing state file is the same as an empty one.
+    pub fn load() -> Result<Self> {
+        let path = path();
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display())),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    // The state is written to a temporary file first so that it is never left half written.
+    pub fn save(&self) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        let thread_id: String = format!("{:?}", thread::current().id())
+            .chars()
+            .filter(|c| c.is_alphanumeric())
+            .collect();
+        let path = path();
+        let temp_path = format!("{}.{}_{thread_id}", path.display(), process::id());
+        let result = fs::write(&temp_path, content)
+            .and_then(|()| fs::rename(&temp_path, path))
+            .with_context(|| format!("Failed to write {}", path.display()));
+        match &result {
+            Ok(()) => trace!("Saved {}", path.display()),
+            Err(e) => trace!("{e:#}"),
+        }
+        result
+    }
+
+    pub fn record(&mut self, name: &str) -> &mut ExerciseRecord {
+        self.exercises.entry(name.to_string()).or_default()
+    }
+}
+
+// Updates from the threads of watch mode must not overwrite each other.
+static UPDATE_LOCK: Mutex<()> = Mutex::new(());
+
+// Wait until an update of another thread is written and prevent further updates.
+// Called before exiting, so that no progress is lost.
+pub fn finish_writes() {
+    let guard = UPDATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    std::mem::forget(guard);
+}
+
+// Load the state file, apply `f` and save it again if anything changed.
+// Exercises should keep working even if the state file can't be accessed,
+// so errors are only reported.
+pub fn update(f: impl FnOnce(&mut StateFile)) {
+    let _guard = UPDATE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let _update_lock = lock::acquire_update();
+
+    let result = StateFile::load().and_then(|mut state| {
+        let old_state = state.clone();
+        f(&mut state);
+        if state == old_state {
+            Ok(())
+        } else {
+            state.save()
+        }
+    });
+
+    if let Err(e) = result {
+        trace!("Failed to update the progress: {e:#}");
+        println!(
+            "Failed to update the progress in {}: {e:#}",
+            path().display()
+        );
+    }
+}
+
+// Move the progress of renamed exercises from their former names to the current ones,
+// so that e.g. a done exercise stays done, and report the records of unknown exercises.
+pub fn rename_aliases(exercises: &[Exercise]) {
+    let renames: Vec<(&str, &str)> = exercises
+        .iter()
+        .flat_map(|e| {
+            e.aliases
+                .iter()
+                .map(|alias| (alias.as_str(), e.name.as_str()))
+        })
+        .collect();
+    let state = StateFile::load().unwrap_or_default();
+    let renamed = |name: &String| renames.iter().any(|(alias, _)| alias == name);
+    if state.exercises.keys().any(renamed)
+        || state.quiz_answers.keys().any(renamed)
+        || state.predictions.keys().any(renamed)
+        || state.expressions_solved.iter().any(renamed)
+        || state.bookmarks.iter().any(renamed)
+    {
+        update(|state| {
+            for &(alias, name) in &renames {
+                if let Some(record) = state.exercises.remove(alias) {
+                    state.exercises.entry(name.to_string()).or_insert(record);
+                }
+                if let Some(answer) = state.quiz_answers.remove(alias) {
+                    state.quiz_answers.entry(name.to_string()).or_insert(answer);
+                }
+                if let Some(correct) = state.predictions.remove(alias) {
+                    state.predictions.entry(name.to_string()).or_insert(correct);
+                }
+                if state.expressions_solved.remove(alias) {
+                    state.expressions_solved.insert(name.to_string());
+                }
+                if state.bookmarks.remove(alias) {
+                    state.bookmarks.insert(name.to_string());
+                }
+            }
+        });
+    }
+    history::rename_exercises(&renames);
+
+    let state = StateFile::load().unwrap_or_default();
+    for name in state.exercises.keys() {
+        if !exercises.iter().any(|e| &e.name == name) {
+            notice!(
+                "The progress of '{name}' in {} belongs to no exercise of the course.",
+                path().display()
+            );
+        }
+    }
+}
+
+// Record that the exercise was verified as done.
+pub fn record_completion(name: &str) {
+    update(|state| {
+        let now = now();
+        let record = state.record(name);
+        if record.completed_at.is_none() {
+            record.completed_at = Some(now);
+        }
+        record.regressed = false;
+        let failures = record.failures;
+        match &mut record.review {
+            Some(review) => review.finish(failures, now),
+            None => record.review = Some(Review::new(now)),
+        }
+    });
+}
+
+// Record that the hint of the exercise was shown.
+pub fn record_hint(name: &str) {
+    update(|state| state.record(name).hints_viewed += 1);
+}
+
+// Record the parts of the exercise whose tests passed.
+pub fn record_parts(name: &str, parts: &[&str]) {
+    if parts.is_empty() {
+        return;
+    }
+    update(|state| {
+        let parts_done = &mut state.record(name).parts_done;
+        parts_done.extend(parts.iter().map(|part| part.to_string()));
+    });
+}
+
+// Record that the verification of the exercise failed.
+pub fn record_failure(name: &str) {
+    update(|state| {
+        let record = state.record(name);
+        record.failures += 1;
+        record.first_failed_at.get_or_insert_with(now);
+    });
+}
+
+// The current Unix timestamp in seconds
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_workspace_key() {
+        let key = workspace_key(Path::new("/home/lab/rustlings"));
+        assert!(key.starts_with("rustlings-"));
+        assert_eq!(key.len(), "rustlings-".len() + 16);
+        assert_eq!(key, workspace_key(Path::new("/home/lab/rustlings")));
+        assert_ne!(
+            workspace_key(Path::new("/a/b_c")),
+            workspace_key(Path::new("/a_b/c"))
+        );
+    }
+}
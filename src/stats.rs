@@ -0,0 +1,187 @@
+use console::style;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::exercise::Exercise;
+use crate::history::format_timestamp;
+use crate::json;
+use crate::state_file::{self, StateFile};
+
+const DAY: u64 = 24 * 60 * 60;
+// The chart shows at most this many days.
+const CHART_DAYS: u64 = 30;
+const CHART_WIDTH: usize = 40;
+
+// Longer gaps between two edits of an exercise don't count as working on it.
+const IDLE_LIMIT: Duration = Duration::from_secs(5 * 60);
+
+// Measures the active time spent on exercises in watch mode.
+// The time between two edits of the same exercise is added to it
+// until the exercise is completed.
+#[derive(Default)]
+pub struct ActivityTracker {
+    last_edits: HashMap<String, Instant>,
+}
+
+impl ActivityTracker {
+    pub fn edited(&mut self, exercise: &Exercise) {
+        let now = Instant::now();
+        *LAST_EDIT.lock().unwrap() = Some((exercise.name.clone(), now));
+        let Some(last_edit) = self.last_edits.insert(exercise.name.clone(), now) else {
+            return;
+        };
+        add_time(&exercise.name, now - last_edit);
+    }
+}
+
+// The exercise that was edited last and when
+static LAST_EDIT: Mutex<Option<(String, Instant)>> = Mutex::new(None);
+
+fn add_time(name: &str, gap: Duration) {
+    if gap <= IDLE_LIMIT {
+        state_file::update(|state| {
+            let record = state.record(name);
+            if record.completed_at.is_none() {
+                record.seconds_spent += gap.as_secs();
+            }
+        });
+    }
+}
+
+// Add the time since the last edit when watch mode is exited
+pub fn record_time_until_exit() {
+    let last_edit = LAST_EDIT.lock().unwrap_or_else(|e| e.into_inner()).take();
+    if let Some((name, last_edit)) = last_edit {
+        add_time(&name, last_edit.elapsed());
+    }
+}
+
+pub fn seconds_spent(exercise: &Exercise, state: &StateFile) -> Option<u64> {
+    state
+        .exercises
+        .get(&exercise.name)
+        .map(|record| record.seconds_spent)
+        .filter(|&seconds| seconds > 0)
+}
+
+// Formats a duration like `1h 05m`, `5m 30s` or `45s`.
+pub fn format_duration(seconds: u64) -> String {
+    let (hours, minutes, seconds) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if hours > 0 {
+        format!("{hours}h {minutes:02}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds:02}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+// One line per day with a bar of the exercises completed until the end of the day,
+// from the day of the first completion until `now`.
+fn progress_chart(completions: &[u64], now: u64) -> Vec<String> {
+    let Some(first_day) = completions.iter().min().map(|t| t / DAY) else {
+        return Vec::new();
+    };
+    let today = now / DAY;
+    let first_day = first_day.max(today.saturating_sub(CHART_DAYS - 1));
+    let total = completions.len();
+
+    (first_day..=today)
+        .map(|day| {
+            let completed = completions.iter().filter(|&&t| t / DAY <= day).count();
+            let width = completed * CHART_WIDTH / total;
+            format!(
+                "{} {:<CHART_WIDTH$} {completed}",
+                &format_timestamp(day * DAY)[..10],
+                "#".repeat(width)
+            )
+        })
+        .collect()
+}
+
+// Print the totals of the recorded progress.
+pub fn stats(exercises: &[Exercise]) {
+    let state = StateFile::load().unwrap_or_default();
+    let required = exercises.iter().filter(|e| !e.optional).collect::<Vec<_>>();
+    let done = required.iter().filter(|e| e.looks_done(&state)).count();
+    let records = || {
+        exercises
+            .iter()
+            .filter_map(|e| Some((e, state.exercises.get(&e.name)?)))
+    };
+
+    let total_seconds: u64 = records().map(|(_, record)| record.seconds_spent).sum();
+    let failures: u32 = records().map(|(_, record)| record.failures).sum();
+    let hints: u32 = records().map(|(_, record)| record.hints_viewed).sum();
+    if json::enabled() {
+        json::print(&json::Stats {
+            done,
+            total: required.len(),
+            seconds_spent: total_seconds,
+            failed_verifications: failures,
+            hints_viewed: hints,
+        });
+        return;
+    }
+
+    println!("Exercises done:       {done} / {}", required.len());
+    println!("Time spent:           {}", format_duration(total_seconds));
+    println!("Failed verifications: {failures}");
+    println!("Hints viewed:         {hints}");
+
+    let completions = records()
+        .filter_map(|(_, record)| record.completed_at)
+        .collect::<Vec<_>>();
+    let chart = progress_chart(&completions, state_file::now());
+    if !chart.is_empty() {
+        println!("\n{}", style("Completed exercises over time:").bold());
+        for line in chart {
+            println!("  {line}");
+        }
+    }
+
+    let mut longest = records()
+        .filter(|(_, record)| record.seconds_spent > 0)
+        .collect::<Vec<_>>();
+    longest.sort_by_key(|(_, record)| std::cmp::Reverse(record.seconds_spent));
+    if !longest.is_empty() {
+        println!("\n{}", style("Exercises that took the longest:").bold());
+        for (exercise, record) in longest.into_iter().take(3) {
+            println!(
+                "  {:<17}\t{}",
+                exercise.name,
+                format_duration(record.seconds_spent)
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_progress_chart() {
+        assert!(progress_chart(&[], 0).is_empty());
+
+        let chart = progress_chart(&[10, 20, 2 * DAY], 3 * DAY);
+        assert_eq!(chart.len(), 4);
+        assert!(chart[0].starts_with("1970-01-01 ##########################  "));
+        assert!(chart[0].ends_with(" 2"));
+        assert!(chart[3].starts_with("1970-01-04 ########################################"));
+        assert!(chart[3].ends_with(" 3"));
+
+        // Old completions are counted, but only the last days are shown.
+        let chart = progress_chart(&[0, 100 * DAY], 100 * DAY);
+        assert_eq!(chart.len(), CHART_DAYS as usize);
+        assert!(chart[0].ends_with(" 1"));
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(45), "45s");
+        assert_eq!(format_duration(330), "5m 30s");
+        assert_eq!(format_duration(3900), "1h 05m");
+    }
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use crate::exercise::{Exercise, ExerciseOutput, Mode};
+use crate::pristine::pristine;
+use crate::variants;
+
+// The number of `#[test]` functions by name that aren't marked with `#[ignore]`.
+// Tests in different modules can have the same name.
+fn active_tests(source: &str) -> BTreeMap<&str, usize> {
+    let mut tests = BTreeMap::new();
+    let (mut in_test, mut ignored) = (false, false);
+    for line in source.lines().map(str::trim_start) {
+        if line.starts_with("#[test]") {
+            (in_test, ignored) = (true, false);
+        } else if line.starts_with("#[ignore") {
+            ignored = true;
+        } else if line.starts_with("#[") || line.starts_with("//") {
+            continue;
+        } else if let Some((_, rest)) = line.split_once("fn ").filter(|_| in_test) {
+            let name = rest
+                .split(|c: char| !c.is_alphanumeric() && c != '_')
+                .next()
+                .unwrap_or_default();
+            if !ignored {
+                *tests.entry(name).or_default() += 1;
+            }
+            in_test = false;
+        }
+    }
+    tests
+}
+
+// The number of tests by name that ran according to the output, e.g. `adds` for
+// `test tests::adds ... ok` or `test tests::overflows - should panic ... ok`
+fn ran_tests(output: &ExerciseOutput) -> BTreeMap<&str, usize> {
+    let mut tests = BTreeMap::new();
+    for (name, _) in output.test_results() {
+        let path = name.split_whitespace().next().unwrap_or_default();
+        let name = path.rsplit("::").next().unwrap_or_default();
+        *tests.entry(name).or_default() += 1;
+    }
+    tests
+}
+
+// The tests of the pristine exercise that didn't run, e.g. since they were deleted, renamed,
+// ignored or left out with `#[cfg(...)]` to make the exercise pass. The pristine copy tells which
+// tests there are and the output of the test run which of them actually ran, so only the tests
+// themselves count and their bodies can still be edited like many exercises require.
+// Exercises without a pristine copy or with a custom harness can't be checked.
+pub fn removed_tests(exercise: &Exercise, output: &ExerciseOutput) -> Vec<String> {
+    if !matches!(exercise.mode, Mode::Test) || exercise.harness.is_some() {
+        return Vec::new();
+    }
+    let Ok(pristine) = pristine(&exercise.path) else {
+        return Vec::new();
+    };
+    let pristine = variants::instantiate(exercise, &pristine);
+    let ran = ran_tests(output);
+    active_tests(&pristine)
+        .into_iter()
+        .filter(|(name, count)| ran.get(name).copied().unwrap_or_default() < *count)
+        .map(|(name, _)| name.to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SOURCE: &str = "fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds() {
+        assert_eq!(add(1, 2), 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn overflows() {
+        add(i32::MAX, 1);
+    }
+}
+";
+
+    #[test]
+    fn test_active_tests() {
+        assert_eq!(
+            active_tests(SOURCE),
+            BTreeMap::from([("adds", 1), ("overflows", 1)])
+        );
+        let ignored = SOURCE.replace("#[should_panic]", "#[ignore]");
+        assert_eq!(active_tests(&ignored), BTreeMap::from([("adds", 1)]));
+        let renamed = SOURCE.replace("fn adds()", "fn adds_numbers()");
+        assert!(active_tests(&renamed).contains_key("adds_numbers"));
+        assert!(active_tests("fn main() {}").is_empty());
+    }
+
+    #[test]
+    fn test_ran_tests() {
+        let output = ExerciseOutput {
+            stdout: String::from(
+                "running 2 tests\ntest tests::adds ... ok\ntest tests::overflows - should panic ... ok\n\ntest result: ok. 2 passed; 0 failed",
+            ),
+            stderr: String::new(),
+        };
+        assert_eq!(
+            ran_tests(&output),
+            BTreeMap::from([("adds", 1), ("overflows", 1)])
+        );
+        assert!(ran_tests(&ExerciseOutput::default()).is_empty());
+    }
+}
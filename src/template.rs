@@ -0,0 +1,102 @@
+use regex::{Captures, Regex};
+
+// The variables that exercise templates can contain, e.g. `{{exercise_name}}`
+const VARIABLES: &[&str] = &["course_name", "exercise_name", "topic", "hint"];
+
+pub const COMPILE_TEMPLATE: &str = "// {{exercise_name}}.rs
+//
+// TODO: Describe the task of the exercise.
+//
+// Execute `rustlings hint {{exercise_name}}` or use the `hint` watch subcommand for a hint.
+
+// I AM NOT DONE
+
+fn main() {}
+";
+
+pub const TEST_TEMPLATE: &str = "// {{exercise_name}}.rs
+//
+// TODO: Describe the task of the exercise.
+//
+// Make me pass the tests!
+//
+// Execute `rustlings hint {{exercise_name}}` or use the `hint` watch subcommand for a hint.
+
+// I AM NOT DONE
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_works() {}
+}
+";
+
+pub struct Variables<'a> {
+    pub course_name: &'a str,
+    pub exercise_name: &'a str,
+    pub topic: &'a str,
+}
+
+fn placeholder_regex() -> Regex {
+    Regex::new(&format!(r"\{{\{{\s*({})\s*\}}\}}", VARIABLES.join("|"))).unwrap()
+}
+
+// Replace the variables of the template. The `{{hint}}` markers are numbered
+// in their order, e.g. `// {{hint}} Look at the types` becomes `// Hint 1: Look at the types`.
+pub fn expand(template: &str, variables: &Variables) -> String {
+    let mut hints = 0;
+    placeholder_regex()
+        .replace_all(template, |captures: &Captures| match &captures[1] {
+            "course_name" => variables.course_name.to_string(),
+            "exercise_name" => variables.exercise_name.to_string(),
+            "topic" => variables.topic.to_string(),
+            _ => {
+                hints += 1;
+                format!("Hint {hints}:")
+            }
+        })
+        .into_owned()
+}
+
+// The variables that weren't expanded, e.g. in exercises that were copied from a template by hand
+pub fn placeholders(source: &str) -> Vec<String> {
+    placeholder_regex()
+        .find_iter(source)
+        .map(|found| found.as_str().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let variables = Variables {
+            course_name: "Rustlings",
+            exercise_name: "vecs3",
+            topic: "vecs",
+        };
+        assert_eq!(
+            expand(
+                "// {{exercise_name}} of {{ course_name }} about {{topic}}\n// {{hint}} a\n// {{hint}} b",
+                &variables
+            ),
+            "// vecs3 of Rustlings about vecs\n// Hint 1: a\n// Hint 2: b"
+        );
+        assert_eq!(
+            expand("println!(\"{{}}\");", &variables),
+            "println!(\"{{}}\");"
+        );
+    }
+
+    #[test]
+    fn test_placeholders() {
+        assert_eq!(
+            placeholders("// {{exercise_name}}\nprintln!(\"{{name}}\");"),
+            ["{{exercise_name}}"]
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use console::{style, StyledObject};
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+use crate::ui;
+
+// Red and green are hard to tell apart with the most common color vision deficiencies.
+// The colorblind theme uses blue and orange instead, which stay distinct, and adds cues
+// that don't depend on colors: glyphs like ✓ and ✗ and bold text for done exercises.
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum Theme {
+    #[default]
+    Default,
+    Colorblind,
+}
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+pub fn set(theme: Theme) {
+    let _ = THEME.set(theme);
+}
+
+fn theme() -> Theme {
+    *THEME.get_or_init(Theme::default)
+}
+
+// The colors of 256-color terminals
+const BLUE: u8 = 33;
+const ORANGE: u8 = 208;
+
+// Success, like a passing exercise
+pub fn good<D>(text: D) -> StyledObject<D> {
+    match theme() {
+        Theme::Default => style(text).green(),
+        Theme::Colorblind => style(text).color256(BLUE).bold(),
+    }
+}
+
+// Failure, like a failing exercise
+pub fn bad<D>(text: D) -> StyledObject<D> {
+    match theme() {
+        Theme::Default => style(text).red(),
+        Theme::Colorblind => style(text).color256(ORANGE),
+    }
+}
+
+// The colors of the done and the pending part of the progress bar in an `indicatif` template
+pub fn bar_colors() -> &'static str {
+    match theme() {
+        Theme::Default => "green/red",
+        Theme::Colorblind => "33/208",
+    }
+}
+
+// The padded status of an exercise in `rustlings list`, e.g. `Done   ` or `✓ Done   `
+pub fn list_status(status: &str, done: bool) -> String {
+    match theme() {
+        Theme::Default => format!("{status:<7}"),
+        Theme::Colorblind if done => style(format!("{} {status:<7}", ui::glyph("✓", "+")))
+            .bold()
+            .to_string(),
+        Theme::Colorblind => style(format!("{} {status:<7}", ui::glyph("✗", "x")))
+            .dim()
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_theme() {
+        #[derive(Deserialize)]
+        struct Config {
+            theme: Theme,
+        }
+        let config: Config = toml_edit::de::from_str(r#"theme = "colorblind""#).unwrap();
+        assert_eq!(config.theme, Theme::Colorblind);
+    }
+}
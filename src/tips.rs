@@ -0,0 +1,96 @@
+use console::style;
+use std::env;
+use std::fs;
+use std::process::{self, Command};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::exercise::{Exercise, Mode};
+
+// After an exercise passes, Clippy can suggest more idiomatic code.
+// The suggestions never fail the exercise. Watch mode only mentions how many there are
+// until they are shown with the `tips` command.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static LAST_TIPS: Mutex<String> = Mutex::new(String::new());
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+// The number of warnings without the summary like `warning: 2 warnings emitted`
+fn count(stderr: &str) -> usize {
+    stderr
+        .lines()
+        .filter_map(|line| line.strip_prefix("warning: "))
+        .filter(|message| !message.ends_with(" emitted"))
+        .count()
+}
+
+// The warnings of Clippy, or `None` if it isn't installed.
+// `clippy-driver` takes the same arguments as rustc.
+fn clippy_warnings(exercise: &Exercise) -> Option<String> {
+    let metadata = env::temp_dir().join(format!("rustlings_tips_{}.rmeta", process::id()));
+    let mut cmd = Command::new("clippy-driver");
+    if let Mode::Test = exercise.mode {
+        cmd.arg("--test");
+    }
+    let output = cmd
+        .arg(&exercise.path)
+        .args([
+            "--edition",
+            "2021",
+            "--emit=metadata",
+            "--color",
+            "never",
+            "-o",
+        ])
+        .arg(&metadata)
+        .output()
+        .ok()?;
+    let _ = fs::remove_file(&metadata);
+    Some(String::from_utf8_lossy(&output.stderr).to_string())
+}
+
+// Run Clippy on the exercise that just passed and mention its suggestions.
+pub fn check(exercise: &Exercise) {
+    // Clippy exercises must already satisfy Clippy.
+    if !ENABLED.load(Ordering::Relaxed) || matches!(exercise.mode, Mode::Clippy) {
+        return;
+    }
+
+    let tips = clippy_warnings(exercise).unwrap_or_default();
+    let count = count(&tips);
+    *LAST_TIPS.lock().unwrap() = if count > 0 { tips } else { String::new() };
+    if count > 0 {
+        println!(
+            "{}\n",
+            style(format!(
+                "Clippy has {count} style tips for this exercise. Type `tips` to show them."
+            ))
+            .dim()
+        );
+    }
+}
+
+// The suggestions for the last exercise that passed
+pub fn last() -> Option<String> {
+    let tips = LAST_TIPS.lock().unwrap();
+    (!tips.is_empty()).then(|| tips.clone())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_count() {
+        let stderr = "warning: length comparison to zero
+ --> exercises/vecs1.rs:3:8
+warning: useless use of `vec!`
+ --> exercises/vecs1.rs:2:13
+warning: 2 warnings emitted
+";
+        assert_eq!(count(stderr), 2);
+        assert_eq!(count(""), 0);
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt::{self, Display, Formatter};
+use std::process::Command;
+
+// A version as printed by `rustc --version` and `cargo --version`.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy)]
+pub struct Version {
+    major: u32,
+    minor: u32,
+    patch: u32,
+}
+
+impl Version {
+    // Parse versions like `1.70`, `1.77.2` or `1.79.0-nightly`.
+    pub fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.split('.');
+        let mut next_part = || -> Option<u32> {
+            match parts.next() {
+                Some(part) => {
+                    let digits = part.split(|c: char| !c.is_ascii_digit()).next()?;
+                    digits.parse().ok()
+                }
+                None => Some(0),
+            }
+        };
+
+        Some(Self {
+            major: next_part()?,
+            minor: next_part()?,
+            patch: next_part()?,
+        })
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+// The version of the given program, if it could be determined.
+fn installed_version(program: &str) -> Option<Version> {
+    let output = Command::new(program).arg("--version").output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    // For example `rustc 1.77.2 (25ef9e3d8 2024-04-09)`
+    Version::parse(stdout.split_whitespace().nth(1)?)
+}
+
+// Check that the installed `rustc` and `cargo` are at least the given version.
+// Programs whose version can't be determined are not reported.
+pub fn check_toolchain(minimum: Version) -> Result<(), String> {
+    for program in ["rustc", "cargo"] {
+        if let Some(installed) = installed_version(program) {
+            if installed < minimum {
+                return Err(format!(
+                    "Rustlings requires Rust {minimum} or newer, but `{program}` has the version {installed}.
+Some exercises use features of newer Rust versions and would fail with confusing errors.
+Run `rustup update` to update your Rust toolchain and try again."
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        let version = |major, minor, patch| Version {
+            major,
+            minor,
+            patch,
+        };
+
+        assert_eq!(Version::parse("1.70"), Some(version(1, 70, 0)));
+        assert_eq!(Version::parse("1.77.2"), Some(version(1, 77, 2)));
+        assert_eq!(Version::parse("1.79.0-nightly"), Some(version(1, 79, 0)));
+        assert_eq!(Version::parse("nightly"), None);
+        assert_eq!(Version::parse(""), None);
+    }
+
+    #[test]
+    fn test_version_order() {
+        assert!(Version::parse("1.70").unwrap() < Version::parse("1.70.1").unwrap());
+        assert!(Version::parse("1.9.0").unwrap() < Version::parse("1.10.0").unwrap());
+        assert!(Version::parse("2.0").unwrap() > Version::parse("1.99.9").unwrap());
+    }
+}
@@ -1,28 +1,223 @@
 macro_rules! print_emoji {
-    ($emoji:expr, $sign:expr, $color: ident, $fmt:literal, $ex:expr) => {{
-        use console::{style, Emoji};
-        use std::env;
+    ($emoji:expr, $sign:expr, $style:path, $fmt:literal, $ex:expr) => {{
+        use console::Emoji;
         let formatstr = format!($fmt, $ex);
-        if env::var("NO_EMOJI").is_ok() {
-            println!("{} {}", style($sign).$color(), style(formatstr).$color());
+        if $crate::ui::no_emoji() {
+            println!("{} {}", $style($sign), $style(formatstr));
         } else {
-            println!(
-                "{} {}",
-                style(Emoji($emoji, $sign)).$color(),
-                style(formatstr).$color()
-            );
+            println!("{} {}", $style(Emoji($emoji, $sign)), $style(formatstr));
         }
     }};
 }
 
 macro_rules! warn {
     ($fmt:literal, $ex:expr) => {{
-        print_emoji!("⚠️ ", "!", red, $fmt, $ex);
+        print_emoji!("⚠️ ", "!", $crate::theme::bad, $fmt, $ex);
     }};
 }
 
 macro_rules! success {
     ($fmt:literal, $ex:expr) => {{
-        print_emoji!("✅ ", "✓", green, $fmt, $ex);
+        print_emoji!(
+            "✅ ",
+            $crate::ui::glyph("✓", "+"),
+            $crate::theme::good,
+            $fmt,
+            $ex
+        );
     }};
 }
+
+// Messages that are printed before the output of a command, e.g. about restored files.
+// With `--porcelain` or `--json`, they go to stderr, so that stdout can still be parsed.
+macro_rules! notice {
+    ($($arg:tt)*) => {{
+        if $crate::ui::machine_readable() {
+            eprintln!($($arg)*);
+        } else {
+            println!($($arg)*);
+        }
+    }};
+}
+
+pub fn machine_readable() -> bool {
+    crate::porcelain::enabled() || crate::json::enabled()
+}
+
+// Clears the terminal with an ANSI escape code.
+// Works in UNIX and newer Windows terminals. Consoles without support for
+// escape codes (like the legacy Windows console) are cleared using the console API.
+pub fn clear_screen() {
+    if console::colors_enabled() {
+        println!("\x1Bc");
+    } else {
+        let _ = console::Term::stdout().clear_screen();
+    }
+}
+
+// Removes the escape codes from text if the terminal doesn't support them.
+pub fn plain_if_unsupported(text: &str) -> std::borrow::Cow<'_, str> {
+    if console::colors_enabled() {
+        text.into()
+    } else {
+        console::strip_ansi_codes(text)
+    }
+}
+
+// Emojis are disabled with the `NO_EMOJI` environment variable.
+// They are left out as well if the output is redirected, to keep it easy to process.
+pub fn no_emoji() -> bool {
+    std::env::var("NO_EMOJI").is_ok()
+        || !std::io::IsTerminal::is_terminal(&std::io::stdout())
+        || ascii()
+}
+
+// Symbols like ✓ and the block characters of the Fe-nish line are replaced with ASCII
+// with `--ascii` or if the terminal can't show them, e.g. because the locale isn't UTF-8.
+static ASCII: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+pub fn use_ascii() {
+    ASCII.store(true, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub fn ascii() -> bool {
+    if ASCII.load(std::sync::atomic::Ordering::Relaxed) {
+        return true;
+    }
+    let term = console::Term::stdout();
+    term.is_term() && !term.features().wants_emoji()
+}
+
+// `unicode`, or `ascii` if the terminal only supports ASCII
+pub fn glyph(unicode: &'static str, ascii: &'static str) -> &'static str {
+    if self::ascii() {
+        ascii
+    } else {
+        unicode
+    }
+}
+
+// Replaces the block characters of ASCII art like the Fe-nish line.
+pub fn ascii_art(text: &str) -> std::borrow::Cow<'_, str> {
+    if !ascii() {
+        return text.into();
+    }
+    text.chars()
+        .map(|c| match c {
+            '░' => '.',
+            '▒' => '#',
+            '▓' => '%',
+            '█' => '@',
+            c if c.is_ascii() => c,
+            _ => '?',
+        })
+        .collect::<String>()
+        .into()
+}
+
+// The spinner that is shown while an exercise is compiled or run
+pub fn spinner() -> indicatif::ProgressBar {
+    if crate::porcelain::enabled() {
+        return indicatif::ProgressBar::hidden();
+    }
+    let progress_bar = indicatif::ProgressBar::new_spinner();
+    if ascii() {
+        progress_bar.set_style(indicatif::ProgressStyle::default_spinner().tick_chars("-\\|/ "));
+    }
+    progress_bar
+}
+
+// The title of the terminal tab can show the progress in watch mode. The old title is saved
+// on the title stack of the terminal before it is changed the first time and restored on exit.
+static TITLE_CHANGED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+pub fn set_title(title: &str) {
+    use std::io::{IsTerminal, Write};
+    use std::sync::atomic::Ordering;
+
+    let mut stdout = std::io::stdout();
+    if !stdout.is_terminal() {
+        return;
+    }
+    if !TITLE_CHANGED.swap(true, Ordering::Relaxed) {
+        let _ = stdout.write_all(b"\x1b[22;0t");
+    }
+    let _ = write!(stdout, "\x1b]0;{title}\x07");
+    let _ = stdout.flush();
+}
+
+// Restore the title from before `set_title`. Called on exit, also after Ctrl+C and panics.
+pub fn reset_title() {
+    use std::io::Write;
+
+    if TITLE_CHANGED.swap(false, std::sync::atomic::Ordering::Relaxed) {
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(b"\x1b[23;0t");
+        let _ = stdout.flush();
+    }
+}
+
+// The interactive list is drawn on the alternate screen of the terminal, so that the
+// previous output is shown again when it is closed.
+static ALTERNATE_SCREEN: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+pub struct AlternateScreen;
+
+impl AlternateScreen {
+    // Also hides the cursor
+    pub fn enter() -> Self {
+        use std::io::Write;
+
+        ALTERNATE_SCREEN.store(true, std::sync::atomic::Ordering::Relaxed);
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(b"\x1b[?1049h\x1b[?25l");
+        let _ = stdout.flush();
+        Self
+    }
+}
+
+impl Drop for AlternateScreen {
+    fn drop(&mut self) {
+        leave_alternate_screen();
+    }
+}
+
+// Called on exit as well, since the `AlternateScreen` isn't dropped after Ctrl+C.
+pub fn leave_alternate_screen() {
+    use std::io::Write;
+
+    if ALTERNATE_SCREEN.swap(false, std::sync::atomic::Ordering::Relaxed) {
+        let mut stdout = std::io::stdout();
+        let _ = stdout.write_all(b"\x1b[?25h\x1b[?1049l");
+        let _ = stdout.flush();
+    }
+}
+
+// Shows long text in the pager from the `PAGER` environment variable (`less` by default).
+// The text is printed directly if the output isn't a terminal or the pager can't be started.
+pub fn page(text: &str) {
+    use std::io::{IsTerminal, Write};
+    use std::process::{Command, Stdio};
+
+    if std::io::stdout().is_terminal() {
+        let pager = std::env::var("PAGER").unwrap_or_else(|_| String::from("less -R"));
+        let mut parts = shlex::Shlex::new(&pager);
+        if let Some(program) = parts.next() {
+            let child = Command::new(program)
+                .args(parts)
+                .stdin(Stdio::piped())
+                .spawn();
+            if let Ok(mut child) = child {
+                if let Some(mut stdin) = child.stdin.take() {
+                    // The pager might be quit before reading everything.
+                    let _ = stdin.write_all(text.as_bytes());
+                }
+                if child.wait().is_ok_and(|status| status.success()) {
+                    return;
+                }
+            }
+        }
+    }
+
+    println!("{text}");
+}
@@ -0,0 +1,112 @@
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::exercise::Exercise;
+use crate::state_file::{self, ExerciseRecord, StateFile};
+
+// Copies of the exercises and their records from before destructive operations like resetting an exercise
+pub const UNDO_PATH: &str = ".rustlings-undo.json";
+
+fn path() -> PathBuf {
+    state_file::progress_path(UNDO_PATH)
+}
+
+// Only the most recent operations can be undone.
+const MAX_ENTRIES: usize = 10;
+
+#[derive(Serialize, Deserialize)]
+struct UndoEntry {
+    // What the operation did, e.g. `reset exercises/05_vecs/vecs1.rs`
+    description: String,
+    // Unix timestamp of the operation
+    timestamp: u64,
+    files: Vec<SavedFile>,
+    // The records of the exercises, `None` for exercises without a record
+    records: BTreeMap<String, Option<ExerciseRecord>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedFile {
+    path: PathBuf,
+    content: String,
+}
+
+fn load() -> Result<Vec<UndoEntry>> {
+    let path = path();
+    match fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+fn save(entries: &[UndoEntry]) -> Result<()> {
+    let content = serde_json::to_string(entries)?;
+    let path = path();
+    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+// Remember the files and the records of the exercises before an operation that changes them.
+// If they can't be saved, the user is asked to abort the operation by returning an error.
+pub fn push(description: &str, exercises: &[&Exercise]) -> Result<()> {
+    let files = exercises
+        .iter()
+        .map(|exercise| {
+            Ok(SavedFile {
+                path: exercise.path.clone(),
+                content: fs::read_to_string(&exercise.path)
+                    .with_context(|| format!("Failed to read {exercise}"))?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let state = StateFile::load()?;
+    let records = exercises
+        .iter()
+        .map(|exercise| {
+            let record = state.exercises.get(&exercise.name).cloned();
+            (exercise.name.clone(), record)
+        })
+        .collect();
+
+    let mut entries = load()?;
+    entries.push(UndoEntry {
+        description: description.to_string(),
+        timestamp: state_file::now(),
+        files,
+        records,
+    });
+    let excess = entries.len().saturating_sub(MAX_ENTRIES);
+    entries.drain(..excess);
+    save(&entries)
+}
+
+// Restore the exercises and their records from before the last destructive operation.
+// The rest of the progress, e.g. exercises completed since then, is kept.
+pub fn undo() -> Result<()> {
+    let mut entries = load()?;
+    let Some(entry) = entries.pop() else {
+        bail!("There is nothing to undo");
+    };
+
+    for file in &entry.files {
+        fs::write(&file.path, &file.content)
+            .with_context(|| format!("Failed to restore {}", file.path.display()))?;
+    }
+    state_file::update(|state| {
+        for (name, record) in entry.records {
+            match record {
+                Some(record) => state.exercises.insert(name, record),
+                None => state.exercises.remove(&name),
+            };
+        }
+    });
+    save(&entries)?;
+
+    println!("Undid: {}", entry.description);
+    Ok(())
+}
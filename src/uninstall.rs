@@ -0,0 +1,207 @@
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use crate::exercise::{cargo_target_dir, Exercise, CLIPPY_DIR, FULL_OUTPUT_PATH, NEXTEST_DIR};
+use crate::state_file::StateFile;
+
+// The directory that solved exercises are moved to when removing the exercises.
+const SOLUTIONS_DIR: &str = "rustlings-solutions";
+// Where older versions wrote the Cargo package of the Clippy exercises
+const LEGACY_CLIPPY_DIR: &str = "./exercises/22_clippy";
+
+// Remove the files generated while running exercises.
+// If `all` is set, the exercises and `info.toml` are removed as well.
+// Solved exercises are moved to `SOLUTIONS_DIR` if `keep_solutions` is set.
+pub fn uninstall(exercises: &[Exercise], all: bool, keep_solutions: bool, yes: bool) -> Result<()> {
+    let mut targets = generated_files()?;
+    if all {
+        targets.extend(
+            ["exercises", "info.toml"]
+                .into_iter()
+                .map(PathBuf::from)
+                .filter(|path| path.exists()),
+        );
+    }
+
+    if targets.is_empty() {
+        println!("Nothing to remove");
+        return Ok(());
+    }
+
+    println!("The following files and directories will be removed:");
+    for target in &targets {
+        println!("  {}", target.display());
+    }
+    if all && keep_solutions {
+        println!("Solved exercises will be moved to {SOLUTIONS_DIR}/ first.");
+    }
+    if !yes && !confirm()? {
+        println!("Aborted");
+        return Ok(());
+    }
+
+    if all && keep_solutions {
+        let state = StateFile::load().unwrap_or_default();
+        for exercise in exercises.iter().filter(|e| e.looks_done(&state)) {
+            let dest = Path::new(SOLUTIONS_DIR).join(&exercise.path);
+            if let Some(dir) = dest.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::copy(&exercise.path, &dest)
+                .with_context(|| format!("Failed to keep the solution of {exercise}"))?;
+        }
+    }
+
+    for target in &targets {
+        let result = if target.is_dir() {
+            fs::remove_dir_all(target)
+        } else {
+            fs::remove_file(target)
+        };
+        result.with_context(|| format!("Failed to remove {}", target.display()))?;
+    }
+
+    if all {
+        println!("To remove the `rustlings` command too, run `cargo uninstall rustlings`");
+    }
+
+    Ok(())
+}
+
+// Remove the build artifacts and caches, which can take gigabytes, and report how much space
+// was reclaimed. Unlike `uninstall`, the files of `rustlings lsp` and the progress are kept.
+pub fn clean() -> Result<()> {
+    let targets = build_artifacts()?;
+    if targets.is_empty() {
+        println!("Nothing to clean");
+        return Ok(());
+    }
+
+    let mut reclaimed = 0;
+    for target in &targets {
+        let size = disk_usage(target);
+        let result = if target.is_dir() {
+            fs::remove_dir_all(target)
+        } else {
+            fs::remove_file(target)
+        };
+        result.with_context(|| format!("Failed to remove {}", target.display()))?;
+        println!("Removed {} ({})", target.display(), format_size(size));
+        reclaimed += size;
+    }
+    println!("Reclaimed {}", format_size(reclaimed));
+
+    Ok(())
+}
+
+// The total size of the files in the directory, or the size of the file
+fn disk_usage(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| disk_usage(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.1} {}", UNITS[unit])
+}
+
+// Files created by compiling, running and testing exercises
+fn build_artifacts() -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+
+    for entry in fs::read_dir(".")? {
+        let path = entry?.path();
+        let is_temp_file = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("temp_"));
+        if is_temp_file {
+            files.push(path);
+        }
+    }
+
+    let legacy_clippy_dir = Path::new(LEGACY_CLIPPY_DIR);
+    files.extend(
+        [
+            cargo_target_dir(),
+            PathBuf::from(NEXTEST_DIR),
+            PathBuf::from(CLIPPY_DIR),
+            PathBuf::from(FULL_OUTPUT_PATH),
+            legacy_clippy_dir.join("Cargo.lock"),
+            // Where older versions built the Clippy exercises
+            legacy_clippy_dir.join("target"),
+        ]
+        .into_iter()
+        .filter(|path| path.exists()),
+    );
+
+    Ok(files)
+}
+
+// Files created by compiling exercises and by `rustlings lsp`.
+fn generated_files() -> Result<Vec<PathBuf>> {
+    let mut files = build_artifacts()?;
+    files.extend(
+        [
+            PathBuf::from("rust-project.json"),
+            Path::new(LEGACY_CLIPPY_DIR).join("Cargo.toml"),
+        ]
+        .into_iter()
+        .filter(|path| path.exists()),
+    );
+
+    Ok(files)
+}
+
+// Prompts are suppressed if the input or output is redirected, since nobody might see them
+// or answer them.
+fn confirm() -> io::Result<bool> {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        println!("Pass --yes to confirm when the input or output is redirected.");
+        return Ok(false);
+    }
+
+    print!("Do you want to continue? [y/N] ");
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim(), "y" | "Y" | "yes"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+    }
+}
@@ -0,0 +1,150 @@
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use std::fs;
+use std::process;
+use tracing::trace;
+
+use crate::exercise::Exercise;
+use crate::shuffle::generator;
+use crate::state_file;
+
+// A parameter of an exercise that differs between learners, so that answers can't just be
+// copied in a classroom. This is deserialized from the `params` table of an exercise,
+// e.g. `params = { count = { min = 3, max = 9 }, fruit = ["apple", "banana"] }`.
+#[derive(Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(untagged)]
+pub enum Param {
+    // A number between `min` and `max`, both included
+    Range { min: i64, max: i64 },
+    // One of the values, e.g. names
+    Choices(Vec<String>),
+}
+
+impl Param {
+    fn value(&self, mut next: impl FnMut() -> u64) -> String {
+        match self {
+            Self::Range { min, max } => {
+                let span = min.abs_diff(*max).saturating_add(1);
+                min.min(max)
+                    .wrapping_add((next() % span) as i64)
+                    .to_string()
+            }
+            Self::Choices(choices) if choices.is_empty() => String::new(),
+            Self::Choices(choices) => choices[(next() % choices.len() as u64) as usize].clone(),
+        }
+    }
+}
+
+// The FNV-1a hash, so that each parameter of each exercise gets its own numbers
+fn hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+// The seed of the learner. It is created the first time and stored in the state file,
+// so that the exercises stay the same after resetting them.
+fn seed() -> u64 {
+    let mut seed = 0;
+    state_file::update(|state| {
+        seed = *state
+            .variant_seed
+            .get_or_insert_with(|| generator(state_file::now() ^ u64::from(process::id()))());
+    });
+    seed
+}
+
+// Replace the parameters of the exercise, e.g. `{{count}}`, with the values for the seed
+pub fn expand(exercise: &Exercise, source: &str, seed: u64) -> String {
+    if exercise.params.is_empty() {
+        return source.to_string();
+    }
+    let names: Vec<String> = exercise
+        .params
+        .keys()
+        .map(|name| regex::escape(name))
+        .collect();
+    let regex = Regex::new(&format!(r"\{{\{{\s*({})\s*\}}\}}", names.join("|"))).unwrap();
+    regex
+        .replace_all(source, |captures: &Captures| {
+            let name = &captures[1];
+            let next = generator(seed ^ hash(&format!("{}/{name}", exercise.name)));
+            exercise.params[name].value(next)
+        })
+        .into_owned()
+}
+
+// The variant of the learner of a pristine exercise or solution
+pub fn instantiate(exercise: &Exercise, source: &str) -> String {
+    if exercise.params.is_empty() {
+        return source.to_string();
+    }
+    expand(exercise, source, seed())
+}
+
+// Instantiate the exercises and their solutions that still contain parameters, e.g. since
+// the course was just cloned or an exercise was restored. Called when Rustlings starts.
+pub fn instantiate_all(exercises: &[Exercise]) {
+    let with_params: Vec<&Exercise> = exercises.iter().filter(|e| !e.params.is_empty()).collect();
+    if with_params.is_empty() {
+        return;
+    }
+
+    let seed = seed();
+    for exercise in with_params {
+        for path in [exercise.path.clone(), exercise.solution_path()] {
+            let Ok(source) = fs::read_to_string(&path) else {
+                continue;
+            };
+            let variant = expand(exercise, &source, seed);
+            if variant != source {
+                trace!("Instantiating the parameters of {}", path.display());
+                if let Err(e) = fs::write(&path, variant) {
+                    notice!("Failed to write the variant of {}: {e}", path.display());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::exercise::Mode;
+    use std::collections::BTreeMap;
+
+    fn exercise() -> Exercise {
+        let mut exercise = Exercise::for_test("vecs5", "vecs5.rs", Mode::Test);
+        exercise.params = BTreeMap::from([
+            (String::from("count"), Param::Range { min: 3, max: 9 }),
+            (
+                String::from("fruit"),
+                Param::Choices(vec![String::from("apple"), String::from("banana")]),
+            ),
+        ]);
+        exercise
+    }
+
+    #[test]
+    fn test_expand() {
+        let exercise = exercise();
+        let source = "let fruit = \"{{fruit}}\"; // {{ count }} {{count}} {{other}}";
+        let variant = expand(&exercise, source, 42);
+        assert_eq!(variant, expand(&exercise, source, 42));
+        assert!(variant.ends_with("{{other}}"));
+
+        let count: Vec<&str> = variant.split(' ').skip(5).take(2).collect();
+        assert_eq!(count[0], count[1]);
+        assert!((3..=9).contains(&count[0].parse::<i64>().unwrap()));
+        assert!(variant.contains("\"apple\"") || variant.contains("\"banana\""));
+    }
+
+    #[test]
+    fn test_seeds_give_different_variants() {
+        let exercise = exercise();
+        let variants: Vec<String> = (0..20)
+            .map(|seed| expand(&exercise, "{{count}}", seed))
+            .collect();
+        assert!(variants.iter().any(|variant| *variant != variants[0]));
+    }
+}
@@ -1,7 +1,129 @@
-use crate::exercise::{CompiledExercise, Exercise, Mode, State};
+use crate::exercise::{uses_not_done_marker, Exercise, ExerciseOutput, Mode, State};
+use crate::exit_code;
+use crate::explain::record_error_codes;
+use crate::history::{self, Outcome};
+use crate::porcelain;
+use crate::shutdown;
+use crate::state_file;
+use crate::tamper;
+use crate::theme;
+use crate::tips;
+use crate::ui;
 use console::style;
 use indicatif::{ProgressBar, ProgressStyle};
-use std::{env, time::Duration};
+use std::time::{Duration, Instant};
+
+// What verifying an exercise found
+#[derive(PartialEq, Eq, Debug)]
+pub enum Verdict {
+    Passed,
+    // Passes, but still contains the "I AM NOT DONE" comment
+    NotDone,
+    // Passes, but doesn't run all of its tests anymore
+    TestsRemoved(Vec<String>),
+    CompilationFailed,
+    // Compiles, but running it or its tests failed
+    Failed,
+}
+
+// An exercise that was verified
+pub struct Verified<'a> {
+    pub exercise: &'a Exercise,
+    pub verdict: Verdict,
+    // Like the results of `--porcelain`, e.g. `passed` or `not-done`
+    pub result: &'static str,
+    pub duration: Duration,
+    pub output: ExerciseOutput,
+}
+
+// Shows the verification of the exercises. `verify` and `verify_quietly` share how the exercises
+// are verified and how the progress is recorded, and only differ in their reporter.
+trait Reporter<'a> {
+    // Called before the exercise is compiled and before it is run
+    fn compiling(&mut self, _exercise: &Exercise) {}
+    fn running(&mut self, _exercise: &Exercise) {}
+    // Ctrl+C stopped the exercise
+    fn cancelled(&mut self, _exercise: &Exercise) {}
+    fn verified(&mut self, verified: Verified<'a>);
+}
+
+// Verify the exercises in order until one of them isn't done, and record the progress.
+fn verify_with<'a>(
+    exercises: impl IntoIterator<Item = &'a Exercise>,
+    reporter: &mut impl Reporter<'a>,
+) -> Result<(), &'a Exercise> {
+    for exercise in exercises {
+        let start = Instant::now();
+        reporter.compiling(exercise);
+        let result = match exercise.compile() {
+            Ok(_) if matches!(exercise.mode, Mode::Clippy) => Ok(ExerciseOutput::default()),
+            Ok(compiled) => {
+                reporter.running(exercise);
+                compiled.run().map_err(|output| (Verdict::Failed, output))
+            }
+            Err(output) => Err((Verdict::CompilationFailed, output)),
+        };
+        if shutdown::take_cancelled() {
+            reporter.cancelled(exercise);
+            return Err(exercise);
+        }
+
+        let (verdict, output) = match result {
+            Ok(output) => {
+                let removed = tamper::removed_tests(exercise, &output);
+                if !removed.is_empty() {
+                    (Verdict::TestsRemoved(removed), output)
+                } else if uses_not_done_marker() && matches!(exercise.state(), State::Pending(_)) {
+                    (Verdict::NotDone, output)
+                } else {
+                    (Verdict::Passed, output)
+                }
+            }
+            Err(failure) => failure,
+        };
+        let result = match &verdict {
+            Verdict::Passed => porcelain::result(None),
+            Verdict::NotDone => "not-done",
+            Verdict::TestsRemoved(_) => "tests-removed",
+            Verdict::CompilationFailed | Verdict::Failed => {
+                porcelain::result(exit_code::last_failure())
+            }
+        };
+
+        match verdict {
+            Verdict::CompilationFailed => record_error_codes(&exercise.name, &output.stderr),
+            _ if matches!(exercise.mode, Mode::Test) => {
+                state_file::record_parts(&exercise.name, &exercise.parts_done(&output));
+            }
+            _ => (),
+        }
+        let failed = matches!(verdict, Verdict::CompilationFailed | Verdict::Failed);
+        let outcome = if failed {
+            Outcome::Failed
+        } else {
+            Outcome::Passed
+        };
+        history::record(&exercise.name, "verify", outcome, start);
+        let done = verdict == Verdict::Passed;
+        if done {
+            state_file::record_completion(&exercise.name);
+        } else if failed {
+            state_file::record_failure(&exercise.name);
+        }
+
+        reporter.verified(Verified {
+            exercise,
+            verdict,
+            result,
+            duration: start.elapsed(),
+            output,
+        });
+        if !done {
+            return Err(exercise);
+        }
+    }
+    Ok(())
+}
 
 // Verify that the provided container of Exercise objects
 // can be compiled and run without any failures.
@@ -16,108 +138,173 @@ pub fn verify<'a>(
 ) -> Result<(), &'a Exercise> {
     let (num_done, total) = progress;
     let bar = ProgressBar::new(total as u64);
-    let mut percentage = num_done as f32 / total as f32 * 100.0;
     bar.set_style(
         ProgressStyle::default_bar()
-            .template("Progress: [{bar:60.green/red}] {pos}/{len} {msg}")
+            .template(&format!(
+                "Progress: [{{bar:60.{}}}] {{pos}}/{{len}} {{msg}}",
+                theme::bar_colors()
+            ))
             .expect("Progressbar template should be valid!")
             .progress_chars("#>-"),
     );
     bar.set_position(num_done as u64);
-    bar.set_message(format!("({percentage:.1} %)"));
+    let mut reporter = Interactive {
+        bar,
+        total,
+        percentage: num_done as f32 / total as f32 * 100.0,
+        spinner: ProgressBar::hidden(),
+        verbose,
+        success_hints,
+    };
+    reporter
+        .bar
+        .set_message(format!("({:.1} %)", reporter.percentage));
+    verify_with(exercises, &mut reporter)
+}
 
-    for exercise in exercises {
-        let compile_result = match exercise.mode {
-            Mode::Test => compile_and_test(exercise, RunMode::Interactive, verbose, success_hints),
-            Mode::Compile => compile_and_run_interactively(exercise, success_hints),
-            Mode::Clippy => compile_only(exercise, success_hints),
-        };
-        if !compile_result.unwrap_or(false) {
-            return Err(exercise);
-        }
-        percentage += 100.0 / total as f32;
-        bar.inc(1);
-        bar.set_message(format!("({percentage:.1} %)"));
-        if bar.position() == total as u64 {
-            println!(
-                "Progress: You completed {} / {} exercises ({:.1} %).",
-                bar.position(),
-                total,
-                percentage
-            );
-            bar.finish();
+// Shows the progress bar and the output of the exercises that aren't done
+struct Interactive {
+    bar: ProgressBar,
+    total: usize,
+    percentage: f32,
+    // Shown while the current exercise is compiled and run
+    spinner: ProgressBar,
+    verbose: bool,
+    success_hints: bool,
+}
+
+impl<'a> Reporter<'a> for Interactive {
+    fn compiling(&mut self, exercise: &Exercise) {
+        self.spinner = ui::spinner();
+        self.spinner.set_message(match exercise.mode {
+            Mode::Test => format!("Testing {exercise}..."),
+            Mode::Compile | Mode::Clippy => format!("Compiling {exercise}..."),
+        });
+        self.spinner.enable_steady_tick(Duration::from_millis(100));
+    }
+
+    fn running(&mut self, exercise: &Exercise) {
+        if matches!(exercise.mode, Mode::Compile) {
+            self.spinner.set_message(format!("Running {exercise}..."));
         }
     }
-    Ok(())
-}
 
-#[derive(PartialEq, Eq)]
-enum RunMode {
-    Interactive,
-    NonInteractive,
-}
+    fn cancelled(&mut self, exercise: &Exercise) {
+        self.spinner.finish_and_clear();
+        warn!("Stopped {} since Ctrl+C was pressed", exercise);
+        println!("Save the exercise to run it again or press Ctrl+C again to quit.\n");
+    }
 
-// Compile and run the resulting test harness of the given Exercise
-pub fn test(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
-    compile_and_test(exercise, RunMode::NonInteractive, verbose, false)?;
-    Ok(())
+    fn verified(&mut self, verified: Verified<'a>) {
+        self.spinner.finish_and_clear();
+        let Verified {
+            exercise,
+            verdict,
+            output,
+            ..
+        } = verified;
+        if matches!(exercise.mode, Mode::Test) && self.verbose && !is_failure(&verdict) {
+            println!("{}", output.stdout);
+        }
+        match verdict {
+            Verdict::Passed => {
+                self.percentage += 100.0 / self.total as f32;
+                self.bar.inc(1);
+                self.bar.set_message(format!("({:.1} %)", self.percentage));
+                if self.bar.position() == self.total as u64 {
+                    println!(
+                        "Progress: You completed {} / {} exercises ({:.1} %).",
+                        self.bar.position(),
+                        self.total,
+                        self.percentage
+                    );
+                    self.bar.finish();
+                }
+            }
+            Verdict::NotDone => {
+                let prompt_output =
+                    (matches!(exercise.mode, Mode::Compile)).then_some(output.stdout);
+                prompt_for_completion(exercise, prompt_output, self.success_hints);
+            }
+            Verdict::TestsRemoved(removed) => warn_about_removed_tests(exercise, &removed),
+            Verdict::CompilationFailed | Verdict::Failed => {
+                print_failure(exercise, &verdict, &output);
+            }
+        }
+    }
 }
 
-// Invoke the rust compiler without running the resulting binary
-fn compile_only(exercise: &Exercise, success_hints: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(Duration::from_millis(100));
-
-    let _ = compile(exercise, &progress_bar)?;
-    progress_bar.finish_and_clear();
+// Passes each verified exercise to a closure
+struct Quiet<F>(F);
 
-    Ok(prompt_for_completion(exercise, None, success_hints))
+impl<'a, F: FnMut(Verified<'a>)> Reporter<'a> for Quiet<F> {
+    fn verified(&mut self, verified: Verified<'a>) {
+        (self.0)(verified);
+    }
 }
 
-// Compile the given Exercise and run the resulting binary in an interactive mode
-fn compile_and_run_interactively(exercise: &Exercise, success_hints: bool) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
-    progress_bar.set_message(format!("Compiling {exercise}..."));
-    progress_bar.enable_steady_tick(Duration::from_millis(100));
+// Verify the exercises like `verify`, but without any output. Each verified exercise
+// is passed to `report` instead, for `--porcelain` and `--json`.
+pub fn verify_quietly<'a>(
+    exercises: impl IntoIterator<Item = &'a Exercise>,
+    report: impl FnMut(Verified<'a>),
+) -> Result<(), &'a Exercise> {
+    verify_with(exercises, &mut Quiet(report))
+}
 
-    let compilation = compile(exercise, &progress_bar)?;
+fn is_failure(verdict: &Verdict) -> bool {
+    matches!(verdict, Verdict::CompilationFailed | Verdict::Failed)
+}
 
-    progress_bar.set_message(format!("Running {exercise}..."));
-    let result = compilation.run();
-    progress_bar.finish_and_clear();
+// An exercise whose tests were removed isn't done, even if it passes.
+fn warn_about_removed_tests(exercise: &Exercise, removed: &[String]) {
+    warn!(
+        "{} passes, but it doesn't run all of its tests anymore",
+        exercise
+    );
+    println!(
+        "These tests didn't run since they were removed, renamed, ignored or left out: {}\nRestore them to complete the exercise.\n",
+        removed.join(", ")
+    );
+}
 
-    let output = match result {
-        Ok(output) => output,
-        Err(output) => {
+fn print_failure(exercise: &Exercise, verdict: &Verdict, output: &ExerciseOutput) {
+    match (verdict, exercise.mode) {
+        (Verdict::CompilationFailed, _) => {
+            warn!(
+                "Compiling of {} failed! Please try again. Here's the output:",
+                exercise
+            );
+            println!("{}", output.stderr);
+        }
+        (_, Mode::Test) => {
+            warn!(
+                "Testing of {} failed! Please try again. Here's the output:",
+                exercise
+            );
+            println!("{}", output.stdout);
+            if let Some(counts) = output.test_counts() {
+                println!("{}", style(counts).bold());
+            }
+            print_parts(exercise, output);
+        }
+        _ => {
             warn!("Ran {} with errors", exercise);
             println!("{}", output.stdout);
             println!("{}", output.stderr);
-            return Err(());
         }
-    };
-
-    Ok(prompt_for_completion(
-        exercise,
-        Some(output.stdout),
-        success_hints,
-    ))
+    }
 }
 
-// Compile the given Exercise as a test harness and display
-// the output if verbose is set to true
-fn compile_and_test(
-    exercise: &Exercise,
-    run_mode: RunMode,
-    verbose: bool,
-    success_hints: bool,
-) -> Result<bool, ()> {
-    let progress_bar = ProgressBar::new_spinner();
+// Compile and run the resulting test harness of the given Exercise
+pub fn test(exercise: &Exercise, verbose: bool) -> Result<(), ()> {
+    let progress_bar = ui::spinner();
     progress_bar.set_message(format!("Testing {exercise}..."));
     progress_bar.enable_steady_tick(Duration::from_millis(100));
-
-    let compilation = compile(exercise, &progress_bar)?;
-    let result = compilation.run();
+    let result = match exercise.compile() {
+        Ok(compiled) => compiled.run().map_err(|output| (Verdict::Failed, output)),
+        Err(output) => Err((Verdict::CompilationFailed, output)),
+    };
     progress_bar.finish_and_clear();
 
     match result {
@@ -125,53 +312,57 @@ fn compile_and_test(
             if verbose {
                 println!("{}", output.stdout);
             }
-            if run_mode == RunMode::Interactive {
-                Ok(prompt_for_completion(exercise, None, success_hints))
+            state_file::record_parts(&exercise.name, &exercise.parts_done(&output));
+            Ok(())
+        }
+        Err(_) if shutdown::cancelled() => Err(()),
+        Err((verdict, output)) => {
+            if verdict == Verdict::CompilationFailed {
+                record_error_codes(&exercise.name, &output.stderr);
             } else {
-                Ok(true)
+                state_file::record_parts(&exercise.name, &exercise.parts_done(&output));
             }
-        }
-        Err(output) => {
-            warn!(
-                "Testing of {} failed! Please try again. Here's the output:",
-                exercise
-            );
-            println!("{}", output.stdout);
+            print_failure(exercise, &verdict, &output);
             Err(())
         }
     }
 }
 
-// Compile the given Exercise and return an object with information
-// about the state of the compilation
-fn compile<'a>(
-    exercise: &'a Exercise,
-    progress_bar: &ProgressBar,
-) -> Result<CompiledExercise<'a>, ()> {
-    let compilation_result = exercise.compile();
+// Show which part of a multi-part exercise the learner is working on, e.g. `Part 2 of 3: parsing`
+fn print_parts(exercise: &Exercise, output: &ExerciseOutput) {
+    let done = exercise.parts_done(output);
+    let Some((index, current)) = exercise
+        .parts
+        .iter()
+        .enumerate()
+        .find(|(_, part)| !done.contains(&part.as_str()))
+    else {
+        return;
+    };
 
-    match compilation_result {
-        Ok(compilation) => Ok(compilation),
-        Err(output) => {
-            progress_bar.finish_and_clear();
-            warn!(
-                "Compiling of {} failed! Please try again. Here's the output:",
-                exercise
-            );
-            println!("{}", output.stderr);
-            Err(())
+    println!(
+        "\n{}",
+        style(format!(
+            "Part {} of {}: {current}",
+            index + 1,
+            exercise.parts.len()
+        ))
+        .bold()
+    );
+    for part in &exercise.parts {
+        if done.contains(&part.as_str()) {
+            println!("  {} {part}", theme::good(ui::glyph("✓", "+")));
+        } else if part == current {
+            println!("  {} {}", ui::glyph("→", ">"), style(part).bold());
+        } else {
+            println!("    {}", style(part).dim());
         }
     }
 }
 
-fn prompt_for_completion(
-    exercise: &Exercise,
-    prompt_output: Option<String>,
-    success_hints: bool,
-) -> bool {
-    let context = match exercise.state() {
-        State::Done => return true,
-        State::Pending(context) => context,
+fn prompt_for_completion(exercise: &Exercise, prompt_output: Option<String>, success_hints: bool) {
+    let State::Pending(context) = exercise.state() else {
+        return;
     };
     match exercise.mode {
         Mode::Compile => success!("Successfully ran {}!", exercise),
@@ -179,7 +370,7 @@ fn prompt_for_completion(
         Mode::Clippy => success!("Successfully compiled {}!", exercise),
     }
 
-    let no_emoji = env::var("NO_EMOJI").is_ok();
+    let no_emoji = crate::ui::no_emoji();
 
     let clippy_success_msg = if no_emoji {
         "The code is compiling, and Clippy is happy!"
@@ -212,6 +403,7 @@ fn prompt_for_completion(
             separator = separator(),
         );
     }
+    tips::check(exercise);
 
     println!("You can keep working on this exercise,");
     println!(
@@ -233,8 +425,6 @@ fn prompt_for_completion(
             formatted_line,
         );
     }
-
-    false
 }
 
 fn separator() -> console::StyledObject<&'static str> {
@@ -0,0 +1,134 @@
+use glob::{MatchOptions, Pattern};
+use notify_debouncer_mini::notify::{self, PollWatcher, RecommendedWatcher, Watcher};
+use notify_debouncer_mini::{new_debouncer_opt, Config, DebounceEventHandler, Debouncer};
+use std::env;
+use std::path::Path;
+use std::time::Duration;
+use tracing::trace;
+
+// The native file watcher misses the changes on network file systems and in some
+// bind mounts of containers (NFS, Docker on macOS and Windows, WSL2 with Windows files).
+// Polling finds them by scanning the watched files regularly.
+pub enum FileWatcher {
+    Native(Debouncer<RecommendedWatcher>),
+    Poll(Debouncer<PollWatcher>),
+}
+
+impl FileWatcher {
+    // Poll every `poll` interval if it is set, otherwise use the watcher of the OS.
+    pub fn new<F: DebounceEventHandler>(
+        debounce: Duration,
+        poll: Option<Duration>,
+        event_handler: F,
+    ) -> notify::Result<Self> {
+        let config = Config::default().with_timeout(debounce);
+        match poll {
+            Some(interval) => {
+                trace!("Polling for file changes every {interval:?}");
+                // Comparing the contents is cheap for the small exercise files and
+                // doesn't depend on the modification times, which are coarse on some file systems.
+                let notify_config = notify::Config::default()
+                    .with_poll_interval(interval)
+                    .with_compare_contents(true);
+                new_debouncer_opt(config.with_notify_config(notify_config), event_handler)
+                    .map(Self::Poll)
+            }
+            None => {
+                trace!("Using the file watcher of the OS");
+                new_debouncer_opt(config, event_handler).map(Self::Native)
+            }
+        }
+    }
+
+    pub fn watcher(&mut self) -> &mut dyn Watcher {
+        match self {
+            Self::Native(debouncer) => debouncer.watcher(),
+            Self::Poll(debouncer) => debouncer.watcher(),
+        }
+    }
+}
+
+// Editors and tools create files next to the exercises that aren't edits of the learner,
+// e.g. the lock files of Emacs like `.#intro1.rs`. Courses and users can add more patterns.
+pub const DEFAULT_IGNORE: &[&str] = &["target/", ".#*", "#*#"];
+
+// Whether the path matches one of the glob patterns.
+// Patterns without a `/` match any component of the path, like `*.bak` or `target`.
+// Other patterns match the path relative to the Rustlings directory or one of its parent
+// directories, like `exercises/generated/**`. `*` doesn't match `/`, while `**` does.
+// A trailing `/` is ignored since everything in a matched directory is ignored anyway.
+pub fn is_ignored<S: AsRef<str>>(path: &Path, patterns: &[S]) -> bool {
+    let path = env::current_dir()
+        .ok()
+        .and_then(|dir| path.strip_prefix(dir).ok())
+        .unwrap_or(path);
+    let path = path.strip_prefix(".").unwrap_or(path);
+    let path = path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.as_ref().trim_end_matches('/');
+        if pattern.contains('/') {
+            let pattern = pattern.trim_start_matches('/');
+            glob_match(pattern, &path)
+                || path
+                    .match_indices('/')
+                    .any(|(ind, _)| glob_match(pattern, &path[..ind]))
+        } else {
+            path.split('/')
+                .any(|component| glob_match(pattern, component))
+        }
+    })
+}
+
+// Invalid patterns like `a**b` don't match anything.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let options = MatchOptions {
+        require_literal_separator: true,
+        ..MatchOptions::new()
+    };
+    Pattern::new(pattern).is_ok_and(|pattern| pattern.matches_with(text, options))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("*.rs", "intro1.rs"));
+        assert!(!glob_match("*.rs", "intro1.rs.bak"));
+        assert!(glob_match("intro?.rs", "intro1.rs"));
+        assert!(!glob_match("*", "a/b"));
+        assert!(glob_match("exercises/**/gen_*.rs", "exercises/gen_a.rs"));
+        assert!(glob_match(
+            "exercises/**/gen_*.rs",
+            "exercises/01/02/gen_a.rs"
+        ));
+        assert!(glob_match("**", "a/b/c"));
+        assert!(glob_match("intro[12].rs", "intro2.rs"));
+        assert!(!glob_match("a**b", "ab"));
+    }
+
+    #[test]
+    fn test_is_ignored() {
+        let patterns = ["target/", ".#*", "exercises/generated"];
+        assert!(is_ignored(Path::new("./target/debug/build.rs"), &patterns));
+        assert!(is_ignored(
+            Path::new("exercises/00_intro/.#intro1.rs"),
+            &patterns
+        ));
+        assert!(is_ignored(Path::new("exercises/generated/a.rs"), &patterns));
+        assert!(!is_ignored(
+            Path::new("exercises/00_intro/intro1.rs"),
+            &patterns
+        ));
+        assert!(!is_ignored(
+            Path::new("other/exercises/generated/a.rs"),
+            &patterns
+        ));
+    }
+}
@@ -0,0 +1,7 @@
+fn add(a: i32, b: i32) -> __???return_type__ {
+    a __???__ b
+}
+
+fn main() {
+    println!("{}", add(1, 2));
+}
@@ -0,0 +1,6 @@
+// I AM NOT DONE
+
+fn main() {
+    let number: u8 = 1;
+    println!("{number}");
+}
@@ -0,0 +1,8 @@
+fn main() {
+    let number: Option<u8> = Some(1);
+    // Print the number if there is one.
+    match number {
+        Some(number) => println!("{number}"),
+        None => (),
+    }
+}
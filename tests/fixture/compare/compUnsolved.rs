@@ -0,0 +1,4 @@
+fn main() {
+    let number: u8 = "1";
+    println!("{number}");
+}
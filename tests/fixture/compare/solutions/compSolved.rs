@@ -0,0 +1,6 @@
+fn main() {
+    let number: Option<u8> = Some(1);
+    if let Some(number) = number {
+        println!("{number}");
+    }
+}
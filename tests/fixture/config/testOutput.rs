@@ -0,0 +1,5 @@
+#[test]
+fn passing() {
+    println!("THE CONFIG SHOWS THIS OUTPUT");
+    assert!(true);
+}
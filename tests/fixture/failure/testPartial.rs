@@ -0,0 +1,9 @@
+#[test]
+fn passing() {
+    assert!(true);
+}
+
+#[test]
+fn failing() {
+    assert!(false);
+}
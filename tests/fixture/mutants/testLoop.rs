@@ -0,0 +1,18 @@
+fn next_multiple(n: u64, of: u64) -> u64 {
+    let mut i = n;
+    while i % of != 0 {
+        i += 1;
+    }
+    i
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiples() {
+        assert_eq!(next_multiple(7, 5), 10);
+        assert_eq!(next_multiple(10, 5), 10);
+    }
+}
@@ -0,0 +1,13 @@
+fn is_adult(age: u8) -> bool {
+    age >= 18
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adult() {
+        assert!(is_adult(30));
+    }
+}
@@ -0,0 +1,5 @@
+fn main() {
+    for line in 0..1000 {
+        println!("line {line}");
+    }
+}
@@ -0,0 +1,23 @@
+#[cfg(test)]
+mod tests {
+    mod parsing {
+        #[test]
+        fn parses() {
+            assert_eq!("1".parse::<i32>(), Ok(1));
+        }
+    }
+
+    mod validation {
+        #[test]
+        fn validates() {
+            assert!(false);
+        }
+    }
+
+    mod report {
+        #[test]
+        fn reports() {
+            assert!(true);
+        }
+    }
+}
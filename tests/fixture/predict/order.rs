@@ -0,0 +1,9 @@
+fn value(n: i32) -> i32 {
+    println!("{n}");
+    n
+}
+
+fn main() {
+    let sum = value(1) + value(2);
+    println!("sum = {sum}");
+}
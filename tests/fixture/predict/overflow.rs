@@ -0,0 +1,5 @@
+fn main() {
+    let x: u8 = 255;
+    let y = x + std::hint::black_box(1);
+    println!("{y}");
+}
@@ -0,0 +1,5 @@
+// The exercise was done, but broken afterwards.
+
+fn main() {
+    let x: i32 = "broken";
+}
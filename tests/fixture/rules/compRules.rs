@@ -0,0 +1,6 @@
+fn main() {
+    let number: Option<i32> = "42".parse().ok();
+    if number.is_some() {
+        println!("{}", number.unwrap());
+    }
+}
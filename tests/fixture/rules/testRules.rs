@@ -0,0 +1,22 @@
+trait Describe {
+    fn describe(&self) -> String;
+}
+
+struct Fruit;
+
+impl Describe for Fruit {
+    fn describe(&self) -> String {
+        String::from("sweet")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes() {
+        let fruit: Option<Fruit> = Some(Fruit);
+        assert_eq!(fruit.unwrap().describe(), "sweet");
+    }
+}
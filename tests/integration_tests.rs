@@ -3,6 +3,7 @@ use glob::glob;
 use predicates::boolean::PredicateBooleanExt;
 use std::fs::File;
 use std::io::Read;
+use std::path::Path;
 use std::process::Command;
 
 #[test]
@@ -13,11 +14,67 @@ fn runs_without_arguments() {
 
 #[test]
 fn fails_when_in_wrong_dir() {
+    let dir = std::env::temp_dir().join(format!("rustlings_wrong_dir_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
     Command::cargo_bin("rustlings")
         .unwrap()
-        .current_dir("tests/")
+        .current_dir(&dir)
         .assert()
-        .code(1);
+        .code(6);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn runs_from_a_subdirectory_of_the_course() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--names"])
+        .current_dir("exercises/00_intro")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("intro1"));
+}
+
+#[test]
+fn wrong_dir_points_to_the_course_in_a_subdirectory() {
+    let dir = std::env::temp_dir().join(format!("rustlings_parent_dir_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("rustlings")).unwrap();
+    std::fs::write(dir.join("rustlings/info.toml"), "").unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("list")
+        .current_dir(&dir)
+        .assert()
+        .code(6)
+        .stdout(predicates::str::contains("cd ./rustlings/"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn missing_exercise_files_are_reported() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compSuccess"])
+        .current_dir("tests/fixture/missing/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "The exercise file compDeleted.rs is missing.",
+        ))
+        .stdout(predicates::str::contains("no pristine copies"));
+}
+
+#[test]
+fn missing_exercise_files_are_reported_on_stderr_with_porcelain() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--porcelain", "list"])
+        .current_dir("tests/fixture/missing/")
+        .assert()
+        .stdout(predicates::str::starts_with("compSuccess\t"))
+        .stderr(predicates::str::contains(
+            "The exercise file compDeleted.rs is missing.",
+        ));
 }
 
 #[test]
@@ -37,7 +94,34 @@ fn verify_fails_if_some_fails() {
         .arg("verify")
         .current_dir("tests/fixture/failure")
         .assert()
-        .code(1);
+        .code(3);
+}
+
+#[test]
+fn verify_from_an_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--from", "testPartial"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(4)
+        .stdout(predicates::str::contains(
+            "Testing of testPartial.rs failed",
+        ))
+        .stdout(predicates::str::contains("compFailure").not());
+}
+
+#[test]
+fn verify_only_an_unknown_topic() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--only", "vecs"])
+        .current_dir("tests/fixture/success")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "Unknown topic 'vecs', the exercises have no topics",
+        ));
 }
 
 #[test]
@@ -57,7 +141,142 @@ fn run_single_compile_failure() {
         .args(["run", "compFailure"])
         .current_dir("tests/fixture/failure/")
         .assert()
-        .code(1);
+        .code(3);
+}
+
+#[test]
+fn run_several_exercises_with_a_glob() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "-j", "2", "comp*", "testSuccess"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("compSuccess"))
+        .stdout(predicates::str::contains("2 / 2 exercises passed"));
+}
+
+#[test]
+fn run_clippy_exercises_in_parallel() {
+    let dir = std::env::temp_dir().join(format!("rustlings_clippy_jobs_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut info = String::new();
+    for (name, value) in [("clippyPi", "3.14"), ("clippyE", "std::f64::consts::E")] {
+        info.push_str(&format!(
+            "[[exercises]]\nname = \"{name}\"\npath = \"{name}.rs\"\nmode = \"clippy\"\nhint = \"\"\n\n"
+        ));
+        std::fs::write(
+            dir.join(format!("{name}.rs")),
+            format!("fn main() {{\n    let value = {value};\n    println!(\"{{value}}\");\n}}\n"),
+        )
+        .unwrap();
+    }
+    std::fs::write(dir.join("info.toml"), info).unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--porcelain", "run", "-j", "2", "clippy*"])
+        .current_dir(&dir)
+        .assert()
+        .code(3)
+        .stdout("clippyPi\tcompilation-failed\nclippyE\tpassed\n");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn run_several_exercises_reports_the_failures() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compFailure", "test*"])
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .code(3)
+        .stdout(predicates::str::contains("Compilation failed"))
+        .stdout(predicates::str::contains("exercises passed"));
+}
+
+#[test]
+fn run_porcelain() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--porcelain", "run", "compFailure", "testPartial"])
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .code(3)
+        .stdout("compFailure\tcompilation-failed\ntestPartial\ttests-failed\n");
+}
+
+#[test]
+fn verify_porcelain() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--porcelain", "verify"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .success()
+        .stdout("compSuccess\tpassed\ntestSuccess\tpassed\n");
+}
+
+#[test]
+fn list_porcelain() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--porcelain", "list"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .success()
+        .stdout("compSuccess\tdone\tcompSuccess.rs\ntestSuccess\tdone\ttestSuccess.rs\n");
+}
+
+#[test]
+fn run_json() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--json", "run", "compFailure"])
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .code(3)
+        .stdout(predicates::str::contains(r#""passed": false"#))
+        .stdout(predicates::str::contains(
+            r#""result": "compilation-failed""#,
+        ))
+        .stdout(predicates::str::contains(r#""stderr": "error"#));
+}
+
+#[test]
+fn list_json() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--json", "list"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(r#""total": 2"#))
+        .stdout(predicates::str::contains(r#""status": "done""#))
+        .stdout(predicates::str::contains("Progress").not());
+}
+
+#[test]
+fn hint_json() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--json", "hint", "testFailure"])
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(r#""hint": "Hello!""#));
+}
+
+#[test]
+fn run_unmatched_glob() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "vecs*"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("No exercise matches 'vecs*'"));
 }
 
 #[test]
@@ -77,7 +296,18 @@ fn run_single_test_failure() {
         .args(["run", "testFailure"])
         .current_dir("tests/fixture/failure/")
         .assert()
-        .code(1);
+        .code(3);
+}
+
+#[test]
+fn run_single_test_partially_passing() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "testPartial"])
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .code(4)
+        .stdout(predicates::str::contains("1/2 tests passing"));
 }
 
 #[test]
@@ -145,6 +375,28 @@ fn get_hint_for_single_test() {
         .stdout("Hello!\n");
 }
 
+#[test]
+fn get_hint_ignoring_case_and_separators() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "Test-Failure"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(0)
+        .stdout("Hello!\n");
+}
+
+#[test]
+fn hint_suggests_similar_exercises() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "testFailur"])
+        .current_dir("tests/fixture/failure")
+        .assert()
+        .code(1)
+        .stdout("No exercise found for 'testFailur'! Did you mean 'testFailure'?\n");
+}
+
 #[test]
 fn all_exercises_require_confirmation() {
     for exercise in glob("exercises/**/*.rs").unwrap() {
@@ -267,3 +519,2010 @@ fn run_rustlings_list_without_done() {
         .success()
         .stdout(predicates::str::contains("Done").not());
 }
+
+#[test]
+fn verify_skips_optional_exercises() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir("tests/fixture/optional")
+        .assert()
+        .success();
+}
+
+#[test]
+fn run_rustlings_list_optional_exercises() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list"])
+        .current_dir("tests/fixture/optional")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("Optional")
+                .and(predicates::str::contains("Pending").not())
+                .and(predicates::str::contains("1 / 1")),
+        );
+}
+
+#[test]
+fn run_next_skips_optional_exercises() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "next"])
+        .current_dir("tests/fixture/optional")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "There are no more exercises to do next!",
+        ));
+}
+
+#[test]
+fn run_single_exercise_by_alias() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compiledSuccess"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "'compiledSuccess' has been renamed to 'compSuccess'",
+        ));
+}
+
+#[test]
+fn uninstall_asks_for_confirmation() {
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["uninstall", "--all"])
+        .current_dir("tests/fixture/state")
+        .write_stdin("n\n")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("info.toml").and(predicates::str::contains("Aborted")))
+        .stdout(predicates::str::contains(
+            "Pass --yes to confirm when the input or output is redirected",
+        ));
+}
+
+#[test]
+fn clean_removes_build_artifacts() {
+    let dir = std::env::temp_dir().join(format!("rustlings_clean_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join(".rustlings-target/debug")).unwrap();
+    std::fs::write(dir.join(".rustlings-target/debug/lib.rlib"), [0; 2048]).unwrap();
+    std::fs::write(dir.join("temp_12_34"), [0; 512]).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"a\"\npath = \"a.rs\"\nmode = \"compile\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("a.rs"), "fn main() {}\n").unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("clean")
+        .env_remove("CARGO_TARGET_DIR")
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Removed ./temp_12_34 (512 B)"))
+        .stdout(predicates::str::contains("Reclaimed 2.5 KiB"));
+    assert!(!dir.join(".rustlings-target").exists());
+    assert!(dir.join("a.rs").exists());
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("clean")
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Nothing to clean"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn run_shows_the_current_part() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "testParts"])
+        .current_dir("tests/fixture/parts/")
+        .assert()
+        .code(4)
+        .stdout(predicates::str::contains("Part 2 of 3: validation"));
+}
+
+#[test]
+fn verify_with_porcelain_records_the_parts() {
+    let dir = std::env::temp_dir().join(format!("rustlings_parts_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for file in ["info.toml", "testParts.rs"] {
+        std::fs::copy(Path::new("tests/fixture/parts").join(file), dir.join(file)).unwrap();
+    }
+
+    let assert = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--porcelain", "verify"])
+        .current_dir(&dir)
+        .assert();
+    let state = std::fs::read_to_string(dir.join(".rustlings-state.json")).unwrap_or_default();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert
+        .code(4)
+        .stdout("testParts\ttests-failed\n")
+        .stderr(predicates::str::contains("Achievement unlocked"));
+    assert!(state.contains("\"parsing\""), "{state}");
+}
+
+#[test]
+fn renamed_exercises_keep_their_progress() {
+    let dir = std::env::temp_dir().join(format!("rustlings_renamed_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for file in ["compMarked.rs", "rustlings.toml"] {
+        std::fs::copy(
+            Path::new("tests/fixture/nomarker").join(file),
+            dir.join(file),
+        )
+        .unwrap();
+    }
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"compMarked\"\npath = \"compMarked.rs\"\nmode = \"compile\"\naliases = [\"compOld\"]\nhint = \"\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join(".rustlings-state.json"),
+        r#"{"exercises": {"compOld": {"completed_at": 1}, "compGone": {"failures": 2}}, "bookmarks": ["compOld"]}"#,
+    )
+    .unwrap();
+
+    let assert = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--solved"])
+        .current_dir(&dir)
+        .env("XDG_CONFIG_HOME", "no-user-config")
+        .assert();
+    let state = std::fs::read_to_string(dir.join(".rustlings-state.json")).unwrap_or_default();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert
+        .success()
+        .stdout(predicates::str::contains("compMarked"))
+        .stdout(predicates::str::contains(
+            "The progress of 'compGone' in .rustlings-state.json belongs to no exercise of the course.",
+        ));
+    assert!(!state.contains("compOld"), "{state}");
+}
+
+#[test]
+fn eval_compares_the_value_of_the_expression() {
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["eval", "shadowing"])
+        .current_dir("tests/fixture/eval/")
+        .write_stdin("x + 1\nlet x = x * 3; x\n")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("That evaluates to 6"))
+        .stdout(predicates::str::contains("Solved shadowing!"));
+}
+
+#[test]
+fn eval_can_be_skipped() {
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["eval", "shadowing"])
+        .current_dir("tests/fixture/eval/")
+        .write_stdin("\n")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Solved 0 of 1"));
+}
+
+#[test]
+fn predict_compares_the_output() {
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["predict", "order"])
+        .current_dir("tests/fixture/predict/")
+        .write_stdin("1\n2\nsum = 3\n\n")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("order was predicted correctly"));
+}
+
+#[test]
+fn predict_shows_the_answer() {
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["predict", "overflow"])
+        .current_dir("tests/fixture/predict/")
+        .write_stdin("0\n\n")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "The answer for overflow is:\npanic",
+        ))
+        .stdout(predicates::str::contains("attempt to add with overflow"));
+}
+
+#[test]
+fn run_reports_unfilled_blanks() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compBlanks"])
+        .current_dir("tests/fixture/blanks/")
+        .assert()
+        .code(3)
+        .stdout(predicates::str::contains(
+            "compBlanks.rs still has 2 blanks",
+        ))
+        .stdout(predicates::str::contains("line 1: __???return_type__"));
+}
+
+#[test]
+fn run_reports_broken_rules() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compRules"])
+        .current_dir("tests/fixture/rules/")
+        .assert()
+        .code(3)
+        .stdout(predicates::str::contains(
+            "compRules.rs compiles, but it doesn't follow the rules",
+        ))
+        .stdout(predicates::str::contains(
+            "It must use a `match` expression.",
+        ))
+        .stdout(predicates::str::contains(
+            "line 4: It must not call `unwrap`.",
+        ));
+}
+
+#[test]
+fn rules_ignore_the_tests() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "testRules"])
+        .current_dir("tests/fixture/rules/")
+        .assert()
+        .success();
+}
+
+#[test]
+fn unknown_rules_are_course_errors() {
+    let dir = std::env::temp_dir().join(format!("rustlings_rules_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"compRules\"\npath = \"compRules.rs\"\nmode = \"compile\"\nhint = \"\"\nrules = { require = [\"if let\"] }\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("compRules.rs"), "fn main() {}\n").unwrap();
+
+    let assert = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compRules"])
+        .current_dir(&dir)
+        .assert();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert
+        .code(6)
+        .stdout(predicates::str::contains("Unknown construct `if let`"));
+}
+
+#[test]
+fn exercises_with_params_are_instantiated() {
+    let dir = std::env::temp_dir().join(format!("rustlings_variants_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"compParams\"\npath = \"compParams.rs\"\nmode = \"compile\"\nhint = \"\"\nparams = { count = { min = 3, max = 9 }, fruit = [\"apple\"] }\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("compParams.rs"),
+        "fn main() {\n    let count = {{count}};\n    assert!((3..=9).contains(&count));\n    println!(\"{count} {{ fruit }}s\");\n}\n",
+    )
+    .unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compParams"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(" apples"));
+    let variant = std::fs::read_to_string(dir.join("compParams.rs")).unwrap();
+    assert!(!variant.contains("{{"));
+    let state = std::fs::read_to_string(dir.join(".rustlings-state.json")).unwrap();
+    assert!(state.contains("variant_seed"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn share_writes_a_markdown_bundle() {
+    let output = std::env::temp_dir().join(format!("rustlings_share_{}.md", std::process::id()));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["share", "testPartial", "-o"])
+        .arg(&output)
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Your solution of testPartial"));
+    let bundle = std::fs::read_to_string(&output).unwrap();
+    assert!(bundle.starts_with("# testPartial.rs\n"));
+    assert!(bundle.contains("## My solution\n\n```rust\n"));
+    assert!(bundle.contains("It compiles, but it fails."));
+    let _ = std::fs::remove_file(&output);
+}
+
+#[test]
+fn verify_flags_removed_tests() {
+    let dir = std::env::temp_dir().join(format!("rustlings_tamper_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"testRemoved\"\npath = \"testRemoved.rs\"\nmode = \"test\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    let source = "#[cfg(test)]\nmod tests {\n    #[test]\n    fn passing() {}\n\n    #[test]\n    fn failing() {\n        assert!(false);\n    }\n}\n";
+    std::fs::write(dir.join("testRemoved.rs"), source).unwrap();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "Exercises"]);
+
+    std::fs::write(
+        dir.join("testRemoved.rs"),
+        source
+            .replace("assert!(false);", "")
+            .replace("#[test]\n    fn failing", "fn failing"),
+    )
+    .unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "These tests didn't run since they were removed, renamed, ignored or left out: failing",
+        ));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--porcelain", "verify"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout("testRemoved\ttests-removed\n");
+
+    // The tests are still there, but they aren't compiled anymore.
+    std::fs::write(
+        dir.join("testRemoved.rs"),
+        source
+            .replace("assert!(false);", "")
+            .replace("#[cfg(test)]", "#[cfg(any())]"),
+    )
+    .unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("left out: failing, passing"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn read_only_regions_can_be_reverted() {
+    let dir = std::env::temp_dir().join(format!("rustlings_regions_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"testRegion\"\npath = \"testRegion.rs\"\nmode = \"test\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    let source = "fn answer() -> i32 {\n    0\n}\n\n// READ-ONLY\n#[test]\nfn answers() {\n    assert_eq!(answer(), 42);\n}\n// END READ-ONLY\n";
+    std::fs::write(dir.join("testRegion.rs"), source).unwrap();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "Exercises"]);
+
+    std::fs::write(
+        dir.join("testRegion.rs"),
+        source.replace("    0\n", "    42\n").replace("42);", "0);"),
+    )
+    .unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "testRegion"])
+        .current_dir(&dir)
+        .assert()
+        .code(3)
+        .stdout(predicates::str::contains(
+            "The read-only region at line 5 of testRegion.rs must not be changed",
+        ));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["revert", "testRegion"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Restored a read-only region"));
+    assert_eq!(
+        std::fs::read_to_string(dir.join("testRegion.rs")).unwrap(),
+        source.replace("    0\n", "    42\n")
+    );
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "testRegion"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+
+    // Reverting can be undone like resetting.
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("undo")
+        .current_dir(&dir)
+        .assert()
+        .success();
+    assert_eq!(
+        std::fs::read_to_string(dir.join("testRegion.rs")).unwrap(),
+        source.replace("    0\n", "    42\n").replace("42);", "0);")
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn fails_with_outdated_toolchain() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir("tests/fixture/toolchain")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("rustup update"));
+}
+
+#[test]
+fn run_single_compile_success_piped_output_is_plain() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compSuccess"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .success()
+        .stdout(
+            predicates::str::contains("Successfully ran compSuccess.rs")
+                .and(predicates::str::contains("✅").not())
+                .and(predicates::str::contains("\x1b[").not()),
+        );
+}
+
+#[test]
+fn verify_stops_at_unanswered_quiz() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir("tests/fixture/quiz")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("answer the quiz first_quiz"));
+}
+
+#[test]
+fn quiz_with_wrong_answer() {
+    assert_cmd::Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("quiz")
+        .current_dir("tests/fixture/quiz")
+        .write_stdin("1\n")
+        .assert()
+        .code(1)
+        .stdout(
+            predicates::str::contains("Which keyword declares a variable?")
+                .and(predicates::str::contains("1 is not the correct answer")),
+        );
+}
+
+#[test]
+fn challenge_unknown_topic() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["challenge", "--topic", "unknown"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "Unknown topic 'unknown', the exercises have no topics",
+        ));
+}
+
+#[test]
+fn challenge_empty_topic() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["challenge", "--topic", ""])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Unknown topic ''"));
+}
+
+#[test]
+fn exam_writes_signed_report() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["exam", "--key", "secret"])
+        .current_dir("tests/fixture/exam")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Score: 1 / 2 exercises passed, 3 / 4 points (75.0 %)",
+        ))
+        .stdout(predicates::str::contains(
+            "The pass threshold of 70 % was reached",
+        ));
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["exam", "--key", "secret", "--check", "exam-report.json"])
+        .current_dir("tests/fixture/exam")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("The report is valid"));
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["exam", "--key", "wrong", "--check", "exam-report.json"])
+        .current_dir("tests/fixture/exam")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "The signature of exam-report.json is invalid",
+        ));
+}
+
+#[test]
+fn exam_disables_hints() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "pending_exercise"])
+        .current_dir("tests/fixture/exam")
+        .assert()
+        .success()
+        .stdout("Hints are disabled during the exam.\n");
+}
+
+#[test]
+fn exam_disables_hints_until_it_is_graded() {
+    let dir = std::env::temp_dir().join(format!("rustlings_exam_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for file in [
+        "info.toml",
+        "exam.toml",
+        "finished_exercise.rs",
+        "pending_exercise.rs",
+    ] {
+        std::fs::copy(Path::new("tests/fixture/exam").join(file), dir.join(file)).unwrap();
+    }
+    let hint = || {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(["hint", "pending_exercise"])
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+
+    hint();
+    std::fs::rename(dir.join("exam.toml"), dir.join("exam.toml.bak")).unwrap();
+    let during_exam = hint();
+    std::fs::rename(dir.join("exam.toml.bak"), dir.join("exam.toml")).unwrap();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["exam", "--key", "secret"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    std::fs::remove_file(dir.join("exam.toml")).unwrap();
+    let after_exam = hint();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(
+        String::from_utf8_lossy(&during_exam.stdout),
+        "Hints are disabled during the exam.\n"
+    );
+    assert!(String::from_utf8_lossy(&after_exam.stdout).contains("Some hint"));
+}
+
+#[test]
+fn list_achievements() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("achievements")
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("First steps"))
+        .stdout(predicates::str::contains("No hints needed"));
+}
+
+#[test]
+fn recommend_next_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("recommend")
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("rustlings run pending_exercise"));
+}
+
+#[test]
+fn explain_error_code_with_course_note() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["explain", "e0382"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Note from the course about E0382:\nRevisit the move semantics exercises.",
+        ))
+        .stdout(predicates::str::contains("moved"));
+}
+
+#[test]
+fn explain_invalid_error_code() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["explain", "borrow"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn glossary_lists_matching_terms_first() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["glossary", "OWNER"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(
+            "ownership\nEvery value has a single owner that drops it when going out of scope.\n\n\
+             borrow\nA reference to a value that doesn't take ownership of it.\n\n",
+        );
+}
+
+#[test]
+fn glossary_unknown_term() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["glossary", "monad"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1)
+        .stdout("The glossary doesn't mention 'monad'.\n");
+}
+
+#[test]
+fn recheck_finds_broken_exercises() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["recheck", "--all"])
+        .current_dir("tests/fixture/recheck")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "broken_exercise.rs was done but doesn't pass anymore.",
+        ));
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("list")
+        .current_dir("tests/fixture/recheck")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Broken"));
+}
+
+#[test]
+fn stats_show_totals() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("stats")
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Exercises done:       1 / 3"));
+}
+
+#[test]
+fn history_records_runs() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compSuccess"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .success();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["history", "--exercise", "compSuccess", "-n", "1"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("compSuccess").and(predicates::str::contains("passed")));
+}
+
+#[test]
+fn undo_without_reset() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("undo")
+        .current_dir("tests/fixture/optional")
+        .assert()
+        .code(1)
+        .stdout("There is nothing to undo\n");
+}
+
+#[test]
+fn undo_keeps_the_progress_of_other_exercises() {
+    let dir = std::env::temp_dir().join(format!("rustlings_undo_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"compA\"\npath = \"compA.rs\"\nmode = \"compile\"\nhint = \"\"\n\n[[exercises]]\nname = \"compB\"\npath = \"compB.rs\"\nmode = \"compile\"\nhint = \"Print it\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("compA.rs"), "fn main() {}\n").unwrap();
+    std::fs::write(dir.join("compB.rs"), "fn main() {}\n").unwrap();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .current_dir(&dir)
+            .output()
+            .unwrap()
+    };
+    git(&["init", "-q"]);
+    git(&["add", "."]);
+    git(&["commit", "-q", "-m", "Exercises"]);
+
+    let edited = "fn main() {\n    println!(\"edited\");\n}\n";
+    std::fs::write(dir.join("compA.rs"), edited).unwrap();
+    let rustlings = |args: &[&str]| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .current_dir(&dir)
+            .assert()
+            .success();
+    };
+    rustlings(&["reset", "compA"]);
+    rustlings(&["hint", "compB"]);
+    rustlings(&["undo"]);
+
+    assert_eq!(
+        std::fs::read_to_string(dir.join("compA.rs")).unwrap(),
+        edited
+    );
+    let state = std::fs::read_to_string(dir.join(".rustlings-state.json")).unwrap();
+    assert!(state.contains("\"hints_viewed\": 1"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn concurrent_commands_keep_each_others_progress() {
+    let dir = std::env::temp_dir().join(format!("rustlings_concurrent_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"compA\"\npath = \"compA.rs\"\nmode = \"compile\"\nhint = \"Some hint\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("compA.rs"), "fn main() {}\n").unwrap();
+
+    let children: Vec<_> = (0..8)
+        .map(|_| {
+            Command::cargo_bin("rustlings")
+                .unwrap()
+                .args(["hint", "compA"])
+                .current_dir(&dir)
+                .stdout(std::process::Stdio::null())
+                .spawn()
+                .unwrap()
+        })
+        .collect();
+    for mut child in children {
+        assert!(child.wait().unwrap().success());
+    }
+
+    let state = std::fs::read_to_string(dir.join(".rustlings-state.json")).unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(state.contains("\"hints_viewed\": 8"), "{state}");
+}
+
+#[test]
+fn bookmark_exercises() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["bookmark", "required_exercise"])
+        .current_dir("tests/fixture/optional")
+        .assert()
+        .success()
+        .stdout("Bookmarked required_exercise.\n");
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["list", "--bookmarked", "--names"])
+        .current_dir("tests/fixture/optional")
+        .assert()
+        .success()
+        .stdout(predicates::str::starts_with("required_exercise\nProgress"));
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["bookmark", "--remove", "required_exercise"])
+        .current_dir("tests/fixture/optional")
+        .assert()
+        .success();
+}
+
+#[test]
+fn verify_playlist() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--playlist", "done exercises"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["playlist", "list", "done exercises"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .success()
+        .stdout("finished_exercise\n");
+}
+
+#[test]
+fn verify_unknown_playlist() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["verify", "--playlist", "unknown"])
+        .current_dir("tests/fixture/state")
+        .assert()
+        .code(1)
+        .stdout("There is no playlist named 'unknown'\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn shuffled_watch_stops_at_unanswered_quiz() {
+    let dir = std::env::temp_dir().join(format!("rustlings_shuffle_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("exercises")).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"first\"\npath = \"exercises/first.rs\"\nmode = \"compile\"\nhint = \"\"\n\n\
+         [[exercises]]\nname = \"second\"\npath = \"exercises/second.rs\"\nmode = \"compile\"\nhint = \"\"\n\n\
+         [[quizzes]]\nname = \"first_quiz\"\nafter = \"first\"\nquestion = \"?\"\nchoices = [\"a\", \"b\"]\nanswer = 1\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("exercises/first.rs"), "fn main() {}\n").unwrap();
+    std::fs::write(
+        dir.join("exercises/second.rs"),
+        "// I AM NOT DONE\nfn main() { x }\n",
+    )
+    .unwrap();
+
+    // The seed 2 puts the second exercise before the first one
+    let mut watch = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["watch", "--shuffle=2"])
+        .current_dir(&dir)
+        .stdin(std::process::Stdio::null())
+        .stdout(File::create(dir.join("watch.txt")).unwrap())
+        .spawn()
+        .unwrap();
+    let output = || std::fs::read_to_string(dir.join("watch.txt")).unwrap();
+    assert!(wait_until(|| output().contains("Watching for changes")));
+    watch.kill().unwrap();
+    watch.wait().unwrap();
+
+    let output = output();
+    assert!(output.contains("answer the quiz"), "{output}");
+    assert!(!output.contains("second.rs"), "{output}");
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn graph_of_the_course() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["graph", "--format", "mermaid"])
+        .current_dir("tests/fixture/quiz")
+        .assert()
+        .success()
+        .stdout(predicates::str::starts_with("flowchart LR\n"))
+        .stdout(predicates::str::contains(
+            "first_exercise --> second_exercise\n",
+        ))
+        .stdout(predicates::str::contains(
+            "first_exercise --> quiz_first_quiz\n",
+        ));
+}
+
+#[test]
+fn run_test_with_output_from_config() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "testOutput"])
+        .current_dir("tests/fixture/config/")
+        .env("XDG_CONFIG_HOME", "no-user-config")
+        .assert()
+        .code(0)
+        .stdout(predicates::str::contains("THE CONFIG SHOWS THIS OUTPUT"));
+}
+
+#[test]
+fn run_warning_denied_by_course_config() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compWarning"])
+        .current_dir("tests/fixture/course/")
+        .env("XDG_CONFIG_HOME", "no-user-config")
+        .assert()
+        .code(3)
+        .stdout(predicates::str::contains("unused"));
+}
+
+#[test]
+fn run_with_rustflags() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--rustflags=-C no-such-option", "run", "compSuccess"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .code(3)
+        .stdout(predicates::str::contains("no-such-option"));
+}
+
+#[test]
+fn run_with_fast_check() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--fast-check", "run", "testSuccess"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .success();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--fast-check", "run", "compFailure"])
+        .current_dir("tests/fixture/failure/")
+        .assert()
+        .code(3);
+}
+
+#[test]
+fn run_custom_harness_success() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "harnessSuccess"])
+        .current_dir("tests/fixture/harness/")
+        .assert()
+        .success();
+}
+
+#[test]
+fn run_custom_harness_failure() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "harnessFailure"])
+        .current_dir("tests/fixture/harness/")
+        .assert()
+        .code(4);
+}
+
+#[test]
+fn run_in_sandbox() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--sandbox", "run", "compSuccess"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .success();
+}
+
+#[cfg(unix)]
+#[test]
+fn run_exceeding_memory_limit() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compHungry"])
+        .current_dir("tests/fixture/limits/")
+        .env("XDG_CONFIG_HOME", "no-user-config")
+        .assert()
+        .code(4)
+        .stdout(predicates::str::contains("resource limits"));
+}
+
+#[cfg(unix)]
+#[test]
+fn run_exceeding_cpu_time_limit() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compBusy"])
+        .current_dir("tests/fixture/limits/")
+        .env("XDG_CONFIG_HOME", "no-user-config")
+        .assert()
+        .code(5);
+}
+
+#[test]
+fn nextest_is_not_used_with_resource_limits() {
+    let dir = std::env::temp_dir().join(format!("rustlings_nextest_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join(".rustlings.toml"),
+        "[limits]\ncpu_seconds = 10\n\n[build]\ntest_runner = \"nextest\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"testLimited\"\npath = \"testLimited.rs\"\nmode = \"test\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("testLimited.rs"),
+        "#[cfg(test)]\nmod tests {\n    #[test]\n    fn passes() {}\n}\n",
+    )
+    .unwrap();
+
+    let assert = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "testLimited"])
+        .current_dir(&dir)
+        .env("XDG_CONFIG_HOME", "no-user-config")
+        .assert();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert.success().stdout(predicates::str::contains(
+        "cargo-nextest can't run the tests in the sandbox or with resource limits",
+    ));
+}
+
+#[test]
+fn negated_flags_override_the_config() {
+    let dir = std::env::temp_dir().join(format!("rustlings_no_flags_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join(".rustlings.toml"), "deny_warnings = true\n").unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"compWarning\"\npath = \"compWarning.rs\"\nmode = \"compile\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("compWarning.rs"),
+        "fn main() {\n    let x = 1;\n}\n",
+    )
+    .unwrap();
+
+    let run = |args: &[&str]| {
+        Command::cargo_bin("rustlings")
+            .unwrap()
+            .args(args)
+            .args(["run", "compWarning"])
+            .current_dir(&dir)
+            .env("XDG_CONFIG_HOME", "no-user-config")
+            .assert()
+    };
+    run(&[]).failure();
+    run(&["--no-deny-warnings"]).success();
+    // The last of the two flags wins
+    run(&["--no-deny-warnings", "--deny-warnings"]).failure();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn run_with_truncated_output() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compVerbose"])
+        .current_dir("tests/fixture/output/")
+        .env("XDG_CONFIG_HOME", "no-user-config")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "output truncated (showing last 1 KiB)",
+        ))
+        .stdout(predicates::str::contains("line 999"))
+        .stdout(predicates::str::contains("line 1\n").not());
+    let full_output =
+        std::fs::read_to_string("tests/fixture/output/.rustlings-output.txt").unwrap();
+    assert!(full_output.contains("line 1\n"));
+    assert!(full_output.contains("line 999"));
+}
+
+#[test]
+fn full_output_is_cut_off() {
+    let dir = std::env::temp_dir().join(format!("rustlings_flood_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"compFlood\"\npath = \"compFlood.rs\"\nmode = \"compile\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("compFlood.rs"),
+        "fn main() {\n    let line = \"x\".repeat(1023);\n    for _ in 0..32 * 1024 {\n        println!(\"{line}\");\n    }\n}\n",
+    )
+    .unwrap();
+
+    let assert = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compFlood"])
+        .current_dir(&dir)
+        .env("XDG_CONFIG_HOME", "no-user-config")
+        .assert();
+    let full_output = std::fs::read_to_string(dir.join(".rustlings-output.txt")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert.success();
+    assert!(full_output.len() < 17 * 1024 * 1024);
+    assert!(full_output.ends_with("[The output was cut off after 16 MiB]\n"));
+}
+
+#[test]
+fn errors_counts_error_codes() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compMoved"])
+        .current_dir("tests/fixture/errors/")
+        .assert()
+        .code(3);
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("errors")
+        .current_dir("tests/fixture/errors/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("E0382"))
+        .stdout(predicates::str::contains("compMoved"));
+}
+
+#[test]
+fn hint_locked_by_course_config() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "compWarning"])
+        .current_dir("tests/fixture/course/")
+        .env("XDG_CONFIG_HOME", "no-user-config")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "The hint unlocks after 1 failed attempts (0 so far)",
+        ));
+}
+
+#[test]
+fn verify_without_not_done_marker() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir("tests/fixture/nomarker/")
+        .env("XDG_CONFIG_HOME", "no-user-config")
+        .assert()
+        .success();
+}
+
+#[test]
+fn run_warning_allowed_by_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compWarningAllowed"])
+        .current_dir("tests/fixture/course/")
+        .env("XDG_CONFIG_HOME", "no-user-config")
+        .assert()
+        .success();
+}
+
+#[test]
+fn run_lint_allowed_by_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "compUnusedAllowed"])
+        .current_dir("tests/fixture/course/")
+        .env("XDG_CONFIG_HOME", "no-user-config")
+        .assert()
+        .success();
+}
+
+#[test]
+fn compare_with_solution() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["compare", "compSolved"])
+        .current_dir("tests/fixture/compare/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "+     if let Some(number) = number {",
+        ))
+        .stdout(predicates::str::contains(
+            "`match`: 1 in your solution, 0 in the official one",
+        ))
+        .stdout(predicates::str::contains("Print the number").not());
+}
+
+#[test]
+fn compare_requires_passing_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["compare", "compUnsolved"])
+        .current_dir("tests/fixture/compare/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Solve compUnsolved.rs first"));
+}
+
+#[test]
+fn compare_requires_done_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["compare", "compMarked"])
+        .current_dir("tests/fixture/compare/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Solve compMarked.rs first"));
+}
+
+#[test]
+fn run_mutants_survive_weak_tests() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "testWeak"])
+        .current_dir("tests/fixture/mutants/")
+        .assert()
+        .code(4)
+        .stdout(predicates::str::contains(
+            "Your tests still pass if line 2 of the implementation is changed",
+        ))
+        .stdout(predicates::str::contains("age > 18"));
+}
+
+#[test]
+fn run_mutants_killed_by_strong_tests() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "testStrong"])
+        .current_dir("tests/fixture/mutants/")
+        .assert()
+        .success();
+}
+
+#[test]
+fn run_mutants_that_loop_forever_are_stopped() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "testLoop"])
+        .current_dir("tests/fixture/mutants/")
+        .assert()
+        .success();
+}
+
+#[test]
+fn dev_run_all_solutions_passing() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["dev", "run-all-solutions"])
+        .current_dir("tests/fixture/compare/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("3 / 3 solutions passed"));
+}
+
+#[test]
+fn dev_run_all_solutions_lints_clippy_solutions() {
+    let dir = std::env::temp_dir().join(format!("rustlings_clippy_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("exercises")).unwrap();
+    std::fs::create_dir_all(dir.join("solutions")).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"clippyPi\"\npath = \"exercises/clippyPi.rs\"\nmode = \"clippy\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("exercises/clippyPi.rs"),
+        "fn main() {\n    let pi = 3.14;\n    println!(\"{pi}\");\n}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("solutions/clippyPi.rs"),
+        "fn main() {\n    let pi = std::f64::consts::PI;\n    println!(\"{pi}\");\n}\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "clippyPi"])
+        .current_dir(&dir)
+        .assert()
+        .code(3);
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["dev", "run-all-solutions"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("1 / 1 solutions passed"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn dev_run_all_solutions_failing() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["dev", "run-all-solutions"])
+        .current_dir("tests/fixture/dev/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "The solution of compBroken failed",
+        ))
+        .stdout(predicates::str::contains("mismatched types"))
+        .stdout(predicates::str::contains(
+            "These exercises don't have a solution: compMissing",
+        ))
+        .stdout(predicates::str::contains("1 / 2 solutions passed"));
+}
+
+#[test]
+fn dev_check_unmodified_exercises() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["dev", "check"])
+        .current_dir("tests/fixture/dev/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "3 / 3 exercises need to be edited to pass",
+        ));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["dev", "check"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains(
+            "compSuccess.rs passes without any edits",
+        ));
+}
+
+#[test]
+fn dev_new_expands_template() {
+    let dir = std::env::temp_dir().join(format!("rustlings_dev_new_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("info.toml"), "course_name = \"Test course\"\n").unwrap();
+    std::fs::write(
+        dir.join("template.rs"),
+        "// {{exercise_name}} ({{topic}}) of {{course_name}}\n// {{hint}} first\n// {{hint}} second\nfn main() {}\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args([
+            "dev",
+            "new",
+            "exercises/05_vecs/vecs3.rs",
+            "--template",
+            "template.rs",
+        ])
+        .current_dir(&dir)
+        .assert()
+        .success();
+
+    let source = std::fs::read_to_string(dir.join("exercises/05_vecs/vecs3.rs")).unwrap();
+    assert_eq!(
+        source,
+        "// vecs3 (vecs) of Test course\n// Hint 1: first\n// Hint 2: second\nfn main() {}\n"
+    );
+    let info = std::fs::read_to_string(dir.join("info.toml")).unwrap();
+    assert!(info
+        .contains("name = \"vecs3\"\npath = \"exercises/05_vecs/vecs3.rs\"\nmode = \"compile\""));
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["dev", "new", "exercises/05_vecs/vecs3.rs"])
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("already exists"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn path_arguments_are_relative_to_the_subdirectory() {
+    let dir = std::env::temp_dir().join(format!("rustlings_subdir_paths_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("exercises/05_vecs")).unwrap();
+    std::fs::write(dir.join("info.toml"), "").unwrap();
+    std::fs::write(dir.join("template.rs"), "fn main() {}\n").unwrap();
+    let subdir = dir.join("exercises/05_vecs");
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["dev", "new", "vecs3.rs", "--template", "../../template.rs"])
+        .current_dir(&subdir)
+        .assert()
+        .success();
+    assert!(subdir.join("vecs3.rs").exists());
+    let info = std::fs::read_to_string(dir.join("info.toml")).unwrap();
+    assert!(
+        info.contains("path = \"exercises/05_vecs/vecs3.rs\""),
+        "{info}"
+    );
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["share", "vecs3", "-o", "shared.md"])
+        .current_dir(&subdir)
+        .assert()
+        .success();
+    assert!(subdir.join("shared.md").exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn export_workbook_with_solutions() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["export", "--solutions"])
+        .current_dir("tests/fixture/compare/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("### compSolved"))
+        .stdout(predicates::str::contains("Solution:"))
+        .stdout(predicates::str::contains("if let Some(number) = number {"));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["export", "--format", "html"])
+        .current_dir("tests/fixture/compare/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("<h3>compUnsolved</h3>"))
+        .stdout(predicates::str::contains("Solution:").not());
+}
+
+#[test]
+fn report_progress_as_markdown() {
+    let output = std::env::temp_dir().join(format!("rustlings_report_{}.md", std::process::id()));
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["report", "--output"])
+        .arg(&output)
+        .current_dir("tests/fixture/state/")
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("The report was written to"));
+
+    let report = std::fs::read_to_string(&output).unwrap();
+    let _ = std::fs::remove_file(&output);
+    assert!(report.starts_with("# Progress report: Rustlings\n"));
+    assert!(report.contains("| Topic | Done | Completion | Time spent | Hints viewed |"));
+    assert!(report.contains("| Exercise | Status |"));
+}
+
+#[test]
+fn reset_of_another_course_ignores_the_embedded_copies() {
+    let dir = std::env::temp_dir().join(format!("rustlings_other_course_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("exercises/00_intro")).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"intro1\"\npath = \"exercises/00_intro/intro1.rs\"\nmode = \"compile\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    let exercise = dir.join("exercises/00_intro/intro1.rs");
+    std::fs::write(&exercise, "fn main() {}\n").unwrap();
+
+    let assert = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["reset", "intro1"])
+        .current_dir(&dir)
+        .assert();
+    let content = std::fs::read_to_string(&exercise).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert
+        .failure()
+        .stdout(predicates::str::contains("There is no pristine copy"));
+    assert_eq!(content, "fn main() {}\n");
+}
+
+#[test]
+fn reset_from_embedded_copy_with_preview() {
+    let dir = std::env::temp_dir().join(format!("rustlings_reset_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("exercises/00_intro")).unwrap();
+    std::fs::copy("info.toml", dir.join("info.toml")).unwrap();
+    let exercise = dir.join("exercises/00_intro/intro1.rs");
+    std::fs::write(&exercise, "fn main() {}\n").unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["reset", "intro1", "--dry-run"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("discards these changes"))
+        .stdout(predicates::str::contains("- fn main() {}"));
+    assert_eq!(
+        std::fs::read_to_string(&exercise).unwrap(),
+        "fn main() {}\n"
+    );
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["diff", "intro1"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("+ fn main() {}"))
+        .stdout(predicates::str::contains("- // intro1.rs"));
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["reset", "intro1"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("intro1.rs was reset"));
+    assert!(std::fs::read_to_string(&exercise)
+        .unwrap()
+        .starts_with("// intro1.rs"));
+    assert_eq!(
+        std::fs::read_dir(dir.join(".rustlings-backups"))
+            .unwrap()
+            .count(),
+        1
+    );
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["restore", "intro1"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains(
+            "Restored exercises/00_intro/intro1.rs",
+        ));
+    assert_eq!(
+        std::fs::read_to_string(&exercise).unwrap(),
+        "fn main() {}\n"
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn diff_unchanged_exercise() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["diff", "intro1"])
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("You didn't change"));
+}
+
+#[test]
+fn per_user_state_migrates_workspace_state() {
+    let dir = std::env::temp_dir().join(format!("rustlings_per_user_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let workspace = dir.join("workspace");
+    std::fs::create_dir_all(workspace.join("exercises")).unwrap();
+    std::fs::write(
+        workspace.join("info.toml"),
+        "[[exercises]]\nname = \"intro1\"\npath = \"exercises/intro1.rs\"\nmode = \"compile\"\nhint = \"Read it\"\n",
+    )
+    .unwrap();
+    std::fs::write(workspace.join("exercises/intro1.rs"), "fn main() {}\n").unwrap();
+    std::fs::write(workspace.join(".rustlings.toml"), "per_user_state = true\n").unwrap();
+    std::fs::write(workspace.join(".rustlings-state.json"), "{}").unwrap();
+    std::fs::write(workspace.join(".rustlings-history.jsonl"), "").unwrap();
+    std::fs::write(workspace.join(".rustlings-undo.json"), "[]").unwrap();
+    std::fs::create_dir(workspace.join(".rustlings-backups")).unwrap();
+    std::fs::write(workspace.join(".rustlings-backups/intro1-1.rs"), "").unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["hint", "intro1"])
+        .current_dir(&workspace)
+        .env("XDG_DATA_HOME", dir.join("data"))
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("Your progress was moved"));
+    for file in [
+        ".rustlings-state.json",
+        ".rustlings-history.jsonl",
+        ".rustlings-undo.json",
+        ".rustlings-backups",
+    ] {
+        assert!(!workspace.join(file).exists(), "{file}");
+    }
+    let user_dir = std::fs::read_dir(dir.join("data/rustlings"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    let state = std::fs::read_to_string(user_dir.join("state.json")).unwrap();
+    assert!(state.contains("\"intro1\""));
+    assert!(user_dir.join("history.jsonl").exists());
+    assert!(user_dir.join("undo.json").exists());
+    assert!(user_dir.join("backups/intro1-1.rs").exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn verify_refuses_to_run_twice() {
+    let dir = std::env::temp_dir().join(format!("rustlings_lock_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("exercises")).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"intro1\"\npath = \"exercises/intro1.rs\"\nmode = \"compile\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("exercises/intro1.rs"), "fn main() {}\n").unwrap();
+    // The test process plays the other rustlings that is still running.
+    let lock = dir.join(".rustlings-state.lock");
+    std::fs::write(&lock, std::process::id().to_string()).unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("verify")
+        .current_dir(&dir)
+        .assert()
+        .code(1)
+        .stdout(predicates::str::contains("Another rustlings"));
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--force", "verify"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    assert!(!lock.exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn log_file_records_the_compilation() {
+    let log_file = std::env::temp_dir().join(format!("rustlings_log_{}.txt", std::process::id()));
+    let _ = std::fs::remove_file(&log_file);
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("--log-file")
+        .arg(&log_file)
+        .args(["run", "compSuccess"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .success();
+    let log = std::fs::read_to_string(&log_file).unwrap();
+    let _ = std::fs::remove_file(&log_file);
+    assert!(log.contains("rustlings::exercise: Compiling compSuccess succeeded"));
+    assert!(log.contains("rustlings::exercise: Running compSuccess exited with"));
+}
+
+#[test]
+fn verbose_logs_to_stderr() {
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--verbose", "run", "compSuccess"])
+        .current_dir("tests/fixture/success/")
+        .assert()
+        .success()
+        .stderr(predicates::str::contains("Compiling compSuccess succeeded"));
+}
+
+// Waits up to 10 seconds for the condition
+#[cfg(unix)]
+fn wait_until(condition: impl Fn() -> bool) -> bool {
+    for _ in 0..200 {
+        if condition() {
+            return true;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    false
+}
+
+// Starts watch mode in a new Rustlings directory with the exercise.
+// Its output is written to `watch.txt`.
+#[cfg(unix)]
+fn spawn_watch(name: &str, exercise: &str) -> (std::path::PathBuf, std::process::Child) {
+    let dir = std::env::temp_dir().join(format!("rustlings_{name}_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("exercises")).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        format!("[[exercises]]\nname = \"{name}\"\npath = \"exercises/{name}.rs\"\nmode = \"compile\"\nhint = \"\"\n"),
+    )
+    .unwrap();
+    std::fs::write(dir.join(format!("exercises/{name}.rs")), exercise).unwrap();
+
+    let watch = Command::cargo_bin("rustlings")
+        .unwrap()
+        .arg("watch")
+        .current_dir(&dir)
+        .stdin(std::process::Stdio::null())
+        .stdout(File::create(dir.join("watch.txt")).unwrap())
+        .spawn()
+        .unwrap();
+    (dir, watch)
+}
+
+#[cfg(unix)]
+#[test]
+fn dev_watch_reloads_info_toml_after_each_rename_save() {
+    let dir = std::env::temp_dir().join(format!("rustlings_dev_watch_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("exercises")).unwrap();
+    let info = |names: &[&str]| {
+        names
+            .iter()
+            .map(|name| format!("[[exercises]]\nname = \"{name}\"\npath = \"exercises/{name}.rs\"\nmode = \"compile\"\nhint = \"\"\n"))
+            .collect::<String>()
+    };
+    std::fs::write(dir.join("info.toml"), info(&["first"])).unwrap();
+    std::fs::write(dir.join("exercises/first.rs"), "fn main() {}\n").unwrap();
+    std::fs::write(dir.join("exercises/second.rs"), "fn main() {}\n").unwrap();
+
+    let mut watch = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["dev", "watch"])
+        .current_dir(&dir)
+        .stdin(std::process::Stdio::null())
+        .stdout(File::create(dir.join("watch.txt")).unwrap())
+        .spawn()
+        .unwrap();
+    let output = dir.join("watch.txt");
+    let count = |text: &str| {
+        std::fs::read_to_string(&output)
+            .unwrap()
+            .matches(text)
+            .count()
+    };
+    assert!(wait_until(|| count("Watching the course") == 1));
+
+    // Save info.toml the way many editors do, by renaming a new file over it
+    let rename_save = |names: &[&str]| {
+        std::fs::write(dir.join("info.toml.new"), info(names)).unwrap();
+        std::fs::rename(dir.join("info.toml.new"), dir.join("info.toml")).unwrap();
+    };
+    rename_save(&["first", "second"]);
+    assert!(wait_until(|| count("All 2 exercises") == 1));
+    rename_save(&["first"]);
+    assert!(wait_until(|| count("All 1 exercises") == 2));
+
+    watch.kill().unwrap();
+    watch.wait().unwrap();
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[cfg(unix)]
+fn send_signal(child: &std::process::Child, signal: &str) {
+    Command::new("kill")
+        .args([signal, &child.id().to_string()])
+        .status()
+        .unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn interrupted_watch_releases_the_lock() {
+    let (dir, mut watch) = spawn_watch("interrupt", "// I AM NOT DONE\nfn main() { x }\n");
+    let output = dir.join("watch.txt");
+    assert!(wait_until(|| std::fs::read_to_string(&output)
+        .unwrap()
+        .contains("Watching for changes")));
+    assert!(dir.join(".rustlings-state.lock").exists());
+
+    send_signal(&watch, "-INT");
+    assert_eq!(watch.wait().unwrap().code(), Some(130));
+    assert!(!dir.join(".rustlings-state.lock").exists());
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn interrupt_cancels_the_running_exercise_in_watch_mode() {
+    let (dir, mut watch) = spawn_watch(
+        "cancel",
+        "// I AM NOT DONE\nfn main() {\n    std::fs::write(\"cancel.started\", \"\").unwrap();\n    loop {\n        std::thread::sleep(std::time::Duration::from_millis(10));\n    }\n}\n",
+    );
+    let output = dir.join("watch.txt");
+    assert!(wait_until(|| dir.join("cancel.started").exists()));
+
+    send_signal(&watch, "-INT");
+    assert!(wait_until(|| std::fs::read_to_string(&output)
+        .unwrap()
+        .contains(
+            "Stopped exercises/cancel.rs since Ctrl+C was pressed"
+        )));
+    assert!(watch.try_wait().unwrap().is_none());
+
+    send_signal(&watch, "-INT");
+    assert_eq!(watch.wait().unwrap().code(), Some(130));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn terminated_run_stops_the_exercise() {
+    let dir = std::env::temp_dir().join(format!("rustlings_terminate_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("exercises")).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"endless\"\npath = \"exercises/endless.rs\"\nmode = \"compile\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("exercises/endless.rs"),
+        "fn main() {\n    let child = std::process::Command::new(\"sleep\").arg(\"60\").spawn().unwrap();\n    std::fs::write(\"endless.pid\", format!(\"{} {}\", std::process::id(), child.id())).unwrap();\n    loop {\n        std::thread::sleep(std::time::Duration::from_millis(10));\n    }\n}\n",
+    )
+    .unwrap();
+
+    let mut run = Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "endless"])
+        .current_dir(&dir)
+        .stdout(std::process::Stdio::null())
+        .spawn()
+        .unwrap();
+    let pid_file = dir.join("endless.pid");
+    assert!(wait_until(
+        || std::fs::read_to_string(&pid_file).is_ok_and(|pids| !pids.is_empty())
+    ));
+    let pids = std::fs::read_to_string(&pid_file).unwrap();
+    send_signal(&run, "-TERM");
+    assert_eq!(run.wait().unwrap().code(), Some(143));
+
+    // The exercise and the process that it started are stopped.
+    // A stopped process can stay a zombie if nothing reaps it in a container.
+    let is_running = |pid: &str| {
+        let output = Command::new("ps")
+            .args(["-o", "stat=", "-p", pid])
+            .output()
+            .unwrap();
+        let stat = String::from_utf8_lossy(&output.stdout);
+        !stat.trim().is_empty() && !stat.trim().starts_with('Z')
+    };
+    for pid in pids.split(' ') {
+        assert!(wait_until(|| !is_running(pid)));
+    }
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[cfg(unix)]
+#[test]
+fn processes_started_by_a_finished_exercise_are_stopped() {
+    let dir = std::env::temp_dir().join(format!("rustlings_orphans_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"spawner\"\npath = \"spawner.rs\"\nmode = \"compile\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("spawner.rs"),
+        "fn main() {\n    let child = std::process::Command::new(\"sleep\").arg(\"60\").spawn().unwrap();\n    std::fs::write(\"spawner.pid\", child.id().to_string()).unwrap();\n}\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "spawner"])
+        .current_dir(&dir)
+        .assert()
+        .success();
+    let pid = std::fs::read_to_string(dir.join("spawner.pid")).unwrap();
+    let is_running = || {
+        let output = Command::new("ps")
+            .args(["-o", "stat=", "-p", &pid])
+            .output()
+            .unwrap();
+        let stat = String::from_utf8_lossy(&output.stdout);
+        !stat.trim().is_empty() && !stat.trim().starts_with('Z')
+    };
+    assert!(wait_until(|| !is_running()));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn ascii_colorblind_list_marks_the_status() {
+    let dir = std::env::temp_dir().join(format!("rustlings_ascii_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(dir.join("exercises")).unwrap();
+    std::fs::write(
+        dir.join("info.toml"),
+        "[[exercises]]\nname = \"done1\"\npath = \"exercises/done1.rs\"\nmode = \"compile\"\nhint = \"\"\n\n\
+         [[exercises]]\nname = \"pending1\"\npath = \"exercises/pending1.rs\"\nmode = \"compile\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join("exercises/done1.rs"), "fn main() {}\n").unwrap();
+    std::fs::write(
+        dir.join("exercises/pending1.rs"),
+        "// I AM NOT DONE\nfn main() {}\n",
+    )
+    .unwrap();
+    std::fs::write(dir.join(".rustlings.toml"), "theme = \"colorblind\"\n").unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["--ascii", "list"])
+        .current_dir(&dir)
+        .assert()
+        .success()
+        .stdout(predicates::str::contains("+ Done"))
+        .stdout(predicates::str::contains("x Pending"));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn run_clippy_exercise_inside_a_cargo_workspace() {
+    let dir = std::env::temp_dir().join(format!("rustlings_workspace_{}", std::process::id()));
+    let course = dir.join("course");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(course.join("exercises/22_clippy")).unwrap();
+    std::fs::write(dir.join("Cargo.toml"), "[workspace]\nmembers = []\n").unwrap();
+    std::fs::write(
+        course.join("info.toml"),
+        "[[exercises]]\nname = \"clippy1\"\npath = \"exercises/22_clippy/clippy1.rs\"\nmode = \"clippy\"\nhint = \"\"\n",
+    )
+    .unwrap();
+    std::fs::write(
+        course.join("exercises/22_clippy/clippy1.rs"),
+        "fn main() {\n    println!(\"{}\", 1 + 1);\n}\n",
+    )
+    .unwrap();
+
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["run", "clippy1"])
+        .current_dir(&course)
+        .assert()
+        .success();
+    Command::cargo_bin("rustlings")
+        .unwrap()
+        .args(["dev", "check"])
+        .current_dir(&course)
+        .assert()
+        .stdout(predicates::str::contains(r#"exclude = ["course"]"#));
+    let _ = std::fs::remove_dir_all(&dir);
+}